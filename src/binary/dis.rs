@@ -8,6 +8,8 @@ pub fn dissasemble<R: io::Read + io::Seek>(r: &mut R) -> super::Result<()> {
     super::read_magic(r)?;
     let constants = super::read_constant_pool(r)?;
     let function_table = super::read_function_table(r)?;
+    let (layouts, result_layout) = super::read_layout_table(r)?;
+    let exports = super::read_export_table(r)?;
 
     println!("         ╥");
 
@@ -37,6 +39,36 @@ pub fn dissasemble<R: io::Read + io::Seek>(r: &mut R) -> super::Result<()> {
         );
     }
 
+    println!(
+        "         ║ :: {} {}",
+        "layout table size".underline(),
+        layouts.len().to_string().bold()
+    );
+    for (i, layout) in layouts.iter().enumerate() {
+        println!(
+            "         ║      #{i} = {}",
+            layout.to_string().bold().cyan()
+        );
+    }
+    println!(
+        "         ║ :: {} #{}",
+        "result layout".underline(),
+        result_layout.to_string().bold()
+    );
+
+    println!(
+        "         ║ :: {} {}",
+        "export table size".underline(),
+        exports.len().to_string().bold()
+    );
+    for (name, layout) in &exports {
+        println!(
+            "         ║      {} : #{}",
+            name.bold().bright_blue(),
+            layout.to_string().bold()
+        );
+    }
+
     println!("         ║ ");
 
     let orig = r.stream_position()?;
@@ -75,6 +107,11 @@ pub fn dissasemble<R: io::Read + io::Seek>(r: &mut R) -> super::Result<()> {
             Op::index(count) => print!("{:>14} {}", "index", count.to_string().bold()),
             Op::index_big(count) => print!("{:>14} {}", "index_big", count.to_string().bold()),
             Op::index_dyn => print!("{:>14}", "index_dyn"),
+            Op::array_len => print!("{:>14}", "array_len"),
+            Op::array_set => print!("{:>14}", "array_set"),
+            Op::array_push => print!("{:>14}", "array_push"),
+            Op::array_pop => print!("{:>14}", "array_pop"),
+            Op::array_slice => print!("{:>14}", "array_slice"),
             Op::spill(offset) => {
                 print!("{:>14} {}", "spill", offset.to_string().bold())
             }
@@ -98,6 +135,7 @@ pub fn dissasemble<R: io::Read + io::Seek>(r: &mut R) -> super::Result<()> {
             Op::le => print!("{:>14}", "le"),
             Op::gt => print!("{:>14}", "gt"),
             Op::ge => print!("{:>14}", "ge"),
+            Op::to_str => print!("{:>14}", "to_str"),
             Op::sin => print!("{:>14}", "sin"),
             Op::cos => print!("{:>14}", "cos"),
             Op::tan => print!("{:>14}", "tan"),
@@ -128,11 +166,16 @@ pub fn dissasemble<R: io::Read + io::Seek>(r: &mut R) -> super::Result<()> {
             }
             Op::do_frame => print!("{:>14}", "do_frame"),
             Op::end_frame => print!("{:>14}", "end_frame"),
+            Op::try_begin(pos) => {
+                print!("{:>14} -> <{:0>8}>", "try_begin", pos.to_string().bold())
+            }
+            Op::try_end => print!("{:>14}", "try_end"),
             Op::call(count) => print!("{:>14} [{}]", "call", count.to_string().bold()),
             Op::ret => print!("{:>14}", "ret"),
             Op::pop => print!("{:>14}", "pop"),
             Op::pop_offset(offset) => print!("{:>14} {}", "pop_offset", offset.to_string().bold()),
             Op::dup => print!("{:>14}", "dup"),
+            Op::raise => print!("{:>14}", "raise"),
             Op::panic => print!("{:>14}", "panic"),
         }
         println!();
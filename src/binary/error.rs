@@ -8,6 +8,8 @@ pub enum Error {
     MagicMismatch,
     IllegalOpcode(u8),
     IllegalValue,
+    // closures only exist within a running program
+    UnserializableValue,
     IO(io::Error),
     Utf8(string::FromUtf8Error),
 }
@@ -0,0 +1,13 @@
+#![allow(non_upper_case_globals)]
+
+pub const any: u8 = 0;
+pub const int: u8 = 1;
+pub const float: u8 = 2;
+pub const bool: u8 = 3;
+pub const string: u8 = 4;
+pub const func: u8 = 5;
+pub const tuple: u8 = 6;
+pub const array: u8 = 7;
+pub const record: u8 = 8;
+pub const union: u8 = 9;
+pub const list: u8 = 10;
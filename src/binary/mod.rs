@@ -3,14 +3,16 @@ pub use error::{Error, Result};
 
 pub mod value;
 
+pub mod layout;
+
 pub mod opcode;
 pub use opcode::Opcode;
 
-use crate::exe::Value;
+use crate::exe::{Layout, LayoutID, LayoutTable, Value};
 use byteorder::{LE, ReadBytesExt, WriteBytesExt};
 use std::{
-    collections::{BTreeMap, HashMap},
-    io::{self},
+    collections::BTreeMap,
+    io::{self, Read},
 };
 
 pub const MAGIC: &[u8] = "exemarin".as_bytes();
@@ -29,6 +31,22 @@ pub fn write_magic<W: io::Write>(w: &mut W) -> Result<()> {
     Ok(())
 }
 
+pub const SNAPSHOT_MAGIC: &[u8] = "snpmarin".as_bytes();
+
+pub fn read_snapshot_magic<R: io::Read>(r: &mut R) -> Result<()> {
+    let mut buf = [0; SNAPSHOT_MAGIC.len()];
+    r.read_exact(&mut buf)?;
+    match buf == SNAPSHOT_MAGIC {
+        true => Ok(()),
+        false => Err(Error::MagicMismatch),
+    }
+}
+
+pub fn write_snapshot_magic<W: io::Write>(w: &mut W) -> Result<()> {
+    w.write_all(SNAPSHOT_MAGIC)?;
+    Ok(())
+}
+
 pub fn read_opcode<R: io::Read>(r: &mut R) -> Result<Opcode> {
     match r.read_u8()? {
         opcode::load_fun => Ok(Opcode::load_fun(r.read_u32::<LE>()?)),
@@ -39,6 +57,11 @@ pub fn read_opcode<R: io::Read>(r: &mut R) -> Result<Opcode> {
         opcode::index => Ok(Opcode::index(r.read_u8()?)),
         opcode::index_big => Ok(Opcode::index_big(r.read_u64::<LE>()?)),
         opcode::index_dyn => Ok(Opcode::index_dyn),
+        opcode::array_len => Ok(Opcode::array_len),
+        opcode::array_set => Ok(Opcode::array_set),
+        opcode::array_push => Ok(Opcode::array_push),
+        opcode::array_pop => Ok(Opcode::array_pop),
+        opcode::array_slice => Ok(Opcode::array_slice),
         opcode::spill => Ok(Opcode::spill(r.read_u16::<LE>()?)),
         opcode::add => Ok(Opcode::add),
         opcode::sub => Ok(Opcode::sub),
@@ -60,6 +83,7 @@ pub fn read_opcode<R: io::Read>(r: &mut R) -> Result<Opcode> {
         opcode::le => Ok(Opcode::le),
         opcode::gt => Ok(Opcode::gt),
         opcode::ge => Ok(Opcode::ge),
+        opcode::to_str => Ok(Opcode::to_str),
         opcode::sin => Ok(Opcode::sin),
         opcode::cos => Ok(Opcode::cos),
         opcode::tan => Ok(Opcode::tan),
@@ -77,11 +101,14 @@ pub fn read_opcode<R: io::Read>(r: &mut R) -> Result<Opcode> {
         opcode::jump_ne => Ok(Opcode::jump_ne(r.read_u32::<LE>()?)),
         opcode::do_frame => Ok(Opcode::do_frame),
         opcode::end_frame => Ok(Opcode::end_frame),
+        opcode::try_begin => Ok(Opcode::try_begin(r.read_u32::<LE>()?)),
+        opcode::try_end => Ok(Opcode::try_end),
         opcode::call => Ok(Opcode::call(r.read_u8()?)),
         opcode::ret => Ok(Opcode::ret),
         opcode::pop => Ok(Opcode::pop),
         opcode::pop_offset => Ok(Opcode::pop_offset(r.read_u16::<LE>()?)),
         opcode::dup => Ok(Opcode::dup),
+        opcode::raise => Ok(Opcode::raise),
         opcode::panic => Ok(Opcode::panic),
         byte => Err(Error::IllegalOpcode(byte)),
     }
@@ -128,6 +155,26 @@ pub fn write_opcode<W: io::Write>(w: &mut W, opcode: &Opcode) -> Result<()> {
             w.write_u8(opcode::index_dyn)?;
             Ok(())
         }
+        Opcode::array_len => {
+            w.write_u8(opcode::array_len)?;
+            Ok(())
+        }
+        Opcode::array_set => {
+            w.write_u8(opcode::array_set)?;
+            Ok(())
+        }
+        Opcode::array_push => {
+            w.write_u8(opcode::array_push)?;
+            Ok(())
+        }
+        Opcode::array_pop => {
+            w.write_u8(opcode::array_pop)?;
+            Ok(())
+        }
+        Opcode::array_slice => {
+            w.write_u8(opcode::array_slice)?;
+            Ok(())
+        }
         Opcode::spill(offset) => {
             w.write_u8(opcode::spill)?;
             w.write_u16::<LE>(*offset)?;
@@ -213,6 +260,10 @@ pub fn write_opcode<W: io::Write>(w: &mut W, opcode: &Opcode) -> Result<()> {
             w.write_u8(opcode::ge)?;
             Ok(())
         }
+        Opcode::to_str => {
+            w.write_u8(opcode::to_str)?;
+            Ok(())
+        }
         Opcode::sin => {
             w.write_u8(opcode::sin)?;
             Ok(())
@@ -289,6 +340,15 @@ pub fn write_opcode<W: io::Write>(w: &mut W, opcode: &Opcode) -> Result<()> {
             w.write_u8(opcode::end_frame)?;
             Ok(())
         }
+        Opcode::try_begin(pos) => {
+            w.write_u8(opcode::try_begin)?;
+            w.write_u32::<LE>(*pos)?;
+            Ok(())
+        }
+        Opcode::try_end => {
+            w.write_u8(opcode::try_end)?;
+            Ok(())
+        }
         Opcode::call(count) => {
             w.write_u8(opcode::call)?;
             w.write_u8(*count)?;
@@ -311,6 +371,10 @@ pub fn write_opcode<W: io::Write>(w: &mut W, opcode: &Opcode) -> Result<()> {
             w.write_u8(opcode::dup)?;
             Ok(())
         }
+        Opcode::raise => {
+            w.write_u8(opcode::raise)?;
+            Ok(())
+        }
         Opcode::panic => {
             w.write_u8(opcode::panic)?;
             Ok(())
@@ -318,19 +382,31 @@ pub fn write_opcode<W: io::Write>(w: &mut W, opcode: &Opcode) -> Result<()> {
     }
 }
 
-fn read_string<R: io::Read>(r: &mut R) -> Result<String> {
-    let len = r.read_u64::<LE>()? as usize;
-    let mut buf = vec![0; len];
-    r.read_exact(&mut buf)?;
-    Ok(String::from_utf8(buf)?)
+pub fn read_bytes<R: io::Read>(r: &mut R) -> Result<Vec<u8>> {
+    let len = r.read_u64::<LE>()?;
+    // the length isn't trusted, the buffer only grows with what is actually read
+    let mut buf = Vec::new();
+    r.take(len).read_to_end(&mut buf)?;
+    if buf.len() as u64 != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(buf)
 }
 
-fn write_string<W: io::Write>(w: &mut W, s: &str) -> Result<()> {
-    w.write_u64::<LE>(s.len() as u64)?;
-    w.write_all(s.as_bytes())?;
+pub fn write_bytes<W: io::Write>(w: &mut W, bytes: &[u8]) -> Result<()> {
+    w.write_u64::<LE>(bytes.len() as u64)?;
+    w.write_all(bytes)?;
     Ok(())
 }
 
+pub fn read_string<R: io::Read>(r: &mut R) -> Result<String> {
+    Ok(String::from_utf8(read_bytes(r)?)?)
+}
+
+pub fn write_string<W: io::Write>(w: &mut W, s: &str) -> Result<()> {
+    write_bytes(w, s.as_bytes())
+}
+
 pub fn read_value<R: io::Read>(r: &mut R) -> Result<Value> {
     match r.read_u8()? {
         value::int => Ok(Value::Int(r.read_i64::<LE>()?)),
@@ -345,13 +421,56 @@ pub fn read_value<R: io::Read>(r: &mut R) -> Result<Value> {
             }
             Ok(Value::Bundle(items.into()))
         }
+        value::nil => Ok(Value::Nil),
+        value::tuple => Ok(Value::Tuple(read_values(r)?)),
+        value::array => Ok(Value::Array(read_values(r)?)),
+        value::list => Ok(Value::List(read_values(r)?)),
+        value::record => {
+            let name = read_string(r)?;
+            let count = r.read_u32::<LE>()? as usize;
+            let mut fields = Vec::with_capacity(count);
+            for _ in 0..count {
+                fields.push((read_string(r)?, read_value(r)?));
+            }
+            Ok(Value::Record(name, fields.into()))
+        }
+        value::variant => {
+            let name = read_string(r)?;
+            let variant = read_string(r)?;
+            Ok(Value::Variant(name, variant, read_values(r)?))
+        }
         _ => Err(Error::IllegalValue),
     }
 }
 
+fn read_values<R: io::Read>(r: &mut R) -> Result<Box<[Value]>> {
+    let count = r.read_u32::<LE>()? as usize;
+    let mut values = Vec::with_capacity(count);
+    for _ in 0..count {
+        values.push(read_value(r)?);
+    }
+    Ok(values.into())
+}
+
+fn write_values<W: io::Write>(w: &mut W, values: &[Value]) -> Result<()> {
+    w.write_u32::<LE>(
+        values
+            .len()
+            .try_into()
+            .expect("value has more than 4294967295 items"),
+    )?;
+    for value in values {
+        write_value(w, value)?;
+    }
+    Ok(())
+}
+
 pub fn write_value<W: io::Write>(w: &mut W, value: &Value) -> Result<()> {
     match value {
-        Value::Nil => unimplemented!(),
+        Value::Nil => {
+            w.write_u8(value::nil)?;
+            Ok(())
+        }
         Value::Int(n) => {
             w.write_u8(value::int)?;
             w.write_i64::<LE>(*n)?;
@@ -372,7 +491,7 @@ pub fn write_value<W: io::Write>(w: &mut W, value: &Value) -> Result<()> {
             w.write_u8(*b as u8)?;
             Ok(())
         }
-        Value::Func => unimplemented!(),
+        Value::Func(_) => Err(Error::UnserializableValue),
         Value::Bundle(items) => {
             w.write_u8(value::bundle)?;
             w.write_u8(
@@ -386,6 +505,39 @@ pub fn write_value<W: io::Write>(w: &mut W, value: &Value) -> Result<()> {
             }
             Ok(())
         }
+        Value::Tuple(items) => {
+            w.write_u8(value::tuple)?;
+            write_values(w, items)
+        }
+        Value::Array(items) => {
+            w.write_u8(value::array)?;
+            write_values(w, items)
+        }
+        Value::List(items) => {
+            w.write_u8(value::list)?;
+            write_values(w, items)
+        }
+        Value::Record(name, fields) => {
+            w.write_u8(value::record)?;
+            write_string(w, name)?;
+            w.write_u32::<LE>(
+                fields
+                    .len()
+                    .try_into()
+                    .expect("record has more than 4294967295 fields"),
+            )?;
+            for (field, value) in fields {
+                write_string(w, field)?;
+                write_value(w, value)?;
+            }
+            Ok(())
+        }
+        Value::Variant(name, variant, args) => {
+            w.write_u8(value::variant)?;
+            write_string(w, name)?;
+            write_string(w, variant)?;
+            write_values(w, args)
+        }
     }
 }
 
@@ -433,7 +585,10 @@ pub fn read_function_table<R: io::Read>(r: &mut R) -> Result<BTreeMap<u32, Strin
     Ok(table)
 }
 
-pub fn write_function_table<W: io::Write>(w: &mut W, table: &HashMap<u32, String>) -> Result<()> {
+pub fn write_function_table<'a, W: io::Write>(
+    w: &mut W,
+    table: impl ExactSizeIterator<Item = (&'a u32, &'a String)>,
+) -> Result<()> {
     w.write_u16::<LE>(
         table
             .len()
@@ -446,5 +601,168 @@ pub fn write_function_table<W: io::Write>(w: &mut W, table: &HashMap<u32, String
     Ok(())
 }
 
+fn read_layout_id<R: io::Read>(r: &mut R) -> Result<LayoutID> {
+    Ok(r.read_u32::<LE>()? as LayoutID)
+}
+
+fn write_layout_id<W: io::Write>(w: &mut W, id: LayoutID) -> Result<()> {
+    w.write_u32::<LE>(id.try_into().expect("more than 2^32 layouts"))?;
+    Ok(())
+}
+
+fn read_layout_ids<R: io::Read>(r: &mut R) -> Result<Box<[LayoutID]>> {
+    let count = r.read_u32::<LE>()? as usize;
+    (0..count).map(|_| read_layout_id(r)).collect()
+}
+
+fn write_layout_ids<W: io::Write>(w: &mut W, ids: &[LayoutID]) -> Result<()> {
+    w.write_u32::<LE>(ids.len() as u32)?;
+    for id in ids {
+        write_layout_id(w, *id)?;
+    }
+    Ok(())
+}
+
+pub fn read_layout<R: io::Read>(r: &mut R) -> Result<Layout> {
+    match r.read_u8()? {
+        layout::any => Ok(Layout::Any),
+        layout::int => Ok(Layout::Int),
+        layout::float => Ok(Layout::Float),
+        layout::bool => Ok(Layout::Bool),
+        layout::string => Ok(Layout::String),
+        layout::func => {
+            let args = read_layout_ids(r)?;
+            let ret = read_layout_id(r)?;
+            Ok(Layout::Func(args, ret))
+        }
+        layout::tuple => Ok(Layout::Tuple(read_layout_ids(r)?)),
+        layout::array => Ok(Layout::Array(read_layout_id(r)?)),
+        layout::record => {
+            let name = read_string(r)?;
+            let count = r.read_u32::<LE>()? as usize;
+            let mut fields = Vec::with_capacity(count);
+            for _ in 0..count {
+                fields.push((read_string(r)?, read_layout_id(r)?));
+            }
+            Ok(Layout::Record(name, fields.into()))
+        }
+        layout::union => {
+            let name = read_string(r)?;
+            let count = r.read_u32::<LE>()? as usize;
+            let mut variants = Vec::with_capacity(count);
+            for _ in 0..count {
+                variants.push((read_string(r)?, read_layout_ids(r)?));
+            }
+            Ok(Layout::Union(name, variants.into()))
+        }
+        layout::list => {
+            let item = read_layout_id(r)?;
+            let cons_tag = r.read_u8()? as usize;
+            Ok(Layout::List { item, cons_tag })
+        }
+        _ => Err(Error::IllegalValue),
+    }
+}
+
+pub fn write_layout<W: io::Write>(w: &mut W, l: &Layout) -> Result<()> {
+    match l {
+        Layout::Any => w.write_u8(layout::any)?,
+        Layout::Int => w.write_u8(layout::int)?,
+        Layout::Float => w.write_u8(layout::float)?,
+        Layout::Bool => w.write_u8(layout::bool)?,
+        Layout::String => w.write_u8(layout::string)?,
+        Layout::Func(args, ret) => {
+            w.write_u8(layout::func)?;
+            write_layout_ids(w, args)?;
+            write_layout_id(w, *ret)?;
+        }
+        Layout::Tuple(items) => {
+            w.write_u8(layout::tuple)?;
+            write_layout_ids(w, items)?;
+        }
+        Layout::Array(item) => {
+            w.write_u8(layout::array)?;
+            write_layout_id(w, *item)?;
+        }
+        Layout::Record(name, fields) => {
+            w.write_u8(layout::record)?;
+            write_string(w, name)?;
+            w.write_u32::<LE>(fields.len() as u32)?;
+            for (field_name, field) in fields {
+                write_string(w, field_name)?;
+                write_layout_id(w, *field)?;
+            }
+        }
+        Layout::Union(name, variants) => {
+            w.write_u8(layout::union)?;
+            write_string(w, name)?;
+            w.write_u32::<LE>(variants.len() as u32)?;
+            for (variant_name, args) in variants {
+                write_string(w, variant_name)?;
+                write_layout_ids(w, args)?;
+            }
+        }
+        Layout::List { item, cons_tag } => {
+            w.write_u8(layout::list)?;
+            write_layout_id(w, *item)?;
+            w.write_u8(*cons_tag as u8)?;
+        }
+    }
+    Ok(())
+}
+
+// (layout table, result layout)
+pub fn read_layout_table<R: io::Read>(r: &mut R) -> Result<(LayoutTable, LayoutID)> {
+    let count = r.read_u32::<LE>()? as usize;
+    let mut layouts = Vec::with_capacity(count);
+    for _ in 0..count {
+        layouts.push(read_layout(r)?);
+    }
+    let result = read_layout_id(r)?;
+    Ok((LayoutTable::from_layouts(layouts), result))
+}
+
+pub fn write_layout_table<W: io::Write>(
+    w: &mut W,
+    layouts: &LayoutTable,
+    result: LayoutID,
+) -> Result<()> {
+    w.write_u32::<LE>(
+        layouts
+            .len()
+            .try_into()
+            .expect("layout table has more than 2^32 entries"),
+    )?;
+    for l in layouts.iter() {
+        write_layout(w, l)?;
+    }
+    write_layout_id(w, result)?;
+    Ok(())
+}
+
+// (name, layout) of the functions the host may call
+pub fn read_export_table<R: io::Read>(r: &mut R) -> Result<Vec<(String, LayoutID)>> {
+    let count = r.read_u16::<LE>()? as usize;
+    let mut exports = Vec::with_capacity(count);
+    for _ in 0..count {
+        exports.push((read_string(r)?, read_layout_id(r)?));
+    }
+    Ok(exports)
+}
+
+pub fn write_export_table<W: io::Write>(w: &mut W, exports: &[(String, LayoutID)]) -> Result<()> {
+    w.write_u16::<LE>(
+        exports
+            .len()
+            .try_into()
+            .expect("export table has more than 65535 entries"),
+    )?;
+    for (name, layout) in exports {
+        write_string(w, name)?;
+        write_layout_id(w, *layout)?;
+    }
+    Ok(())
+}
+
 mod dis;
 pub use dis::dissasemble;
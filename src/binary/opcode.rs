@@ -8,6 +8,11 @@ opcodes! {
     0x16 index_big(u64)
     0x17 index_dyn
     0x18 spill(u16)
+    0x19 array_len
+    0x1a array_set
+    0x1b array_push
+    0x1c array_pop
+    0x1d array_slice
     0x20 add
     0x21 sub
     0x22 mul
@@ -28,6 +33,7 @@ opcodes! {
     0x53 le
     0x54 gt
     0x55 ge
+    0x60 to_str
     0x70 sin
     0x71 cos
     0x72 tan
@@ -45,11 +51,14 @@ opcodes! {
     0xb4 jump_ne(u32)
     0xb5 do_frame
     0xb6 end_frame
+    0xb7 try_begin(u32)
+    0xb8 try_end
     0xbe call(u8)
     0xbf ret
     0xe0 pop
     0xe1 pop_offset(u16)
     0xe2 dup
+    0xfd raise
     0xfe panic
 }
 
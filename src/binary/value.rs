@@ -5,3 +5,10 @@ pub const float: u8 = 1;
 pub const string: u8 = 2;
 pub const bool: u8 = 3;
 pub const bundle: u8 = 4;
+pub const nil: u8 = 5;
+pub const func: u8 = 6;
+pub const tuple: u8 = 7;
+pub const array: u8 = 8;
+pub const list: u8 = 9;
+pub const record: u8 = 10;
+pub const variant: u8 = 11;
@@ -7,21 +7,25 @@ pub enum Expr {
     Int(Lexeme),
     Float(Lexeme),
     String(Lexeme),
+    Interpolation(Interpolation),
     Builtin(Lexeme),
     True(Lexeme),
     False(Lexeme),
     Underscores(Lexeme),
     Var(Lexeme),
+    Hole(Hole),
     Tuple(Tuple),
     Array(Array),
     Block(Block),
     Conditional(Conditional),
     Break(Break),
     Skip(Skip),
+    Raise(Raise),
     Call(Call),
     Index(Index),
     Access(Access),
     Let(Let),
+    Assign(Assign),
     Pub(Pub),
     Fun(Fun),
     Alias(Alias),
@@ -31,11 +35,16 @@ pub enum Expr {
     Record(Record),
     RecordValue(RecordValue),
     Union(Union),
+    Synonym(Synonym),
+    Newtype(Newtype),
     Class(Class),
     Have(Have),
     Binary(Binary),
     Unary(Unary),
     ArrayType(ArrayType),
+    Spread(Spread),
+    Annotated(Annotated),
+    With(With),
 }
 
 #[derive(Debug, Clone)]
@@ -43,6 +52,18 @@ pub struct Lexeme {
     pub span: Span,
 }
 
+#[derive(Debug, Clone)]
+pub struct Interpolation {
+    pub span: Span,
+    pub parts: Box<[InterpolationPart]>,
+}
+
+#[derive(Debug, Clone)]
+pub enum InterpolationPart {
+    Text(Span),
+    Hole(Expr),
+}
+
 #[derive(Debug, Clone)]
 pub struct Tuple {
     pub left_paren: Span,
@@ -57,6 +78,36 @@ pub struct Array {
     pub items: Box<[Expr]>,
 }
 
+// only valid inside array patterns, as in [first, ..rest]
+#[derive(Debug, Clone)]
+pub struct Spread {
+    pub spread: Span,
+    pub rest: Option<Box<Expr>>,
+}
+
+// an expression, a pattern or a signature followed by its type, as in x: int
+#[derive(Debug, Clone)]
+pub struct Annotated {
+    pub expr: Box<Expr>,
+    pub colon: Span,
+    pub ty: Box<Expr>,
+}
+
+// an expression whose constraints of a class are solved by a named instance, as in concat with sum
+#[derive(Debug, Clone)]
+pub struct With {
+    pub expr: Box<Expr>,
+    pub with_kw: Span,
+    pub instance: Box<Expr>,
+}
+
+// a named placeholder for an expression, as in ?name, whose expected type is reported
+#[derive(Debug, Clone)]
+pub struct Hole {
+    pub question: Span,
+    pub name: Span,
+}
+
 #[derive(Debug, Clone)]
 pub struct Block {
     pub do_kw: Span,
@@ -76,8 +127,10 @@ pub struct Conditional {
 pub enum Branch {
     If(IfBranch),
     While(WhileBranch),
+    For(ForBranch),
     Loop(LoopBranch),
     Match(MatchBranch),
+    Try(TryBranch),
     Else(ElseBranch),
 }
 
@@ -99,6 +152,18 @@ pub struct WhileBranch {
     pub body: Box<[Expr]>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ForBranch {
+    pub for_kw: Span,
+    pub in_kw: Span,
+    pub do_kw: Span,
+    pub label: Box<Label>,
+    pub pattern: Box<Expr>,
+    pub iterable: Box<Expr>,
+    pub range_end: Option<(Span, Box<Expr>)>,
+    pub body: Box<[Expr]>,
+}
+
 #[derive(Debug, Clone)]
 pub struct LoopBranch {
     pub loop_kw: Span,
@@ -118,9 +183,19 @@ pub struct MatchBranch {
 pub struct MatchCase {
     pub maps: Span,
     pub pattern: Box<Expr>,
+    pub guard: Option<(Span, Box<Expr>)>,
     pub value: Box<Expr>,
 }
 
+#[derive(Debug, Clone)]
+pub struct TryBranch {
+    pub try_kw: Span,
+    pub catch_kw: Span,
+    pub label: Box<Label>,
+    pub body: Box<[Expr]>,
+    pub cases: Box<[MatchCase]>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ElseBranch {
     pub label: Box<Label>,
@@ -140,6 +215,12 @@ pub struct Skip {
     pub label: Box<Label>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Raise {
+    pub raise_kw: Span,
+    pub value: Box<Expr>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Call {
     pub left_paren: Span,
@@ -166,11 +247,19 @@ pub struct Access {
 #[derive(Debug, Clone)]
 pub struct Let {
     pub let_kw: Span,
+    pub mut_kw: Option<Span>,
     pub assign: Option<Span>,
     pub pattern: Box<Expr>,
     pub value: Box<Expr>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Assign {
+    pub assign: Span,
+    pub target: Box<Expr>,
+    pub value: Box<Expr>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Pub {
     pub pub_kw: Span,
@@ -220,6 +309,7 @@ pub struct Record {
     pub record_kw: Span,
     pub end_kw: Span,
     pub signature: Box<Expr>,
+    pub deriving: Option<Box<[Expr]>>,
     pub fields: Box<[(Expr, Expr)]>,
 }
 
@@ -227,6 +317,7 @@ pub struct Record {
 pub struct RecordValue {
     pub left_brace: Span,
     pub right_brace: Span,
+    pub base: Option<(Span, Box<Expr>)>,
     pub fields: Box<[(Expr, Option<Expr>)]>,
 }
 
@@ -235,9 +326,26 @@ pub struct Union {
     pub union_kw: Span,
     pub end_kw: Span,
     pub signature: Box<Expr>,
+    pub deriving: Option<Box<[Expr]>>,
     pub variants: Box<[Expr]>,
 }
 
+#[derive(Debug, Clone)]
+pub struct Synonym {
+    pub type_kw: Span,
+    pub assign: Span,
+    pub signature: Box<Expr>,
+    pub ty: Box<Expr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Newtype {
+    pub newtype_kw: Span,
+    pub assign: Span,
+    pub signature: Box<Expr>,
+    pub variant: Box<Expr>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ClassItem {
     Constant,
@@ -251,13 +359,16 @@ pub struct Class {
     pub end_kw: Span,
     pub signature: Box<Expr>,
     pub associated: Option<Box<[Expr]>>,
+    pub superclasses: Option<Box<[Expr]>>,
     pub items: Box<[(ClassItem, Expr, Expr)]>,
+    pub defaults: Box<[Let]>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Have {
     pub have_kw: Span,
     pub end_kw: Span,
+    pub name: Option<Span>,
     pub class: Box<Expr>,
     pub items: Box<[Expr]>,
 }
@@ -341,6 +452,12 @@ impl Skip {
     }
 }
 
+impl Raise {
+    pub fn span(&self) -> Span {
+        mix_spans([self.raise_kw, self.value.span()])
+    }
+}
+
 impl Call {
     pub fn span(&self) -> Span {
         mix_spans([
@@ -373,6 +490,7 @@ impl Let {
     pub fn span(&self) -> Span {
         mix_spans([
             self.let_kw,
+            self.mut_kw.unwrap_or_default(),
             self.pattern.span(),
             self.assign.unwrap_or_default(),
             self.value.span(),
@@ -380,6 +498,12 @@ impl Let {
     }
 }
 
+impl Assign {
+    pub fn span(&self) -> Span {
+        mix_spans([self.target.span(), self.assign, self.value.span()])
+    }
+}
+
 impl Pub {
     pub fn span(&self) -> Span {
         mix_spans([self.pub_kw, self.expr.span()])
@@ -465,6 +589,28 @@ impl Union {
     }
 }
 
+impl Synonym {
+    pub fn span(&self) -> Span {
+        mix_spans([
+            self.type_kw,
+            self.signature.span(),
+            self.assign,
+            self.ty.span(),
+        ])
+    }
+}
+
+impl Newtype {
+    pub fn span(&self) -> Span {
+        mix_spans([
+            self.newtype_kw,
+            self.signature.span(),
+            self.assign,
+            self.variant.span(),
+        ])
+    }
+}
+
 impl Class {
     pub fn span(&self) -> Span {
         mix_spans([
@@ -484,6 +630,7 @@ impl Have {
     pub fn span(&self) -> Span {
         mix_spans([
             self.have_kw,
+            self.name.unwrap_or_default(),
             self.class.span(),
             item_spans(&self.items),
             self.end_kw,
@@ -509,6 +656,33 @@ impl ArrayType {
     }
 }
 
+impl Spread {
+    pub fn span(&self) -> Span {
+        mix_spans([
+            self.spread,
+            self.rest.as_ref().map(|e| e.span()).unwrap_or_default(),
+        ])
+    }
+}
+
+impl Annotated {
+    pub fn span(&self) -> Span {
+        mix_spans([self.expr.span(), self.colon, self.ty.span()])
+    }
+}
+
+impl With {
+    pub fn span(&self) -> Span {
+        mix_spans([self.expr.span(), self.with_kw, self.instance.span()])
+    }
+}
+
+impl Hole {
+    pub fn span(&self) -> Span {
+        Span::combine(self.question, self.name)
+    }
+}
+
 impl Expr {
     pub fn span(&self) -> Span {
         match self {
@@ -516,21 +690,25 @@ impl Expr {
             Self::Int(e) => e.span,
             Self::Float(e) => e.span,
             Self::String(e) => e.span,
+            Self::Interpolation(e) => e.span,
             Self::Builtin(e) => e.span,
             Self::True(e) => e.span,
             Self::False(e) => e.span,
             Self::Underscores(e) => e.span,
             Self::Var(e) => e.span,
+            Self::Hole(e) => e.span(),
             Self::Tuple(e) => e.span(),
             Self::Array(e) => e.span(),
             Self::Block(e) => e.span(),
             Self::Conditional(e) => e.span(),
             Self::Break(e) => e.span(),
             Self::Skip(e) => e.span(),
+            Self::Raise(e) => e.span(),
             Self::Call(e) => e.span(),
             Self::Index(e) => e.span(),
             Self::Access(e) => e.span(),
             Self::Let(e) => e.span(),
+            Self::Assign(e) => e.span(),
             Self::Pub(e) => e.span(),
             Self::Fun(e) => e.span(),
             Self::Alias(e) => e.span(),
@@ -540,11 +718,16 @@ impl Expr {
             Self::Record(e) => e.span(),
             Self::RecordValue(e) => e.span(),
             Self::Union(e) => e.span(),
+            Self::Synonym(e) => e.span(),
+            Self::Newtype(e) => e.span(),
             Self::Class(e) => e.span(),
             Self::Have(e) => e.span(),
             Self::Binary(e) => e.span(),
             Self::Unary(e) => e.span(),
             Self::ArrayType(e) => e.span(),
+            Self::Spread(e) => e.span(),
+            Self::Annotated(e) => e.span(),
+            Self::With(e) => e.span(),
         }
     }
 }
@@ -566,22 +749,45 @@ impl Branch {
                 b.do_kw,
                 item_spans(&b.body),
             ]),
+            Branch::For(b) => mix_spans([
+                b.for_kw,
+                b.label.span(),
+                b.pattern.span(),
+                b.in_kw,
+                b.iterable.span(),
+                mix_spans(
+                    b.range_end
+                        .iter()
+                        .map(|(spread, end)| mix_spans([*spread, end.span()])),
+                ),
+                b.do_kw,
+                item_spans(&b.body),
+            ]),
             Branch::Loop(b) => mix_spans([b.loop_kw, b.label.span(), item_spans(&b.body)]),
-            Branch::Match(b) => {
-                mix_spans([
-                    b.match_kw,
-                    b.scrutinee.span(),
-                    b.with_kw,
-                    mix_spans(b.cases.iter().map(|case| {
-                        mix_spans([case.pattern.span(), case.maps, case.value.span()])
-                    })),
-                ])
-            }
+            Branch::Match(b) => mix_spans([
+                b.match_kw,
+                b.scrutinee.span(),
+                b.with_kw,
+                mix_spans(b.cases.iter().map(MatchCase::span)),
+            ]),
+            Branch::Try(b) => mix_spans([
+                b.try_kw,
+                b.label.span(),
+                item_spans(&b.body),
+                b.catch_kw,
+                mix_spans(b.cases.iter().map(MatchCase::span)),
+            ]),
             Branch::Else(b) => mix_spans([b.label.span(), item_spans(&b.body)]),
         }
     }
 }
 
+impl MatchCase {
+    pub fn span(&self) -> Span {
+        mix_spans([self.pattern.span(), self.maps, self.value.span()])
+    }
+}
+
 fn item_spans(items: &[Expr]) -> Span {
     mix_spans(items.iter().map(|e| e.span()))
 }
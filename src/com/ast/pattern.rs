@@ -15,6 +15,16 @@ pub enum Pattern {
     Call(Span, Span, Box<Expr>, Box<[Pattern]>),
     Access(Box<Expr>),
     Record(Span, Span, Box<[(Option<Span>, Option<Pattern>)]>),
+    Or(Box<[Pattern]>),
+    // [prefix.., ..rest, suffix..]
+    Array(
+        Span,
+        Span,
+        Box<[Pattern]>,
+        Option<(Span, Box<Pattern>)>,
+        Box<[Pattern]>,
+    ),
+    Annotated(Box<Pattern>, Span, Box<Expr>),
 }
 
 impl Pattern {
@@ -37,6 +47,9 @@ impl Pattern {
             }
             P::Access(e) => e.span(),
             P::Record(left_brace, right_brace, _) => mix_spans([*left_brace, *right_brace]),
+            P::Or(alternatives) => item_spans(alternatives),
+            P::Array(left_bracket, right_bracket, ..) => mix_spans([*left_bracket, *right_bracket]),
+            P::Annotated(pattern, colon, ty) => mix_spans([pattern.span(), *colon, ty.span()]),
         }
     }
 
@@ -57,6 +70,14 @@ impl Pattern {
             P::Record(_, _, fields) => fields
                 .iter()
                 .all(|(_, pat)| pat.as_ref().map(Self::is_irrefutable).unwrap_or(true)),
+            // the first alternative always matches, the others are never tried
+            P::Or(alternatives) => alternatives.first().is_some_and(Self::is_irrefutable),
+            // only [..rest] matches arrays of any length
+            P::Array(_, _, prefix, Some((_, rest)), suffix) => {
+                prefix.is_empty() && suffix.is_empty() && rest.is_irrefutable()
+            }
+            P::Array(..) => false,
+            P::Annotated(pattern, ..) => pattern.is_irrefutable(),
         }
     }
 }
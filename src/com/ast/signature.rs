@@ -7,6 +7,7 @@ pub enum Signature {
     Name(Span, Box<Signature>),
     Args(Box<[Pattern]>, Box<Signature>),
     Empty,
+    Returns(Box<Expr>),
 }
 
 impl Signature {
@@ -21,6 +22,7 @@ impl Signature {
                 next.collect_arg_patterns(patterns);
             }
             Self::Empty => {}
+            Self::Returns(_) => {}
         }
     }
 
@@ -29,6 +31,14 @@ impl Signature {
         self.collect_arg_patterns(&mut patterns);
         patterns
     }
+
+    pub fn return_annotation(&self) -> Option<&Expr> {
+        match self {
+            Self::Name(_, next) | Self::Args(_, next) => next.return_annotation(),
+            Self::Returns(ty) => Some(ty),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -1,4 +1,4 @@
-use super::{Branch, Expr};
+use super::{Branch, Expr, InterpolationPart};
 
 pub fn preorder_traversal(expr: &Expr) -> Vec<&Expr> {
     let mut nodes = Vec::new();
@@ -15,11 +15,19 @@ fn walk_expr<'a>(expr: &'a Expr, nodes: &mut Vec<&'a Expr>) {
         E::Int(..) => {}
         E::Float(..) => {}
         E::String(..) => {}
+        E::Interpolation(e) => {
+            for part in &e.parts {
+                if let InterpolationPart::Hole(hole) = part {
+                    walk_expr(hole, nodes);
+                }
+            }
+        }
         E::Builtin(..) => {}
         E::True(..) => {}
         E::False(..) => {}
         E::Underscores(..) => {}
         E::Var(..) => {}
+        E::Hole(..) => {}
         E::Tuple(e) => {
             for item in &e.items {
                 walk_expr(item, nodes);
@@ -47,6 +55,7 @@ fn walk_expr<'a>(expr: &'a Expr, nodes: &mut Vec<&'a Expr>) {
             }
         }
         E::Skip(..) => {}
+        E::Raise(e) => walk_expr(&e.value, nodes),
         E::Call(e) => {
             walk_expr(&e.callee, nodes);
             for arg in &e.args {
@@ -64,6 +73,10 @@ fn walk_expr<'a>(expr: &'a Expr, nodes: &mut Vec<&'a Expr>) {
             walk_expr(&e.pattern, nodes);
             walk_expr(&e.value, nodes);
         }
+        E::Assign(e) => {
+            walk_expr(&e.target, nodes);
+            walk_expr(&e.value, nodes);
+        }
         E::Pub(e) => {
             walk_expr(&e.expr, nodes);
         }
@@ -77,6 +90,9 @@ fn walk_expr<'a>(expr: &'a Expr, nodes: &mut Vec<&'a Expr>) {
         E::Super(..) => {}
         E::Record(..) => {}
         E::RecordValue(e) => {
+            if let Some((_, base)) = &e.base {
+                walk_expr(base, nodes);
+            }
             for (name, value) in &e.fields {
                 walk_expr(name, nodes);
                 if let Some(value) = value {
@@ -85,6 +101,8 @@ fn walk_expr<'a>(expr: &'a Expr, nodes: &mut Vec<&'a Expr>) {
             }
         }
         E::Union(..) => {}
+        E::Synonym(..) => {}
+        E::Newtype(..) => {}
         E::Class(..) => {}
         E::Have(e) => {
             for item in &e.items {
@@ -99,6 +117,13 @@ fn walk_expr<'a>(expr: &'a Expr, nodes: &mut Vec<&'a Expr>) {
             walk_expr(&e.arg, nodes);
         }
         E::ArrayType(..) => {}
+        E::Spread(e) => {
+            if let Some(rest) = &e.rest {
+                walk_expr(rest, nodes);
+            }
+        }
+        E::Annotated(e) => walk_expr(&e.expr, nodes),
+        E::With(e) => walk_expr(&e.expr, nodes),
     }
 }
 
@@ -117,6 +142,16 @@ fn walk_branch<'a>(branch: &'a Branch, nodes: &mut Vec<&'a Expr>) {
                 walk_expr(item, nodes);
             }
         }
+        B::For(b) => {
+            walk_expr(&b.pattern, nodes);
+            walk_expr(&b.iterable, nodes);
+            if let Some((_, end)) = &b.range_end {
+                walk_expr(end, nodes);
+            }
+            for item in &b.body {
+                walk_expr(item, nodes);
+            }
+        }
         B::Loop(b) => {
             for item in &b.body {
                 walk_expr(item, nodes);
@@ -126,6 +161,21 @@ fn walk_branch<'a>(branch: &'a Branch, nodes: &mut Vec<&'a Expr>) {
             walk_expr(&b.scrutinee, nodes);
             for case in &b.cases {
                 walk_expr(&case.pattern, nodes);
+                if let Some((_, guard)) = &case.guard {
+                    walk_expr(guard, nodes);
+                }
+                walk_expr(&case.value, nodes);
+            }
+        }
+        B::Try(b) => {
+            for item in &b.body {
+                walk_expr(item, nodes);
+            }
+            for case in &b.cases {
+                walk_expr(&case.pattern, nodes);
+                if let Some((_, guard)) = &case.guard {
+                    walk_expr(guard, nodes);
+                }
                 walk_expr(&case.value, nodes);
             }
         }
@@ -5,6 +5,7 @@ use super::{
     reporting::{Header, Report},
     sem::{self},
 };
+use crate::exe::LayoutTable;
 use codespan_reporting::{
     files::{self, SimpleFile},
     term::{
@@ -49,19 +50,26 @@ pub struct Compiled;
 
 // compiler info
 pub struct StagedInfo {
+    marin_dir: PathBuf,
     is_std_staged: bool,
+    warn_orphan_instances: bool,
 }
 
 pub struct SourceInfo {
+    marin_dir: PathBuf,
     is_std_staged: bool,
+    warn_orphan_instances: bool,
 }
 
 pub struct ParsedInfo {
+    marin_dir: PathBuf,
     is_std_staged: bool,
+    warn_orphan_instances: bool,
 }
 
 pub struct CheckedInfo {
     entities: ir::Entities,
+    layouts: LayoutTable,
     dependency_order: Vec<usize>,
 }
 
@@ -75,7 +83,9 @@ pub fn init() -> Compiler<Staged, StagedInfo> {
         reports: Vec::new(),
         files: Files::default(),
         info: StagedInfo {
+            marin_dir: sem::get_marin_dir(),
             is_std_staged: false,
+            warn_orphan_instances: false,
         },
     }
 }
@@ -199,15 +209,28 @@ impl Compiler<Staged, StagedInfo> {
     }
 
     pub fn add_marin_std(&mut self) {
+        self.add_marin_std_from(sem::get_marin_dir());
+    }
+
+    // builtin directories are looked up in the given directory instead
+    pub fn add_marin_std_from(&mut self, marin_dir: impl AsRef<Path>) {
         if self.info.is_std_staged {
             panic!("marin std library is already staged");
         }
 
-        let marin_std_path = sem::get_marin_std_path();
+        self.info.marin_dir = marin_dir
+            .as_ref()
+            .canonicalize()
+            .expect("couldn't normalize marin directory");
+        let marin_std_path = self.info.marin_dir.join("std");
         self.add_dir_with_info(&marin_std_path, StagedFileInfo::marin_std_file());
         self.info.is_std_staged = true;
     }
 
+    pub fn warn_orphan_instances(&mut self) {
+        self.info.warn_orphan_instances = true;
+    }
+
     fn read_file(staged: Staged, reports: &mut Vec<Report>) -> File {
         match staged {
             Staged::File {
@@ -265,7 +288,9 @@ impl Compiler<Staged, StagedInfo> {
             reports: self.reports,
             files: Files(source_files),
             info: SourceInfo {
+                marin_dir: self.info.marin_dir,
                 is_std_staged: self.info.is_std_staged,
+                warn_orphan_instances: self.info.warn_orphan_instances,
             },
         }
     }
@@ -301,7 +326,9 @@ impl Compiler<Sourced, SourceInfo> {
             reports: self.reports,
             files: Files(parsed_files),
             info: ParsedInfo {
+                marin_dir: self.info.marin_dir,
                 is_std_staged: self.info.is_std_staged,
+                warn_orphan_instances: self.info.warn_orphan_instances,
             },
         }
     }
@@ -309,8 +336,12 @@ impl Compiler<Sourced, SourceInfo> {
 
 impl Compiler<Parsed, ParsedInfo> {
     pub fn check(mut self) -> Compiler<Checked, CheckedInfo> {
-        let deps =
-            sem::analyse_dependencies(&self.files, self.info.is_std_staged, &mut self.reports);
+        let deps = sem::analyse_dependencies(
+            &self.files,
+            &self.info.marin_dir,
+            self.info.is_std_staged,
+            &mut self.reports,
+        );
         let order = sem::sort_dependencies(&deps.graph, &self.files, &mut self.reports);
 
         let files = self.files.0;
@@ -327,7 +358,11 @@ impl Compiler<Parsed, ParsedInfo> {
 
                 let options = sem::CheckModuleOptions::new()
                     .set_verbose(!info.is_from_std)
-                    .set_import_prelude(self.info.is_std_staged);
+                    .set_import_prelude(self.info.is_std_staged)
+                    .set_export_functions(!info.is_from_std)
+                    .set_warn_orphan_instances(
+                        self.info.warn_orphan_instances && !info.is_from_std,
+                    );
                 let ir = checker.check_module(file.name(), id, file.source(), ast, options);
                 irs[id] = Some(Checked(ir))
             }
@@ -336,6 +371,7 @@ impl Compiler<Parsed, ParsedInfo> {
         let dependency_order = order.into_iter().flatten().collect();
 
         let entities = checker.entities;
+        let layouts = checker.layouts;
         self.reports.append(&mut reports);
 
         let checked_files = files
@@ -352,6 +388,7 @@ impl Compiler<Parsed, ParsedInfo> {
             files: Files(checked_files),
             info: CheckedInfo {
                 entities,
+                layouts,
                 dependency_order,
             },
         }
@@ -367,7 +404,12 @@ impl Compiler<Checked, CheckedInfo> {
             compiled_files.push((file, Compiled));
         }
 
-        let lowered = low::lower(modules, self.info.entities, self.info.dependency_order);
+        let lowered = low::lower(
+            modules,
+            self.info.entities,
+            self.info.layouts,
+            self.info.dependency_order,
+        );
         let bytecode = emit::emit(lowered).expect("failed to generate bytecode");
 
         Compiler {
@@ -6,7 +6,7 @@ use super::{
 };
 use crate::{
     binary::{self, Opcode, opcode},
-    exe::Value,
+    exe::{LayoutID, Value},
 };
 use std::{collections::HashMap, io::Cursor};
 
@@ -27,6 +27,7 @@ enum Placeholder {
 
 struct Label {
     depth: usize,
+    handler_depth: usize,
     breaks: Vec<Marker>,
     skips: Vec<Marker>,
 }
@@ -37,6 +38,7 @@ enum JumpMode {
     IfTrue,
     IfFalse,
     Eq,
+    Catch,
 }
 
 enum PseudoOp {
@@ -52,6 +54,7 @@ struct BytecodeBuilder {
     function_positions: Vec<Placeholder>,
     labels: HashMap<ir::LabelID, Label>,
     frame_depth: usize,
+    handler_depth: usize,
 
     opcodes: Vec<(PseudoOp, Option<Marker>)>,
     cursor: Cursor<Vec<u8>>,
@@ -67,6 +70,7 @@ impl BytecodeBuilder {
             function_positions: Vec::new(),
             labels: HashMap::new(),
             frame_depth: 0,
+            handler_depth: 0,
 
             opcodes: Vec::new(),
             cursor: Cursor::new(Vec::new()),
@@ -180,6 +184,7 @@ impl BytecodeBuilder {
             label,
             Label {
                 depth: self.frame_depth,
+                handler_depth: self.handler_depth,
                 breaks: Vec::new(),
                 skips: Vec::new(),
             },
@@ -228,15 +233,22 @@ impl BytecodeBuilder {
                     self.build_deconstruct(pat, 0);
                 }
             }
+            S::Assign { local, value } => {
+                self.build_expression(*value);
+                self.write_opcode(Opcode::set_local(local));
+            }
             S::Block { stmts, needs_frame } => {
                 if needs_frame {
-                    self.write_opcode(Opcode::do_frame);
+                    self.build_do_frame();
                 }
                 for stmt in stmts {
                     self.build_statement(stmt);
                 }
+                // end_frame keeps the top value, but statements leave none
                 if needs_frame {
-                    self.write_opcode(Opcode::end_frame);
+                    self.write_opcode(Opcode::load_nil);
+                    self.build_end_frame();
+                    self.write_opcode(Opcode::pop);
                 }
             }
         }
@@ -347,6 +359,13 @@ impl BytecodeBuilder {
             } => self.build_match(*scrutinee, *decision, *fallback),
             E::Break { value, label } => self.build_break(*value, label),
             E::Skip { label } => self.build_skip(label),
+            E::Raise { tag, value, layout } => self.build_raise(tag, *value, layout),
+            E::Try {
+                tag,
+                body,
+                decision,
+                fallback,
+            } => self.build_try(tag, *body, *decision, *fallback),
 
             E::Add(left, right) => self.build_binary_op(*left, *right, Opcode::add),
             E::Sub(left, right) => self.build_binary_op(*left, *right, Opcode::sub),
@@ -381,6 +400,24 @@ impl BytecodeBuilder {
             E::Gt(left, right) => self.build_binary_op(*left, *right, Opcode::gt),
             E::Ge(left, right) => self.build_binary_op(*left, *right, Opcode::ge),
 
+            E::Str(arg) => self.build_unary_op(*arg, Opcode::to_str),
+
+            E::ArrayLen(array) => self.build_unary_op(*array, Opcode::array_len),
+            E::ArraySet(array, index, value) => {
+                self.build_expression(*array);
+                self.build_expression(*index);
+                self.build_expression(*value);
+                self.write_opcode(Opcode::array_set);
+            }
+            E::ArrayPush(array, value) => self.build_binary_op(*array, *value, Opcode::array_push),
+            E::ArrayPop(array) => self.build_unary_op(*array, Opcode::array_pop),
+            E::ArraySlice(array, start, end) => {
+                self.build_expression(*array);
+                self.build_expression(*start);
+                self.build_expression(*end);
+                self.write_opcode(Opcode::array_slice);
+            }
+
             E::Panic(arg) => self.build_unary_op(*arg, Opcode::panic),
         }
     }
@@ -504,7 +541,7 @@ impl BytecodeBuilder {
     }
 
     fn build_match(&mut self, scrutinee: low::Expr, decision: low::Decision, fallback: low::Expr) {
-        self.write_opcode(Opcode::do_frame);
+        self.build_do_frame();
 
         self.build_expression(scrutinee);
 
@@ -524,7 +561,7 @@ impl BytecodeBuilder {
             self.wire_jump(JumpMode::Always, marker, success_dest_marker);
         }
 
-        self.write_opcode(Opcode::end_frame);
+        self.build_end_frame();
     }
 
     fn build_decision(
@@ -600,6 +637,96 @@ impl BytecodeBuilder {
 
                 self.wire_jump(test_jump_mode, failure_begin_marker, success_begin_marker);
             }
+            D::Guard {
+                guard,
+                success,
+                failure,
+            } => {
+                self.build_expression(*guard);
+
+                let failure_begin_marker = self.mark();
+                self.build_decision(*failure, success_markers, failure_markers);
+
+                let success_begin_marker = self.mark();
+                self.build_decision(*success, success_markers, failure_markers);
+
+                self.wire_jump(JumpMode::IfTrue, failure_begin_marker, success_begin_marker);
+            }
+            D::Bind { stmts, next } => {
+                for stmt in stmts {
+                    self.build_statement(stmt);
+                }
+                self.build_decision(*next, success_markers, failure_markers);
+            }
+        }
+    }
+
+    fn build_raise(&mut self, tag: String, value: low::Expr, layout: LayoutID) {
+        // (tag, value, layout)
+        self.build_constant(Value::String(tag));
+        self.build_expression(value);
+        self.build_constant(Value::Int(layout as i64));
+        self.write_opcode(Opcode::bundle(3));
+        self.write_opcode(Opcode::raise);
+    }
+
+    fn build_try(
+        &mut self,
+        tag: String,
+        body: low::Expr,
+        decision: low::Decision,
+        fallback: low::Expr,
+    ) {
+        self.build_do_frame();
+        let try_begin_marker = self.mark();
+
+        self.handler_depth += 1;
+        self.build_expression(body);
+        self.write_opcode(Opcode::try_end);
+        self.handler_depth -= 1;
+        let body_end_marker = self.mark();
+
+        // the handler starts with the raised (tag, value, layout) bundle on the stack
+        // if the tag doesn't match, the bundle is raised again as is
+        self.write_opcode(Opcode::dup);
+        self.write_opcode(Opcode::index(0));
+        self.build_constant(Value::String(tag));
+        self.write_opcode(Opcode::eq);
+        let tag_test_marker = self.mark();
+        self.write_opcode(Opcode::index(1));
+
+        let mut failure_markers = Vec::new();
+        let mut success_markers = Vec::new();
+        self.build_decision(decision, &mut success_markers, &mut failure_markers);
+
+        let failure_dest_marker = self.mark();
+        for marker in failure_markers {
+            self.wire_jump(JumpMode::Always, marker, failure_dest_marker);
+        }
+
+        self.build_expression(fallback);
+        let reraise_marker = self.mark();
+        self.write_opcode(Opcode::raise);
+
+        let try_end_marker = self.mark();
+        for marker in success_markers {
+            self.wire_jump(JumpMode::Always, marker, try_end_marker);
+        }
+
+        self.wire_jump(JumpMode::Catch, try_begin_marker, body_end_marker);
+        self.wire_jump(JumpMode::Always, body_end_marker, try_end_marker);
+        self.wire_jump(JumpMode::IfFalse, tag_test_marker, reraise_marker);
+
+        self.build_end_frame();
+    }
+
+    fn build_leave_handlers(&mut self, label: ir::LabelID) {
+        let current_depth = self.handler_depth;
+        let label_depth = self.get_label(label).handler_depth;
+        debug_assert!(current_depth >= label_depth);
+
+        for _ in 0..(current_depth - label_depth) {
+            self.write_opcode(Opcode::try_end);
         }
     }
 
@@ -655,6 +782,7 @@ impl BytecodeBuilder {
                         JumpMode::IfTrue => self.cursor.write_u8(opcode::jump_if)?,
                         JumpMode::IfFalse => self.cursor.write_u8(opcode::jump_if_not)?,
                         JumpMode::Eq => self.cursor.write_u8(opcode::jump_eq)?,
+                        JumpMode::Catch => self.cursor.write_u8(opcode::try_begin)?,
                     }
 
                     match &mut placeholders[dest.0] {
@@ -727,6 +855,7 @@ impl BytecodeBuilder {
         for _ in 0..frame_distance {
             self.write_opcode(Opcode::end_frame);
         }
+        self.build_leave_handlers(label);
         let break_marker = self.mark();
         self.get_label_mut(label).breaks.push(break_marker);
     }
@@ -736,23 +865,35 @@ impl BytecodeBuilder {
         let label_depth = self.get_label(label).depth;
         debug_assert!(current_depth >= label_depth);
 
+        // end_frame keeps the top value, but skipping carries none
         let frame_distance = current_depth - label_depth;
-        for _ in 0..frame_distance {
-            self.write_opcode(Opcode::end_frame);
+        if frame_distance > 0 {
+            self.write_opcode(Opcode::load_nil);
+            for _ in 0..frame_distance {
+                self.write_opcode(Opcode::end_frame);
+            }
+            self.write_opcode(Opcode::pop);
         }
+        self.build_leave_handlers(label);
         let skip_marker = self.mark();
         self.get_label_mut(label).skips.push(skip_marker);
     }
 }
 
-pub fn emit(program: low::Program) -> binary::Result<Vec<u8>> {
+pub fn emit(mut program: low::Program) -> binary::Result<Vec<u8>> {
+    let layouts = std::mem::take(&mut program.layouts);
+    let result = program.result;
+    let exports = std::mem::take(&mut program.exports);
+
     let mut bb = BytecodeBuilder::new();
     bb.build_program(program)?;
 
     let mut bytecode = vec![];
     binary::write_magic(&mut bytecode)?;
     binary::write_constant_pool(&mut bytecode, &bb.constants)?;
-    binary::write_function_table(&mut bytecode, &bb.function_table)?;
+    binary::write_function_table(&mut bytecode, bb.function_table.iter())?;
+    binary::write_layout_table(&mut bytecode, &layouts, result)?;
+    binary::write_export_table(&mut bytecode, &exports)?;
     bytecode.append(&mut bb.cursor.into_inner());
 
     Ok(bytecode)
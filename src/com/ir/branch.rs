@@ -1,4 +1,4 @@
-use super::{Decision, Expr, LabelID, Stmt, VariableID};
+use super::{Decision, ExceptionID, Expr, LabelID, Stmt, VariableID};
 
 #[derive(Debug, Clone)]
 pub enum Branch {
@@ -12,6 +12,14 @@ pub enum Branch {
         body: Box<[Stmt]>,
         label: LabelID,
     },
+    // the state variables are bound once, before the first iteration
+    // the body starts by binding the pattern and advancing the state
+    For {
+        state: Box<[(VariableID, Expr)]>,
+        guard: Box<Expr>,
+        body: Box<[Stmt]>,
+        label: LabelID,
+    },
     Loop {
         body: Box<[Stmt]>,
         label: LabelID,
@@ -25,4 +33,11 @@ pub enum Branch {
         scrutinee: Box<Expr>,
         decision: Box<Decision>,
     },
+    Try {
+        body: Box<[Stmt]>,
+        label: LabelID,
+        exception: ExceptionID,
+        caught_var: VariableID,
+        decision: Box<Decision>,
+    },
 }
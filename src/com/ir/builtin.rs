@@ -20,6 +20,7 @@ pub enum Builtin {
     int_pos,
     int_neg,
     int_not,
+    int_str,
 
     float_add,
     float_sub,
@@ -34,6 +35,7 @@ pub enum Builtin {
     float_ge,
     float_pos,
     float_neg,
+    float_str,
 
     string_concat,
     string_eq,
@@ -60,6 +62,13 @@ pub enum Builtin {
     acos,
     atan,
 
+    array_len,
+    array_get,
+    array_set,
+    array_push,
+    array_pop,
+    array_slice,
+
     panic,
 }
 
@@ -83,6 +92,7 @@ impl Display for Builtin {
             Self::int_pos => write!(f, "int_pos"),
             Self::int_neg => write!(f, "int_neg"),
             Self::int_not => write!(f, "int_not"),
+            Self::int_str => write!(f, "int_str"),
 
             Self::float_add => write!(f, "float_add"),
             Self::float_sub => write!(f, "float_sub"),
@@ -97,6 +107,7 @@ impl Display for Builtin {
             Self::float_ge => write!(f, "float_ge"),
             Self::float_pos => write!(f, "float_pos"),
             Self::float_neg => write!(f, "float_neg"),
+            Self::float_str => write!(f, "float_str"),
 
             Self::string_concat => write!(f, "string_concat"),
             Self::string_eq => write!(f, "string_eq"),
@@ -123,6 +134,13 @@ impl Display for Builtin {
             Self::acos => write!(f, "acos"),
             Self::atan => write!(f, "atan"),
 
+            Self::array_len => write!(f, "array_len"),
+            Self::array_get => write!(f, "array_get"),
+            Self::array_set => write!(f, "array_set"),
+            Self::array_push => write!(f, "array_push"),
+            Self::array_pop => write!(f, "array_pop"),
+            Self::array_slice => write!(f, "array_slice"),
+
             Self::panic => write!(f, "panic"),
         }
     }
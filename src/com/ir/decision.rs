@@ -13,4 +13,13 @@ pub enum Decision {
         success: Box<Decision>,
         failure: Box<Decision>,
     },
+    Guard {
+        guard: Box<Expr>,
+        success: Box<Decision>,
+        failure: Box<Decision>,
+    },
+    Bind {
+        stmts: Vec<Stmt>,
+        next: Box<Decision>,
+    },
 }
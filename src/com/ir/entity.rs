@@ -1,5 +1,8 @@
-use super::{Expr, InstanceScheme, PathQuery, Scheme, TypeID};
-use crate::com::loc::{Loc, Span};
+use super::{Expr, InstanceScheme, PathQuery, Scheme, Solution, TypeID};
+use crate::{
+    com::loc::{Loc, Span},
+    exe::LayoutID,
+};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum AnyID {
@@ -7,6 +10,7 @@ pub enum AnyID {
     UserType(UserTypeID),
     Record(RecordID),
     Union(UnionID),
+    Synonym(SynonymID),
     Class(ClassID),
     Instance(InstanceID),
     Import(ImportID),
@@ -17,21 +21,27 @@ define_id_type!(VariableID, Variable);
 define_id_type!(UserTypeID, UserType);
 define_id_type!(RecordID, Record);
 define_id_type!(UnionID, Union);
+define_id_type!(SynonymID, Synonym);
 define_id_type!(ClassID, Class);
 define_id_type!(InstanceID, Instance);
 define_id_type!(ImportID, Import);
 define_id_type!(AliasID, Alias);
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ExceptionID(pub usize);
+
 #[derive(Default)]
 pub struct Entities {
     pub variables: Vec<VariableInfo>,
     pub user_types: Vec<UserTypeInfo>,
     pub records: Vec<RecordInfo>,
     pub unions: Vec<UnionInfo>,
+    pub synonyms: Vec<SynonymInfo>,
     pub classes: Vec<ClassInfo>,
     pub instances: Vec<InstanceInfo>,
     pub imports: Vec<ImportInfo>,
     pub aliases: Vec<AliasInfo>,
+    pub exceptions: Vec<ExceptionInfo>,
 }
 
 impl Entities {
@@ -55,6 +65,11 @@ impl Entities {
         (create_union, next_union_id, get_union_info get_union_info_mut),
         (UnionID => UnionInfo),
     );
+    entity_impl!(
+        synonyms,
+        (create_synonym, next_synonym_id, get_synonym_info get_synonym_info_mut),
+        (SynonymID => SynonymInfo),
+    );
     entity_impl!(
         instances,
         (create_instance, next_instance_id, get_instance_info get_instance_info_mut),
@@ -75,6 +90,11 @@ impl Entities {
         (create_alias, next_alias_id, get_alias_info get_alias_info_mut),
         (AliasID => AliasInfo),
     );
+    entity_impl!(
+        exceptions,
+        (create_exception, next_exception_id, get_exception_info get_exception_info_mut),
+        (ExceptionID => ExceptionInfo),
+    );
 
     pub fn create_dummy_variable(&mut self) -> VariableID {
         let id = self.next_variable_id();
@@ -145,6 +165,8 @@ pub struct UnionInfo {
     pub scheme: Scheme,
     pub type_args: Option<Box<[UnionArgInfo]>>,
     pub variants: Box<[VariantInfo]>,
+    // a single variant with a single argument, represented by that argument
+    pub is_newtype: bool,
 }
 
 impl UnionInfo {
@@ -172,17 +194,35 @@ impl VariantInfo {
     }
 }
 
+// the scheme quantifies over the type arguments, which are expanded into its type
+pub struct SynonymInfo {
+    pub name: String,
+    pub loc: Loc,
+    pub scheme: Scheme,
+    pub type_args: Option<Box<[TypeID]>>,
+}
+
 pub struct ClassInfo {
     pub name: String,
     pub loc: Loc,
     pub items: Box<[ClassItemInfo]>,
     pub arity: (usize, usize),
+    pub superclasses: Box<[SuperclassInfo]>,
+    // the field of a class standing for structural access, as in r.name
+    pub field: Option<String>,
+}
+
+// the arguments of a superclass are indices into the arguments of its subclass
+pub struct SuperclassInfo {
+    pub id: ClassID,
+    pub args: Box<[usize]>,
 }
 
 pub struct ClassItemInfo {
     pub name: String,
     pub loc: Loc,
     pub scheme: Scheme,
+    pub default: Option<VariableID>,
 }
 
 #[derive(Clone)]
@@ -191,6 +231,8 @@ pub struct InstanceInfo {
     pub scheme: InstanceScheme,
     pub original: InstanceID,
     pub items: Box<[InstanceItemInfo]>,
+    // constraints of the items solved when checking the instance
+    pub solutions: Box<[Solution]>,
 }
 
 #[derive(Clone)]
@@ -210,6 +252,14 @@ pub struct AliasInfo {
     pub path: PathQuery,
 }
 
+pub struct ExceptionInfo {
+    pub loc: Loc,
+    pub ty: TypeID,
+    pub tag: String,
+    // the layout of the raised values, to display them when they aren't caught
+    pub layout: LayoutID,
+}
+
 macro_rules! entity_impl {
     (
         $field:ident,
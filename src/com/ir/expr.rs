@@ -1,4 +1,4 @@
-use super::{Branch, Builtin, LabelID, Signature, Stmt, TypeID, VariableID};
+use super::{Branch, Builtin, ExceptionID, LabelID, Signature, Stmt, TypeID, VariableID};
 
 pub type CheckedExpr = (Expr, TypeID);
 
@@ -48,12 +48,25 @@ pub enum Expr {
     Skip {
         label: LabelID,
     },
+    Raise {
+        value: Box<Expr>,
+        exception: ExceptionID,
+    },
+    Assign {
+        var: VariableID,
+        value: Box<Expr>,
+    },
     Fun {
         name: String,
         recursive_binding: Option<VariableID>,
         signature: Box<Signature>,
         expr: Box<Expr>,
     },
+    // functions referring to each other through their bindings, as a tuple of them
+    FunGroup {
+        bindings: Box<[VariableID]>,
+        funs: Box<[Expr]>,
+    },
     Call {
         callee: Box<Expr>,
         args: Box<[Expr]>,
@@ -62,6 +75,8 @@ pub enum Expr {
         tag: usize,
         items: Option<Box<[Expr]>>,
     },
+    // the constructor of a newtype, which leaves its argument as is
+    Newtype,
     Record {
         fields: Box<[Expr]>,
     },
@@ -69,6 +84,11 @@ pub enum Expr {
         accessed: Box<Expr>,
         index: usize,
     },
+    // structural field access, whose index is found with the solution of its constraint
+    FieldAccess {
+        accessed: Box<Expr>,
+        constraint_id: usize,
+    },
     Index {
         indexed: Box<Expr>,
         index: Box<Expr>,
@@ -112,5 +132,44 @@ pub enum Expr {
     Gt(Box<Expr>, Box<Expr>),
     Ge(Box<Expr>, Box<Expr>),
 
+    Str(Box<Expr>),
+
+    ArrayLen(Box<Expr>),
+    ArraySet(Box<Expr>, Box<Expr>, Box<Expr>),
+    ArrayPush(Box<Expr>, Box<Expr>),
+    ArrayPop(Box<Expr>),
+    ArraySlice(Box<Expr>, Box<Expr>, Box<Expr>),
+
     Panic(Box<Expr>),
 }
+
+impl Expr {
+    // whether evaluating the expression can't allocate mutable values (like arrays)
+    // only such expressions have their let-binding generalized
+    pub fn is_nonexpansive(&self) -> bool {
+        match self {
+            Self::Missing
+            | Self::Int { .. }
+            | Self::Float { .. }
+            | Self::String { .. }
+            | Self::Bool { .. }
+            | Self::Var { .. }
+            | Self::AbstractVar { .. }
+            | Self::Fun { .. }
+            | Self::FunGroup { .. }
+            | Self::ClassItem { .. }
+            | Self::Newtype
+            | Self::Builtin(_) => true,
+            Self::Tuple { items } => items.iter().all(Self::is_nonexpansive),
+            Self::Record { fields } => fields.iter().all(Self::is_nonexpansive),
+            Self::Variant { items, .. } => items.iter().flatten().all(Self::is_nonexpansive),
+            Self::Access { accessed, .. } | Self::FieldAccess { accessed, .. } => {
+                accessed.is_nonexpansive()
+            }
+            Self::Call { callee, args } if matches!(**callee, Self::Newtype) => {
+                args.iter().all(Self::is_nonexpansive)
+            }
+            _ => false,
+        }
+    }
+}
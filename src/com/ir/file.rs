@@ -1,6 +1,9 @@
-use super::{Solution, Stmt};
+use super::{Solution, Stmt, VariableID};
+use crate::exe::LayoutID;
 
 pub struct Module {
     pub stmts: Box<[Stmt]>,
     pub solutions: Vec<Solution>,
+    pub result: Option<LayoutID>,
+    pub functions: Vec<(String, VariableID, LayoutID)>,
 }
@@ -1,4 +1,6 @@
-use super::{CheckedExpr, ClassID, ImportID, RecordID, TypeID, UnionID, VariableID};
+use super::{
+    CheckedExpr, ClassID, ImportID, InstanceID, RecordID, SynonymID, TypeID, UnionID, VariableID,
+};
 
 #[derive(Clone, Debug)]
 pub enum PathQuery {
@@ -9,7 +11,9 @@ pub enum PathQuery {
     Record(RecordID),
     Union(UnionID),
     Variant(UnionID, usize),
+    Synonym(SynonymID),
     Class(ClassID),
     ClassItem(ClassID, usize),
+    Instance(InstanceID),
     Import(ImportID),
 }
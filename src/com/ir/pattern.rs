@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use super::{RecordID, UnionID, VariableID};
 
 #[derive(Debug, Clone)]
@@ -12,6 +14,10 @@ pub enum Pattern {
     Tuple(Box<[Pattern]>),
     Variant(UnionID, usize, Option<Box<[Pattern]>>),
     Record(RecordID, Box<[Pattern]>),
+    // all alternatives bind the same variables
+    Or(Box<[Pattern]>),
+    // (prefix, rest, suffix), tested by length
+    Array(Box<[Pattern]>, Option<Box<Pattern>>, Box<[Pattern]>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -39,6 +45,8 @@ impl Pattern {
             Self::Tuple(items) => items.iter().all(|item| item.is_exhaustive()),
             Self::Variant(_, _, _) => false,
             Self::Record(_, fields) => fields.iter().all(|field| field.is_exhaustive()),
+            Self::Or(alternatives) => alternatives.first().is_some_and(|alt| alt.is_exhaustive()),
+            Self::Array(..) => false,
         }
     }
 
@@ -55,6 +63,8 @@ impl Pattern {
             Self::Tuple(items) => C::Tuple(items.len()),
             Self::Variant(id, var, _) => C::Variant(*id, *var),
             Self::Record(id, _) => C::Record(*id),
+            Self::Or(_) => unreachable!("or-patterns have no single constructor"),
+            Self::Array(..) => C::Missing, // arrays are tested by length instead
         }
     }
 
@@ -71,6 +81,8 @@ impl Pattern {
             Self::Variant(_, _, None) => vec![],
             Self::Variant(_, _, Some(items)) => items.to_vec(),
             Self::Record(_, fields) => fields.to_vec(),
+            Self::Or(_) => unreachable!("or-patterns have no single constructor"),
+            Self::Array(..) => vec![],
         }
     }
 
@@ -99,6 +111,17 @@ impl Pattern {
                     field.collect_bindings(bindings);
                 }
             }
+            Self::Or(alternatives) => {
+                if let Some(first) = alternatives.first() {
+                    first.collect_bindings(bindings);
+                }
+            }
+            Self::Array(prefix, rest, suffix) => {
+                let rest = rest.as_deref().into_iter();
+                for item in prefix.iter().chain(rest).chain(suffix.iter()) {
+                    item.collect_bindings(bindings);
+                }
+            }
         }
     }
 
@@ -107,4 +130,29 @@ impl Pattern {
         self.collect_bindings(&mut bindings);
         bindings
     }
+
+    pub fn replace_bindings(&mut self, map: &HashMap<VariableID, VariableID>) {
+        match self {
+            Self::Binding(id) => {
+                if let Some(new_id) = map.get(id) {
+                    *id = *new_id;
+                }
+            }
+            Self::Tuple(items)
+            | Self::Variant(_, _, Some(items))
+            | Self::Record(_, items)
+            | Self::Or(items) => {
+                for item in items {
+                    item.replace_bindings(map);
+                }
+            }
+            Self::Array(prefix, rest, suffix) => {
+                let rest = rest.as_deref_mut().into_iter();
+                for item in prefix.iter_mut().chain(rest).chain(suffix.iter_mut()) {
+                    item.replace_bindings(map);
+                }
+            }
+            _ => {}
+        }
+    }
 }
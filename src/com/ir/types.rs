@@ -5,7 +5,7 @@ use crate::com::{
 use colored::Colorize;
 use std::{collections::BTreeSet, fmt::Display};
 
-use super::{ClassID, InstanceID, RecordID, UnionID};
+use super::{ClassID, InstanceID, RecordID, SynonymID, UnionID};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TypeID(pub usize);
@@ -22,6 +22,8 @@ pub struct TypeNode {
     pub loc: Option<Loc>,
     pub depth: usize,
     pub provenances: Vec<TypeProvenance>,
+    // the type synonym this type was expanded from, with its arguments
+    pub synonym: Option<(SynonymID, Box<[TypeID]>)>,
 }
 
 #[derive(Clone)]
@@ -46,6 +48,8 @@ pub struct InstanceScheme {
     pub forall: BTreeSet<TypeID>,
     pub constraint: Constraint,
     pub required_constraints: Vec<Constraint>,
+    // constraints of the items on the instance itself, which it solves
+    pub self_constraints: Vec<Constraint>,
 }
 
 #[derive(Clone)]
@@ -65,10 +69,18 @@ pub struct ConstraintTrace {
 #[derive(Debug, Clone)]
 pub struct Solution {
     pub trace: ConstraintTrace,
-    pub instance_id: InstanceID,
+    pub dictionary: Dictionary,
     pub additional_constraint_id: usize,
 }
 
+// what the solved constraint is compiled to
+#[derive(Debug, Clone, Copy)]
+pub enum Dictionary {
+    Instance(InstanceID),
+    // the index of the field within the record, for structural field access
+    FieldOffset(usize),
+}
+
 #[derive(Clone, Debug)]
 pub enum Type {
     Var,
@@ -81,6 +93,17 @@ pub enum Type {
     Lambda(Box<[TypeID]>, TypeID),
     Record(RecordID, Option<Box<[TypeID]>>),
     Union(UnionID, Option<Box<[TypeID]>>),
+    // a type constructor given only its first arguments, such as 'list' or 'either(e)'
+    Constructor(TypeConstructor, Box<[TypeID]>),
+    // a type variable standing for a type constructor, applied to its last arguments
+    Apply(TypeID, Box<[TypeID]>),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TypeConstructor {
+    Array,
+    Record(RecordID),
+    Union(UnionID),
 }
 
 impl Type {
@@ -1,4 +1,5 @@
 use super::ir::{self, Solution, VariableID};
+use crate::exe::{Layout, LayoutID, LayoutTable};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -81,6 +82,17 @@ pub enum Expr {
     Skip {
         label: ir::LabelID,
     },
+    Raise {
+        tag: String,
+        value: Box<Expr>,
+        layout: LayoutID,
+    },
+    Try {
+        tag: String,
+        body: Box<Expr>,
+        decision: Box<Decision>,
+        fallback: Box<Expr>,
+    },
 
     Add(Box<Expr>, Box<Expr>),
     Sub(Box<Expr>, Box<Expr>),
@@ -115,6 +127,14 @@ pub enum Expr {
     Gt(Box<Expr>, Box<Expr>),
     Ge(Box<Expr>, Box<Expr>),
 
+    Str(Box<Expr>),
+
+    ArrayLen(Box<Expr>),
+    ArraySet(Box<Expr>, Box<Expr>, Box<Expr>),
+    ArrayPush(Box<Expr>, Box<Expr>),
+    ArrayPop(Box<Expr>),
+    ArraySlice(Box<Expr>, Box<Expr>, Box<Expr>),
+
     Panic(Box<Expr>),
 }
 
@@ -129,6 +149,15 @@ pub enum Decision {
         success: Box<Decision>,
         failure: Box<Decision>,
     },
+    Guard {
+        guard: Box<Expr>,
+        success: Box<Decision>,
+        failure: Box<Decision>,
+    },
+    Bind {
+        stmts: Box<[Stmt]>,
+        next: Box<Decision>,
+    },
 }
 
 impl Expr {
@@ -147,6 +176,10 @@ pub enum Stmt {
     Let {
         bindings: Box<[(Pat, Expr)]>,
     },
+    Assign {
+        local: u8,
+        value: Box<Expr>,
+    },
     Block {
         stmts: Box<[Stmt]>,
         needs_frame: bool,
@@ -180,6 +213,9 @@ pub struct Function {
 
 pub struct Program {
     pub functions: Vec<Function>,
+    pub layouts: LayoutTable,
+    pub result: LayoutID,
+    pub exports: Vec<(String, LayoutID)>,
 }
 
 struct Work {
@@ -230,8 +266,12 @@ struct Lowerer {
 
     abstractions: Vec<AbstractionInfo>,
     abstraction_key_by_var: HashMap<ir::VariableID, usize>,
+    // abstract functions lowered for a given solution, which may refer to themselves
+    abstract_functions: HashMap<(ir::VariableID, usize), FunID>,
+    abstract_captures_in_progress: HashSet<(ir::VariableID, usize)>,
 
     builtins: HashMap<ir::Builtin, FunID>,
+    newtype_constructor: Option<FunID>,
 }
 
 impl Lowerer {
@@ -251,8 +291,11 @@ impl Lowerer {
 
             abstractions: Vec::new(),
             abstraction_key_by_var: HashMap::new(),
+            abstract_functions: HashMap::new(),
+            abstract_captures_in_progress: HashSet::new(),
 
             builtins: HashMap::new(),
+            newtype_constructor: None,
         }
     }
 
@@ -264,16 +307,57 @@ impl Lowerer {
     fn lower_program(
         mut self,
         mut modules: Vec<ir::Module>,
+        mut layouts: LayoutTable,
         dependency_order: Vec<usize>,
     ) -> Program {
         let mut stmts = Vec::new();
         let mut solutions = Vec::new();
+        // empty programs return unit
+        let mut result = layouts.add(Layout::Tuple(Box::new([])));
+        let mut exports = Vec::new();
         for file_id in dependency_order {
             let file_stmts = std::mem::take(&mut modules[file_id].stmts);
             stmts.extend_from_slice(&file_stmts);
             solutions.append(&mut modules[file_id].solutions);
+
+            // the program's result is the one of the last module computing anything
+            if let Some(module_result) = modules[file_id].result {
+                result = module_result;
+            }
+
+            exports.append(&mut modules[file_id].functions);
         }
 
+        // the main function returns its result along with the exported functions
+        // so that the host may call them once the program has run
+        let last = stmts
+            .iter()
+            .rposition(|stmt| !matches!(stmt, ir::Stmt::Missing | ir::Stmt::Nothing));
+        let result_expr = match last.map(|i| stmts.remove(i)) {
+            Some(ir::Stmt::Expr { expr, .. }) => expr,
+            other => {
+                stmts.extend(other);
+                ir::Expr::Tuple {
+                    items: Box::new([]),
+                }
+            }
+        };
+        let export_exprs = exports
+            .iter()
+            .map(|(_, id, _)| ir::Expr::Var { id: *id })
+            .collect();
+        stmts.push(ir::Stmt::Expr {
+            expr: ir::Expr::Tuple {
+                items: Box::new([
+                    result_expr,
+                    ir::Expr::Tuple {
+                        items: export_exprs,
+                    },
+                ]),
+            },
+            ty: ir::TypeID::whatever(),
+        });
+
         // build main function task
         use ir::Signature as Sig;
         self.add_work(
@@ -297,7 +381,15 @@ impl Lowerer {
             functions.push(fun);
         }
 
-        Program { functions }
+        Program {
+            functions,
+            layouts,
+            result,
+            exports: exports
+                .into_iter()
+                .map(|(name, _, layout)| (name, layout))
+                .collect(),
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -331,7 +423,10 @@ impl Lowerer {
             panic!("unknown solution for constraint id '{constraint_id}'")
         };
         let solution = &solutions[0];
-        let instance_info = self.entities.get_instance_info(solution.instance_id);
+        let ir::Dictionary::Instance(instance_id) = solution.dictionary else {
+            panic!("class item solved without an instance")
+        };
+        let instance_info = self.entities.get_instance_info(instance_id);
         let item_info = &instance_info.items[item_id];
 
         let var_id = item_info.binding;
@@ -345,19 +440,19 @@ impl Lowerer {
     }
 
     fn register_solutions(&mut self, solutions: Vec<ir::Solution>) -> SolutionMap {
-        let orig = std::mem::take(&mut self.solutions);
+        let orig = self.solutions.clone();
 
         // construct the map: (constraint_id -> [solutions...])
+        let mut registered = SolutionMap::default();
         for mut solution in solutions {
             if let Some(constraint_id) = solution.trace.constraint_ids.pop() {
-                self.solutions.entry(constraint_id).or_default();
-                self.solutions
-                    .get_mut(&constraint_id)
-                    .unwrap()
-                    .push(solution);
+                registered.entry(constraint_id).or_default().push(solution);
             }
         }
 
+        // constraints deferred by nested statements are still solved by the enclosing ones
+        self.solutions.extend(registered);
+
         orig
     }
 
@@ -583,6 +678,9 @@ impl Lowerer {
                     Self::collect_unwrappings(prev.clone().index(i).index(2), item, map);
                 }
             }
+            // only irrefutable or-patterns are unwrapped, through their first alternative
+            P::Or(alternatives) => Self::collect_unwrappings(prev, &alternatives[0], map),
+            P::Array(..) => unreachable!("array patterns are never irrefutable"),
         }
     }
 
@@ -614,8 +712,9 @@ impl Lowerer {
 
         let orig = self.register_solutions(solutions);
 
-        let pat = self.lower_pattern(lhs);
+        // the value is computed before its bindings occupy any local
         let expr = self.lower_expression(rhs);
+        let pat = self.lower_pattern(lhs);
 
         self.restore_solutions(orig);
 
@@ -685,6 +784,10 @@ impl Lowerer {
                     .map(|item| self.lower_pattern(item))
                     .collect(),
             ),
+            // decision trees expand or-patterns, so this is an irrefutable let
+            // whose first alternative always matches
+            P::Or(alternatives) => self.lower_pattern(alternatives.into_vec().remove(0)),
+            P::Array(..) => unreachable!("array patterns are tested by length"),
         }
     }
 
@@ -708,16 +811,24 @@ impl Lowerer {
             } => self.lower_conditional(branches, is_exhaustive),
             E::Break { expr, label } => self.lower_break(label, expr),
             E::Skip { label } => self.lower_skip(label),
+            E::Raise { value, exception } => self.lower_raise(*value, exception),
+            E::Assign { var, value } => self.lower_assign(var, *value),
             E::Fun {
                 name,
                 recursive_binding,
                 signature,
                 expr,
             } => self.lower_fun(name, recursive_binding, *signature, *expr),
+            E::FunGroup { bindings, funs } => self.lower_fun_group(&bindings, funs),
             E::Call { callee, args } => self.lower_call(*callee, args),
             E::Variant { tag, items } => self.lower_variant(tag, items),
+            E::Newtype => self.lower_newtype_constructor(),
             E::Record { fields } => self.lower_small_bundle(fields),
             E::Access { accessed, index } => self.lower_access(*accessed, index),
+            E::FieldAccess {
+                accessed,
+                constraint_id,
+            } => self.lower_field_access(*accessed, constraint_id),
             E::Index { indexed, index } => self.lower_index(*indexed, *index),
             E::ClassItem {
                 item_id,
@@ -812,6 +923,25 @@ impl Lowerer {
                 Box::new(self.lower_expression(*right)),
             ),
 
+            E::Str(arg) => Expr::Str(Box::new(self.lower_expression(*arg))),
+
+            E::ArrayLen(array) => Expr::ArrayLen(Box::new(self.lower_expression(*array))),
+            E::ArraySet(array, index, value) => Expr::ArraySet(
+                Box::new(self.lower_expression(*array)),
+                Box::new(self.lower_expression(*index)),
+                Box::new(self.lower_expression(*value)),
+            ),
+            E::ArrayPush(array, value) => Expr::ArrayPush(
+                Box::new(self.lower_expression(*array)),
+                Box::new(self.lower_expression(*value)),
+            ),
+            E::ArrayPop(array) => Expr::ArrayPop(Box::new(self.lower_expression(*array))),
+            E::ArraySlice(array, start, end) => Expr::ArraySlice(
+                Box::new(self.lower_expression(*array)),
+                Box::new(self.lower_expression(*start)),
+                Box::new(self.lower_expression(*end)),
+            ),
+
             E::Panic(arg) => Expr::Panic(Box::new(self.lower_expression(*arg))),
         }
     }
@@ -884,6 +1014,21 @@ impl Lowerer {
     fn lower_abstract_variable(&mut self, id: ir::VariableID, constraint_id: usize) -> Expr {
         let info = self.get_variable_abstraction_info(id);
         let abstract_expr = info.abstract_expr.clone();
+        let unwrapping = info.unwrappings_by_binding[&id].clone();
+
+        // instances refer back to themselves through the same solution, only reuse
+        // the function from within its own body where its captures are locals
+        let cached = self.abstract_functions.get(&(id, constraint_id)).copied();
+        if let Some(fun_id) = cached.filter(|fun_id| *fun_id == self.current_fun_id) {
+            let capture_info = &self.capture_info_by_fun_id[&fun_id];
+            return Expr::Unwrap {
+                value: Box::new(Expr::Fun {
+                    id: fun_id,
+                    captured: self.get_captured_locals_from_info(capture_info),
+                }),
+                unwrapping,
+            };
+        }
 
         let abstract_expr_solutions = self.build_abstract_expression_solutions(id, constraint_id);
 
@@ -891,9 +1036,9 @@ impl Lowerer {
         let expr = self.lower_expression(abstract_expr);
         self.restore_solutions(orig);
 
-        // unwrap the correct binding from the abstract bundle
-        let info = self.get_variable_abstraction_info(id);
-        let unwrapping = info.unwrappings_by_binding[&id].clone();
+        if let Expr::Fun { id: fun_id, .. } = &expr {
+            self.abstract_functions.insert((id, constraint_id), *fun_id);
+        }
 
         // emit the abstract variable and return the unwrapping expression
         Expr::Unwrap {
@@ -922,6 +1067,17 @@ impl Lowerer {
         }
     }
 
+    // the index of the field is the dictionary which solved the constraint on the access
+    fn lower_field_access(&mut self, accessed: ir::Expr, constraint_id: usize) -> Expr {
+        let Some(solutions) = self.solutions.get(&constraint_id) else {
+            panic!("unknown solution for constraint id '{constraint_id}'")
+        };
+        let ir::Dictionary::FieldOffset(index) = solutions[0].dictionary else {
+            panic!("field access solved by an instance")
+        };
+        self.lower_access(accessed, index)
+    }
+
     fn lower_index(&mut self, indexed: ir::Expr, index: ir::Expr) -> Expr {
         Expr::Index {
             indexed: Box::new(self.lower_expression(indexed)),
@@ -930,9 +1086,25 @@ impl Lowerer {
     }
 
     fn lower_conditional(&mut self, branches: Box<[ir::Branch]>, is_exhaustive: bool) -> Expr {
+        use ir::Branch as B;
+
+        // some branches keep locals alive while their fallback runs
+        // so the locals of the following branches must come after them
+        let local_index_orig = self.local_index;
+        let mut local_indices = Vec::with_capacity(branches.len());
+        let mut local_index = local_index_orig;
+        for branch in &branches {
+            local_indices.push(local_index);
+            local_index += match branch {
+                B::Match { .. } => 1,
+                B::For { state, .. } => state.len(),
+                _ => 0,
+            };
+        }
+
         let mut fallback = Expr::unit();
-        for branch in branches.into_iter().rev() {
-            use ir::Branch as B;
+        for (branch, local_index) in branches.into_iter().zip(local_indices).rev() {
+            self.local_index = local_index;
             match branch {
                 B::If { guard, body, label } => {
                     fallback = Expr::If {
@@ -953,6 +1125,33 @@ impl Lowerer {
                         else_branch: Box::new(fallback),
                     }
                 }
+                B::For {
+                    state,
+                    guard,
+                    body,
+                    label,
+                } => {
+                    let mut bindings = Vec::with_capacity(state.len());
+                    for (var, value) in state {
+                        let value = self.lower_expression(value);
+                        let local = self.register_local(var);
+                        bindings.push(Stmt::Let {
+                            bindings: Box::new([(Pat::Local(local), value)]),
+                        });
+                    }
+
+                    fallback = Expr::Block {
+                        label: None,
+                        stmts: bindings.into(),
+                        result: Box::new(Expr::While {
+                            label,
+                            guard: Box::new(self.lower_expression(*guard)),
+                            do_branch: Box::new(self.lower_statement_block_as_statement(body)),
+                            else_branch: Box::new(fallback),
+                        }),
+                        needs_frame: true,
+                    }
+                }
                 B::Loop { body, label } => {
                     fallback = Expr::Loop {
                         label,
@@ -968,17 +1167,49 @@ impl Lowerer {
                     decision,
                 } => {
                     let scrutinee = self.lower_expression(*scrutinee);
+                    let local_index_orig = self.local_index;
                     self.register_local(scrutinee_var);
                     let decision = self.lower_decision(*decision, is_exhaustive);
+                    self.local_index = local_index_orig;
                     fallback = Expr::Match {
                         scrutinee: Box::new(scrutinee),
                         decision: Box::new(decision),
                         fallback: Box::new(fallback),
                     };
                 }
+                B::Try {
+                    body,
+                    label,
+                    exception,
+                    caught_var,
+                    decision,
+                } => {
+                    let info = self.entities.get_exception_info(exception);
+                    let (tag, layout) = (info.tag.clone(), info.layout);
+                    let body = self.lower_block_expression(body, Some(label));
+
+                    // the caught value lives in the handler's frame
+                    // if no case matches it, it is raised again
+                    let local_index_orig = self.local_index;
+                    let caught = self.register_local(caught_var);
+                    let decision = self.lower_decision(*decision, true);
+                    self.local_index = local_index_orig;
+
+                    fallback = Expr::Try {
+                        tag: tag.clone(),
+                        body: Box::new(body),
+                        decision: Box::new(decision),
+                        fallback: Box::new(Expr::Raise {
+                            tag,
+                            value: Box::new(Expr::Local { local: caught }),
+                            layout,
+                        }),
+                    };
+                }
             }
         }
 
+        self.local_index = local_index_orig;
         fallback
     }
 
@@ -1024,6 +1255,29 @@ impl Lowerer {
                     failure: Box::new(failure),
                 }
             }
+            D::Guard {
+                guard,
+                success,
+                failure,
+            } => {
+                let guard = self.lower_expression(*guard);
+
+                // same as tests, the success branch may bind locals the failure branch can't see
+                let local_index_orig = self.local_index;
+                let success = self.lower_decision(*success, is_exhaustive);
+                self.local_index = local_index_orig;
+
+                let failure = self.lower_decision(*failure, is_exhaustive);
+                Decision::Guard {
+                    guard: Box::new(guard),
+                    success: Box::new(success),
+                    failure: Box::new(failure),
+                }
+            }
+            D::Bind { stmts, next } => Decision::Bind {
+                stmts: self.lower_statement_list(stmts).into(),
+                next: Box::new(self.lower_decision(*next, is_exhaustive)),
+            },
         }
     }
 
@@ -1041,6 +1295,28 @@ impl Lowerer {
         Expr::Skip { label }
     }
 
+    fn lower_raise(&mut self, value: ir::Expr, exception: ir::ExceptionID) -> Expr {
+        let info = self.entities.get_exception_info(exception);
+        let (tag, layout) = (info.tag.clone(), info.layout);
+        Expr::Raise {
+            tag,
+            value: Box::new(self.lower_expression(value)),
+            layout,
+        }
+    }
+
+    fn lower_assign(&mut self, var: ir::VariableID, value: ir::Expr) -> Expr {
+        Expr::Block {
+            label: None,
+            stmts: Box::new([Stmt::Assign {
+                local: self.get_local(var),
+                value: Box::new(self.lower_expression(value)),
+            }]),
+            result: Box::new(Expr::unit()),
+            needs_frame: false,
+        }
+    }
+
     fn lower_variant(&mut self, tag: usize, items: Option<Box<[ir::Expr]>>) -> Expr {
         Expr::Variant {
             tag: tag as i64,
@@ -1113,6 +1389,62 @@ impl Lowerer {
         }
     }
 
+    // the functions of a group all capture the same variables
+    // so that each of them can be rebuilt from within any other
+    fn lower_fun_group(&mut self, bindings: &[ir::VariableID], funs: Box<[ir::Expr]>) -> Expr {
+        let mut captured = HashSet::new();
+        let mut functions = HashMap::new();
+        for fun in &funs {
+            self.collect_expr_captured_variables(fun, &mut captured, &mut functions);
+        }
+
+        let ids = funs
+            .iter()
+            .map(|_| self.next_function_id())
+            .collect::<Vec<_>>();
+        functions.extend(bindings.iter().copied().zip(ids.iter().copied()));
+
+        let capture_info = CaptureInfo {
+            variables: captured.into_iter().collect(),
+            functions,
+        };
+        let captured_locals = self.get_captured_locals_from_info(&capture_info);
+
+        let solutions = self.rebuild_solutions();
+
+        let mut items = Vec::with_capacity(funs.len());
+        for (fun, id) in funs.into_iter().zip(ids) {
+            let ir::Expr::Fun {
+                name,
+                recursive_binding,
+                signature,
+                expr,
+            } = fun
+            else {
+                panic!("attempt to lower function group with a non-function");
+            };
+
+            self.capture_info_by_fun_id.insert(id, capture_info.clone());
+            self.work.push(Work {
+                name,
+                id,
+                recursive_binding,
+                recursive_fun_id: id,
+                expr: *expr,
+                signature: *signature,
+                solutions: solutions.clone(),
+            });
+            items.push(Expr::Fun {
+                id,
+                captured: captured_locals.clone(),
+            });
+        }
+
+        Expr::Bundle {
+            items: items.into(),
+        }
+    }
+
     fn get_current_capture_info(&self) -> &CaptureInfo {
         &self.capture_info_by_fun_id[&self.current_fun_id]
     }
@@ -1154,15 +1486,27 @@ impl Lowerer {
         use ir::Expr as E;
         match expr {
             E::Missing | E::Int { .. } | E::Float { .. } | E::String { .. } | E::Bool { .. } => {}
+            E::Newtype => {}
             E::Var { id } => {
-                if let Some(fun_id) = self.get_current_capture_info().functions.get(id) {
-                    fun_map.insert(*id, *fun_id);
+                // a recursive function is rebuilt from what it captures
+                if let Some(fun_id) = self.get_current_capture_info().functions.get(id).copied() {
+                    fun_map.insert(*id, fun_id);
+                    for captured_id in &self.capture_info_by_fun_id[&fun_id].variables {
+                        if self.local_by_var.contains_key(captured_id) {
+                            set.insert(*captured_id);
+                        }
+                    }
                 }
                 if self.local_by_var.contains_key(id) {
                     set.insert(*id);
                 }
             }
             E::AbstractVar { id, constraint_id } => {
+                let key = (*id, *constraint_id);
+                if !self.abstract_captures_in_progress.insert(key) {
+                    return;
+                }
+
                 let info = self.get_variable_abstraction_info(*id);
                 let abstract_expr = info.abstract_expr.clone();
 
@@ -1172,6 +1516,8 @@ impl Lowerer {
                 let orig = self.register_solutions(abstract_expr_solutions);
                 self.collect_expr_captured_variables(&abstract_expr, set, fun_map);
                 self.restore_solutions(orig);
+
+                self.abstract_captures_in_progress.remove(&key);
             }
             E::Tuple { items } | E::Array { items } => {
                 for item in items {
@@ -1205,6 +1551,20 @@ impl Lowerer {
                                 self.collect_stmt_captured_variables(stmt, set, fun_map);
                             }
                         }
+                        B::For {
+                            state,
+                            guard,
+                            body,
+                            label: _,
+                        } => {
+                            for (_, value) in state {
+                                self.collect_expr_captured_variables(value, set, fun_map);
+                            }
+                            self.collect_expr_captured_variables(guard, set, fun_map);
+                            for stmt in body {
+                                self.collect_stmt_captured_variables(stmt, set, fun_map);
+                            }
+                        }
                         B::Loop { body, label: _ } | B::Else { body, label: _ } => {
                             for stmt in body {
                                 self.collect_stmt_captured_variables(stmt, set, fun_map);
@@ -1215,6 +1575,18 @@ impl Lowerer {
                             scrutinee: _,
                             decision,
                         } => self.collect_decision_captured_variables(decision, set, fun_map),
+                        B::Try {
+                            body,
+                            label: _,
+                            exception: _,
+                            caught_var: _,
+                            decision,
+                        } => {
+                            for stmt in body {
+                                self.collect_stmt_captured_variables(stmt, set, fun_map);
+                            }
+                            self.collect_decision_captured_variables(decision, set, fun_map)
+                        }
                     }
                 }
             }
@@ -1223,12 +1595,27 @@ impl Lowerer {
                 label: _,
             } => self.collect_expr_captured_variables(expr, set, fun_map),
             E::Break { .. } | E::Skip { .. } => {}
+            E::Raise {
+                value,
+                exception: _,
+            } => self.collect_expr_captured_variables(value, set, fun_map),
+            E::Assign { var, value } => {
+                if self.local_by_var.contains_key(var) {
+                    set.insert(*var);
+                }
+                self.collect_expr_captured_variables(value, set, fun_map);
+            }
             E::Fun {
                 name: _,
                 recursive_binding: _,
                 signature: _,
                 expr,
             } => self.collect_expr_captured_variables(expr, set, fun_map),
+            E::FunGroup { bindings: _, funs } => {
+                for fun in funs {
+                    self.collect_expr_captured_variables(fun, set, fun_map);
+                }
+            }
             E::Call { callee, args } => {
                 self.collect_expr_captured_variables(callee, set, fun_map);
                 for arg in args {
@@ -1247,7 +1634,11 @@ impl Lowerer {
                     self.collect_expr_captured_variables(field, set, fun_map);
                 }
             }
-            E::Access { accessed, index: _ } => {
+            E::Access { accessed, index: _ }
+            | E::FieldAccess {
+                accessed,
+                constraint_id: _,
+            } => {
                 self.collect_expr_captured_variables(accessed, set, fun_map);
             }
             E::Index { indexed, index } => {
@@ -1278,7 +1669,8 @@ impl Lowerer {
             | E::Le(left, right)
             | E::Gt(left, right)
             | E::Ge(left, right)
-            | E::Pow(left, right) => {
+            | E::Pow(left, right)
+            | E::ArrayPush(left, right) => {
                 self.collect_expr_captured_variables(left, set, fun_map);
                 self.collect_expr_captured_variables(right, set, fun_map);
             }
@@ -1293,10 +1685,19 @@ impl Lowerer {
             | E::Tan(arg)
             | E::Asin(arg)
             | E::Acos(arg)
-            | E::Atan(arg) => {
+            | E::Atan(arg)
+            | E::Str(arg)
+            | E::ArrayLen(arg)
+            | E::ArrayPop(arg) => {
                 self.collect_expr_captured_variables(arg, set, fun_map);
             }
 
+            E::ArraySet(first, second, third) | E::ArraySlice(first, second, third) => {
+                self.collect_expr_captured_variables(first, set, fun_map);
+                self.collect_expr_captured_variables(second, set, fun_map);
+                self.collect_expr_captured_variables(third, set, fun_map);
+            }
+
             E::Panic(arg) => {
                 self.collect_expr_captured_variables(arg, set, fun_map);
             }
@@ -1327,16 +1728,67 @@ impl Lowerer {
                 self.collect_decision_captured_variables(success, set, fun_map);
                 self.collect_decision_captured_variables(failure, set, fun_map);
             }
+            D::Guard {
+                guard,
+                success,
+                failure,
+            } => {
+                self.collect_expr_captured_variables(guard, set, fun_map);
+                self.collect_decision_captured_variables(success, set, fun_map);
+                self.collect_decision_captured_variables(failure, set, fun_map);
+            }
+            D::Bind { stmts, next } => {
+                for stmt in stmts {
+                    self.collect_stmt_captured_variables(stmt, set, fun_map);
+                }
+                self.collect_decision_captured_variables(next, set, fun_map);
+            }
         }
     }
 
     fn lower_call(&mut self, callee: ir::Expr, args: Box<[ir::Expr]>) -> Expr {
+        // applying a newtype constructor leaves the value as is
+        if let (ir::Expr::Newtype, [_]) = (&callee, &*args) {
+            let arg = args.into_vec().pop().unwrap();
+            return self.lower_expression(arg);
+        }
+
         Expr::Call {
             callee: Box::new(self.lower_expression(callee)),
             args: self.lower_expression_list(args).into(),
         }
     }
 
+    // newtype constructors are only ever used as values when they aren't applied
+    fn lower_newtype_constructor(&mut self) -> Expr {
+        let id = match self.newtype_constructor {
+            Some(id) => id,
+            None => {
+                let arg = self.entities.create_dummy_variable();
+                let signature = ir::Signature::Args {
+                    args: Box::new([ir::Pattern::Binding(arg)]),
+                    next: Box::new(ir::Signature::Done),
+                };
+                let id = self.add_work(
+                    "newtype".to_string(),
+                    None,
+                    None,
+                    ir::Expr::Var { id: arg },
+                    signature,
+                    CaptureInfo::default(),
+                    Vec::new(),
+                );
+                self.newtype_constructor = Some(id);
+                id
+            }
+        };
+
+        Expr::Fun {
+            id,
+            captured: Box::new([]),
+        }
+    }
+
     fn lower_builtin(&mut self, builtin: ir::Builtin) -> Expr {
         let id = match self.builtins.get(&builtin) {
             Some(id) => *id,
@@ -1373,6 +1825,7 @@ impl Lowerer {
             Bi::int_pos => builtin_unary!(self, Pos),
             Bi::int_neg => builtin_unary!(self, Neg),
             Bi::int_not => builtin_unary!(self, BitNeg),
+            Bi::int_str => builtin_unary!(self, Str),
 
             Bi::float_add => builtin_binary!(self, Add),
             Bi::float_sub => builtin_binary!(self, Sub),
@@ -1387,6 +1840,7 @@ impl Lowerer {
             Bi::float_ge => builtin_binary!(self, Ge),
             Bi::float_pos => builtin_unary!(self, Pos),
             Bi::float_neg => builtin_unary!(self, Neg),
+            Bi::float_str => builtin_unary!(self, Str),
 
             Bi::string_concat => builtin_binary!(self, Add),
             Bi::string_eq => builtin_binary!(self, Eq),
@@ -1413,6 +1867,26 @@ impl Lowerer {
             Bi::acos => builtin_unary!(self, Acos),
             Bi::atan => builtin_unary!(self, Atan),
 
+            Bi::array_len => builtin_unary!(self, ArrayLen),
+            Bi::array_get => {
+                let array = self.entities.create_dummy_variable();
+                let index = self.entities.create_dummy_variable();
+                (
+                    ir::Signature::Args {
+                        args: Box::new([ir::Pattern::Binding(array), ir::Pattern::Binding(index)]),
+                        next: Box::new(ir::Signature::Done),
+                    },
+                    ir::Expr::Index {
+                        indexed: Box::new(ir::Expr::Var { id: array }),
+                        index: Box::new(ir::Expr::Var { id: index }),
+                    },
+                )
+            }
+            Bi::array_set => builtin_ternary!(self, ArraySet),
+            Bi::array_push => builtin_binary!(self, ArrayPush),
+            Bi::array_pop => builtin_unary!(self, ArrayPop),
+            Bi::array_slice => builtin_ternary!(self, ArraySlice),
+
             Bi::panic => builtin_unary!(self, Panic),
         };
 
@@ -1431,9 +1905,10 @@ impl Lowerer {
 pub fn lower(
     modules: Vec<ir::Module>,
     entities: ir::Entities,
+    layouts: LayoutTable,
     dependency_order: Vec<usize>,
 ) -> Program {
-    Lowerer::new(entities).lower_program(modules, dependency_order)
+    Lowerer::new(entities).lower_program(modules, layouts, dependency_order)
 }
 
 macro_rules! builtin_unary {
@@ -1466,5 +1941,29 @@ macro_rules! builtin_binary {
     }};
 }
 
+macro_rules! builtin_ternary {
+    ($self:ident, $ctor:ident) => {{
+        let first = $self.entities.create_dummy_variable();
+        let second = $self.entities.create_dummy_variable();
+        let third = $self.entities.create_dummy_variable();
+        (
+            ir::Signature::Args {
+                args: Box::new([
+                    ir::Pattern::Binding(first),
+                    ir::Pattern::Binding(second),
+                    ir::Pattern::Binding(third),
+                ]),
+                next: Box::new(ir::Signature::Done),
+            },
+            ir::Expr::$ctor(
+                Box::new(ir::Expr::Var { id: first }),
+                Box::new(ir::Expr::Var { id: second }),
+                Box::new(ir::Expr::Var { id: third }),
+            ),
+        )
+    }};
+}
+
 use builtin_binary;
+use builtin_ternary;
 use builtin_unary;
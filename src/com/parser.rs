@@ -6,6 +6,7 @@ use super::{
     ast::{self},
     loc::{Loc, Span},
     reporting::{Header, Label, Report},
+    token,
 };
 
 pub struct Parser<'src, 'e> {
@@ -15,6 +16,7 @@ pub struct Parser<'src, 'e> {
     prev: Token,
     bounds: (usize, usize),
     uid: usize,
+    in_scrutinee: bool,
     reports: &'e mut Vec<Report>,
 }
 
@@ -27,6 +29,7 @@ impl<'src, 'e> Parser<'src, 'e> {
             prev: Token::Eof,
             bounds: (0, 0),
             uid: 0,
+            in_scrutinee: false,
             reports,
         };
 
@@ -154,7 +157,63 @@ impl<'src, 'e> Parser<'src, 'e> {
     }
 
     pub fn try_parse_expression(&mut self) -> Option<ast::Expr> {
-        self.try_parse_operation_expression()
+        // only the outermost expression of a match scrutinee stops at 'with'
+        let in_scrutinee = std::mem::take(&mut self.in_scrutinee);
+        let target = self.try_parse_operation_expression();
+        self.in_scrutinee = in_scrutinee;
+
+        let target = self.parse_optional_instance_selection(target?);
+        let Some(assign) = self.try_expect_token(Token::Assign) else {
+            return Some(target);
+        };
+
+        let value = self.expect_expression();
+        Some(ast::Expr::Assign(ast::Assign {
+            assign,
+            target: Box::new(target),
+            value: Box::new(value),
+        }))
+    }
+
+    fn parse_optional_instance_selection(&mut self, expr: ast::Expr) -> ast::Expr {
+        if self.in_scrutinee {
+            return expr;
+        }
+
+        let Some(with_kw) = self.try_expect_token(Token::With) else {
+            return expr;
+        };
+
+        let instance = self.expect_instance_path();
+        ast::Expr::With(ast::With {
+            expr: Box::new(expr),
+            with_kw,
+            instance: Box::new(instance),
+        })
+    }
+
+    // a name, possibly accessed from a module, as in monoid.sum
+    fn expect_instance_path(&mut self) -> ast::Expr {
+        let Some(mut path) = self.try_parse_var_expression() else {
+            self.reports.push(
+                Report::error(Header::ExpectedExpression())
+                    .with_primary_label(Label::Empty, self.loc_here()),
+            );
+            return ast::Expr::Missing(ast::Lexeme {
+                span: self.span_here(),
+            });
+        };
+
+        while let Some(dot) = self.try_expect_token(Token::Dot) {
+            let accessor = self.expect_accessor();
+            path = ast::Expr::Access(ast::Access {
+                dot,
+                accessor: Box::new(accessor),
+                accessed: Box::new(path),
+            });
+        }
+
+        path
     }
 
     pub fn expect_primary_expression(&mut self) -> ast::Expr {
@@ -227,11 +286,7 @@ impl<'src, 'e> Parser<'src, 'e> {
     fn try_parse_precedence_operation(&mut self, current_prec: usize) -> Option<ast::Expr> {
         let mut lhs = self.try_parse_primary_expression()?;
 
-        loop {
-            let Some(op) = self.try_peek_binary_operator() else {
-                break;
-            };
-
+        while let Some(op) = self.try_peek_binary_operator() {
             let op_prec = op.precedence();
             if op_prec < current_prec {
                 break;
@@ -271,17 +326,19 @@ impl<'src, 'e> Parser<'src, 'e> {
             Token::False => self.try_parse_false_expression(),
             Token::Underscores => self.try_parse_underscores_expression(),
             Token::Ident => self.try_parse_var_expression(),
+            Token::Question => self.try_parse_hole_expression(),
 
             Token::LeftParen => self.try_parse_tuple_expression(),
             Token::LeftBracket => self.try_parse_array_expression(),
             Token::LeftBrace => self.try_parse_record_value_expression(),
 
             Token::Do => self.try_parse_block_expression(),
-            Token::If | Token::While | Token::Loop | Token::Match => {
+            Token::If | Token::While | Token::For | Token::Loop | Token::Match | Token::Try => {
                 self.try_parse_conditional_expression()
             }
             Token::Break => self.try_parse_break_expression(),
             Token::Skip => self.try_parse_skip_expression(),
+            Token::Raise => self.try_parse_raise_expression(),
             Token::Fun => self.try_parse_fun_expression(),
             Token::Let => return self.try_parse_let_expression(),
             Token::Pub => return self.try_parse_pub_expression(),
@@ -291,6 +348,8 @@ impl<'src, 'e> Parser<'src, 'e> {
             Token::Super => self.try_parse_super_expression(),
             Token::Record => self.try_parse_record_expression(),
             Token::Union => self.try_parse_union_expression(),
+            Token::Type => self.try_parse_synonym_expression(),
+            Token::Newtype => self.try_parse_newtype_expression(),
             Token::Class => self.try_parse_class_expression(),
             Token::Have => self.try_parse_have_expression(),
             _ => None,
@@ -308,7 +367,11 @@ impl<'src, 'e> Parser<'src, 'e> {
         }
 
         loop {
-            if let Some(left_paren) = self.try_expect_token(Token::LeftParen) {
+            // a nested expression may have skipped newlines already
+            // in which case a '(' or '[' starts the next expression
+            let same_line = self.prev != Token::Newline;
+
+            if same_line && let Some(left_paren) = self.try_expect_token(Token::LeftParen) {
                 let args = self.parse_comma_separated_items();
                 let right_paren = self.expect_token(Token::RightParen);
 
@@ -321,7 +384,7 @@ impl<'src, 'e> Parser<'src, 'e> {
                 continue;
             }
 
-            if let Some(left_bracket) = self.try_expect_token(Token::LeftBracket) {
+            if same_line && let Some(left_bracket) = self.try_expect_token(Token::LeftBracket) {
                 let indices = self.parse_comma_separated_items();
                 let right_bracket = self.expect_token(Token::RightBracket);
 
@@ -383,8 +446,110 @@ impl<'src, 'e> Parser<'src, 'e> {
     }
 
     fn try_parse_string_expression(&mut self) -> Option<ast::Expr> {
-        self.try_expect_token(Token::String)
-            .map(|token| ast::Expr::String(ast::Lexeme { span: token }))
+        let token = self.try_expect_token(Token::String)?;
+        let contents = &self.source[(token.start + 1)..(token.end - 1)];
+        if !contents.contains(['{', '}']) {
+            return Some(ast::Expr::String(ast::Lexeme { span: token }));
+        }
+
+        Some(ast::Expr::Interpolation(self.parse_interpolation(token)))
+    }
+
+    // "text {hole} text", where '{{' and '}}' escape braces
+    fn parse_interpolation(&mut self, token: Span) -> ast::Interpolation {
+        let bytes = self.source.as_bytes();
+        let end = token.end - 1;
+
+        let mut parts = Vec::new();
+        let mut text_start = token.start + 1;
+        let mut i = text_start;
+        while i < end {
+            match bytes[i] {
+                b'{' | b'}' if i + 1 < end && bytes[i + 1] == bytes[i] => {
+                    i += 2;
+                    continue;
+                }
+                b'}' => {
+                    self.reports.push(
+                        Report::error(Header::InvalidInterpolation()).with_primary_label(
+                            Label::UnmatchedInterpolationBrace,
+                            Span::new(i, i + 1).wrap(self.file),
+                        ),
+                    );
+                    i += 1;
+                    continue;
+                }
+                b'{' => {}
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+
+            if text_start < i {
+                parts.push(ast::InterpolationPart::Text(Span::new(text_start, i)));
+            }
+
+            // find the matching closing brace, holes may contain braces and strings too
+            let hole_end = token::hole_len(&bytes[i + 1..end]).map(|len| i + len);
+
+            let Some(hole_end) = hole_end else {
+                self.reports.push(
+                    Report::error(Header::InvalidInterpolation()).with_primary_label(
+                        Label::UnclosedInterpolationHole,
+                        Span::new(i, end).wrap(self.file),
+                    ),
+                );
+                text_start = end;
+                break;
+            };
+
+            let hole = self.parse_interpolation_hole(Span::new(i + 1, hole_end));
+            parts.push(ast::InterpolationPart::Hole(hole));
+
+            i = hole_end + 1;
+            text_start = i;
+        }
+
+        if text_start < end {
+            parts.push(ast::InterpolationPart::Text(Span::new(text_start, end)));
+        }
+
+        ast::Interpolation {
+            span: token,
+            parts: parts.into(),
+        }
+    }
+
+    // holes are parsed on their own, but their spans stay within the file
+    fn parse_interpolation_hole(&mut self, span: Span) -> ast::Expr {
+        let source = &self.source[..span.end];
+        let mut lexer = Token::lexer(source);
+        lexer.bump(span.start);
+
+        let mut p = Parser {
+            source,
+            file: self.file,
+            lexer: lexer.spanned().peekable(),
+            prev: Token::Eof,
+            bounds: (span.start, span.start),
+            uid: self.uid,
+            in_scrutinee: false,
+            reports: self.reports,
+        };
+        p.peek();
+
+        let hole = p.expect_expression();
+        if p.peek() != Token::Eof {
+            let rest = Span::new(p.pos_from(), span.end);
+            p.reports.push(
+                Report::error(Header::InvalidInterpolation())
+                    .with_primary_label(Label::InterpolationHoleExpression, rest.wrap(p.file)),
+            );
+        }
+
+        self.uid = p.uid;
+        hole
     }
 
     fn try_parse_builtin_expression(&mut self) -> Option<ast::Expr> {
@@ -412,6 +577,12 @@ impl<'src, 'e> Parser<'src, 'e> {
             .map(|token| ast::Expr::Var(ast::Lexeme { span: token }))
     }
 
+    fn try_parse_hole_expression(&mut self) -> Option<ast::Expr> {
+        let question = self.try_expect_token(Token::Question)?;
+        let name = self.expect_token(Token::Ident);
+        Some(ast::Expr::Hole(ast::Hole { question, name }))
+    }
+
     fn try_parse_tuple_expression(&mut self) -> Option<ast::Expr> {
         let left_paren = self.try_expect_token(Token::LeftParen)?;
         let items = self.parse_comma_separated_items();
@@ -426,7 +597,7 @@ impl<'src, 'e> Parser<'src, 'e> {
 
     fn try_parse_array_expression(&mut self) -> Option<ast::Expr> {
         let left_bracket = self.try_expect_token(Token::LeftBracket)?;
-        let items = self.parse_comma_separated_items();
+        let items = self.parse_array_items();
         let right_bracket = self.expect_token(Token::RightBracket);
 
         Some(ast::Expr::Array(ast::Array {
@@ -440,8 +611,16 @@ impl<'src, 'e> Parser<'src, 'e> {
         let left_brace = self.try_expect_token(Token::LeftBrace)?;
         let mut fields = Vec::new();
 
+        // { ..base, field = value }
         self.skip_newlines();
-        while let Some(field) = self.try_parse_expression() {
+        let base = self.try_expect_token(Token::Spread).map(|spread| {
+            let base = self.expect_expression();
+            self.try_expect_token(Token::Comma);
+            self.skip_newlines();
+            (spread, Box::new(base))
+        });
+
+        while let Some(field) = self.try_parse_operation_expression() {
             let expr = self
                 .try_expect_token(Token::Assign)
                 .map(|_| self.expect_expression());
@@ -460,6 +639,7 @@ impl<'src, 'e> Parser<'src, 'e> {
         Some(ast::Expr::RecordValue(ast::RecordValue {
             left_brace,
             right_brace,
+            base,
             fields: fields.into(),
         }))
     }
@@ -500,8 +680,10 @@ impl<'src, 'e> Parser<'src, 'e> {
         match self.peek() {
             Token::If => self.try_parse_if_branch(),
             Token::While => self.try_parse_while_branch(),
+            Token::For => self.try_parse_for_branch(),
             Token::Loop => self.try_parse_loop_branch(),
             Token::Match => self.try_parse_match_branch(),
+            Token::Try => self.try_parse_try_branch(),
             _ => None,
         }
     }
@@ -552,6 +734,30 @@ impl<'src, 'e> Parser<'src, 'e> {
         }))
     }
 
+    fn try_parse_for_branch(&mut self) -> Option<ast::Branch> {
+        let for_kw = self.try_expect_token(Token::For)?;
+        let label = self.parse_optional_label();
+        let pattern = self.expect_expression();
+        let in_kw = self.expect_token(Token::In);
+        let iterable = self.expect_expression();
+        let range_end = self
+            .try_expect_token(Token::Spread)
+            .map(|spread| (spread, Box::new(self.expect_expression())));
+        let do_kw = self.expect_token(Token::Do);
+        let body = self.parse_newline_separated_items();
+
+        Some(ast::Branch::For(ast::ForBranch {
+            for_kw,
+            in_kw,
+            do_kw,
+            label: Box::new(label),
+            pattern: Box::new(pattern),
+            iterable: Box::new(iterable),
+            range_end,
+            body,
+        }))
+    }
+
     fn try_parse_loop_branch(&mut self) -> Option<ast::Branch> {
         let loop_kw = self.try_expect_token(Token::Loop)?;
         let label = self.parse_optional_label();
@@ -566,21 +772,62 @@ impl<'src, 'e> Parser<'src, 'e> {
 
     fn try_parse_match_branch(&mut self) -> Option<ast::Branch> {
         let match_kw = self.try_expect_token(Token::Match)?;
+        self.in_scrutinee = true;
         let scrutinee = self.expect_expression();
+        self.in_scrutinee = false;
         let with_kw = self.expect_token(Token::With);
+        let cases = self.parse_match_cases();
+
+        Some(ast::Branch::Match(ast::MatchBranch {
+            match_kw,
+            with_kw,
+            scrutinee: Box::new(scrutinee),
+            cases,
+        }))
+    }
+
+    fn try_parse_try_branch(&mut self) -> Option<ast::Branch> {
+        let try_kw = self.try_expect_token(Token::Try)?;
+        let label = self.parse_optional_label();
+        let body = self.parse_newline_separated_items();
+        let catch_kw = self.expect_token(Token::Catch);
+        let cases = self.parse_match_cases();
+
+        Some(ast::Branch::Try(ast::TryBranch {
+            try_kw,
+            catch_kw,
+            label: Box::new(label),
+            body,
+            cases,
+        }))
+    }
 
+    fn parse_match_cases(&mut self) -> Box<[ast::MatchCase]> {
         let mut cases = Vec::new();
         self.skip_newlines();
-        loop {
-            let Some(pattern) = self.try_parse_primary_expression() else {
-                break;
-            };
+        while let Some(mut pattern) = self.try_parse_primary_expression() {
+            // alternatives: <pat> | <pat> | ...
+            while let Some(op_tok) = self.try_expect_token(Token::BitOr) {
+                let alternative = self.expect_primary_expression();
+                pattern = ast::Expr::Binary(ast::Binary {
+                    op: ast::BinOp::BitOr,
+                    op_tok,
+                    left: Box::new(pattern),
+                    right: Box::new(alternative),
+                });
+            }
+
+            let guard = self
+                .try_expect_token(Token::If)
+                .map(|if_kw| (if_kw, Box::new(self.expect_expression())));
+
             let maps = self.expect_token(Token::Maps);
             let value = self.expect_expression();
 
             cases.push(ast::MatchCase {
                 maps,
                 pattern: Box::new(pattern),
+                guard,
                 value: Box::new(value),
             });
 
@@ -589,12 +836,7 @@ impl<'src, 'e> Parser<'src, 'e> {
             }
         }
 
-        Some(ast::Branch::Match(ast::MatchBranch {
-            match_kw,
-            with_kw,
-            scrutinee: Box::new(scrutinee),
-            cases: cases.into(),
-        }))
+        cases.into()
     }
 
     fn try_parse_break_expression(&mut self) -> Option<ast::Expr> {
@@ -619,13 +861,26 @@ impl<'src, 'e> Parser<'src, 'e> {
         }))
     }
 
+    fn try_parse_raise_expression(&mut self) -> Option<ast::Expr> {
+        let raise_kw = self.try_expect_token(Token::Raise)?;
+        let value = self.expect_expression();
+
+        Some(ast::Expr::Raise(ast::Raise {
+            raise_kw,
+            value: Box::new(value),
+        }))
+    }
+
     fn try_parse_let_expression(&mut self) -> Option<ast::Expr> {
         let let_kw = self.try_expect_token(Token::Let)?;
+        let mut_kw = self.try_expect_token(Token::Mut);
         let pattern = self.expect_primary_expression();
+        let pattern = self.parse_optional_annotation(pattern);
         let (assign, value) = self.parse_optional_symbol_then_expression(Token::Assign);
 
         Some(ast::Expr::Let(ast::Let {
             let_kw,
+            mut_kw,
             assign,
             pattern: Box::new(pattern),
             value: Box::new(value),
@@ -645,6 +900,7 @@ impl<'src, 'e> Parser<'src, 'e> {
     fn try_parse_fun_expression(&mut self) -> Option<ast::Expr> {
         let fun_kw = self.try_expect_token(Token::Fun)?;
         let signature = self.expect_primary_expression();
+        let signature = self.parse_optional_annotation(signature);
         let (maps, value) = self.parse_optional_symbol_then_expression(Token::Maps);
 
         Some(ast::Expr::Fun(ast::Fun {
@@ -655,6 +911,42 @@ impl<'src, 'e> Parser<'src, 'e> {
         }))
     }
 
+    fn try_parse_annotated_expression(&mut self) -> Option<ast::Expr> {
+        let expr = self.try_parse_expression()?;
+        Some(self.parse_optional_annotation(expr))
+    }
+
+    fn parse_optional_annotation(&mut self, expr: ast::Expr) -> ast::Expr {
+        let Some(colon) = self.try_expect_token(Token::Colon) else {
+            return expr;
+        };
+
+        let ty = self.expect_type_expression();
+        ast::Expr::Annotated(ast::Annotated {
+            expr: Box::new(expr),
+            colon,
+            ty: Box::new(ty),
+        })
+    }
+
+    // a type never contains an assignment, so the return type of a function type stops before '='
+    fn expect_type_expression(&mut self) -> ast::Expr {
+        let Some(fun_kw) = self.try_expect_token(Token::Fun) else {
+            return self.expect_primary_expression();
+        };
+
+        let signature = self.expect_primary_expression();
+        let maps = self.expect_token(Token::Maps);
+        let value = self.expect_type_expression();
+
+        ast::Expr::Fun(ast::Fun {
+            fun_kw,
+            maps: Some(maps),
+            signature: Box::new(signature),
+            value: Box::new(value),
+        })
+    }
+
     fn parse_optional_symbol_then_expression(&mut self, token: Token) -> (Option<Span>, ast::Expr) {
         if let Some(e) = self.try_parse_block_expression() {
             (None, e)
@@ -732,6 +1024,9 @@ impl<'src, 'e> Parser<'src, 'e> {
     fn try_parse_record_expression(&mut self) -> Option<ast::Expr> {
         let record_kw = self.try_expect_token(Token::Record)?;
         let signature = self.expect_primary_expression();
+        let deriving = self
+            .try_expect_token(Token::Deriving)
+            .map(|_| self.parse_strictly_comma_separated_items());
 
         let mut fields = Vec::new();
 
@@ -755,6 +1050,7 @@ impl<'src, 'e> Parser<'src, 'e> {
             record_kw,
             end_kw,
             signature: Box::new(signature),
+            deriving,
             fields: fields.into(),
         }))
     }
@@ -762,6 +1058,9 @@ impl<'src, 'e> Parser<'src, 'e> {
     fn try_parse_union_expression(&mut self) -> Option<ast::Expr> {
         let union_kw = self.try_expect_token(Token::Union)?;
         let signature = self.expect_primary_expression();
+        let deriving = self
+            .try_expect_token(Token::Deriving)
+            .map(|_| self.parse_strictly_comma_separated_items());
         let variants = self.parse_newline_separated_items();
         let end_kw = self.expect_token(Token::End);
 
@@ -769,21 +1068,63 @@ impl<'src, 'e> Parser<'src, 'e> {
             union_kw,
             end_kw,
             signature: Box::new(signature),
+            deriving,
             variants,
         }))
     }
 
+    fn try_parse_synonym_expression(&mut self) -> Option<ast::Expr> {
+        let type_kw = self.try_expect_token(Token::Type)?;
+        let signature = self.expect_primary_expression();
+        let assign = self.expect_token(Token::Assign);
+        let ty = self.expect_primary_expression();
+
+        Some(ast::Expr::Synonym(ast::Synonym {
+            type_kw,
+            assign,
+            signature: Box::new(signature),
+            ty: Box::new(ty),
+        }))
+    }
+
+    fn try_parse_newtype_expression(&mut self) -> Option<ast::Expr> {
+        let newtype_kw = self.try_expect_token(Token::Newtype)?;
+        let signature = self.expect_primary_expression();
+        let assign = self.expect_token(Token::Assign);
+        let variant = self.expect_primary_expression();
+
+        Some(ast::Expr::Newtype(ast::Newtype {
+            newtype_kw,
+            assign,
+            signature: Box::new(signature),
+            variant: Box::new(variant),
+        }))
+    }
+
     fn try_parse_class_expression(&mut self) -> Option<ast::Expr> {
         let class_kw = self.try_expect_token(Token::Class)?;
         let signature = self.expect_primary_expression();
         let associated = self
             .try_expect_token(Token::Of)
             .map(|_| self.parse_strictly_comma_separated_items());
+        let superclasses = self
+            .try_expect_token(Token::Colon)
+            .map(|_| self.parse_strictly_comma_separated_items());
 
         let mut items = Vec::new();
+        let mut defaults = Vec::new();
 
         self.skip_newlines();
         while let Some(item) = self.try_parse_expression() {
+            // let-bindings are default implementations of items
+            if let ast::Expr::Let(default) = item {
+                defaults.push(default);
+                if !self.skip_newlines() {
+                    break;
+                }
+                continue;
+            }
+
             let kind = match self.peek() {
                 Token::Colon => {
                     self.consume_token();
@@ -819,19 +1160,32 @@ impl<'src, 'e> Parser<'src, 'e> {
             end_kw,
             signature: Box::new(signature),
             associated,
+            superclasses,
             items: items.into(),
+            defaults: defaults.into(),
         }))
     }
 
     fn try_parse_have_expression(&mut self) -> Option<ast::Expr> {
         let have_kw = self.try_expect_token(Token::Have)?;
-        let class = self.expect_primary_expression();
+        let mut class = self.expect_primary_expression();
+
+        // named instances, as in have sum: Monoid
+        let mut name = None;
+        if let ast::Expr::Var(var) = &class
+            && self.try_expect_token(Token::Colon).is_some()
+        {
+            name = Some(var.span);
+            class = self.expect_primary_expression();
+        }
+
         let items = self.parse_newline_separated_items();
         let end_kw = self.expect_token(Token::End);
 
         Some(ast::Expr::Have(ast::Have {
             have_kw,
             end_kw,
+            name,
             class: Box::new(class),
             items,
         }))
@@ -856,7 +1210,35 @@ impl<'src, 'e> Parser<'src, 'e> {
         let mut items = Vec::new();
 
         self.skip_newlines();
-        while let Some(item) = self.try_parse_expression() {
+        while let Some(item) = self.try_parse_annotated_expression() {
+            items.push(item);
+
+            if self.try_expect_token(Token::Comma).is_some() {
+                self.skip_newlines();
+            } else if !self.skip_newlines() {
+                break;
+            }
+        }
+
+        items.into()
+    }
+
+    // same as comma separated items, but also allows '..' and '..rest'
+    fn parse_array_items(&mut self) -> Box<[ast::Expr]> {
+        let mut items = Vec::new();
+
+        self.skip_newlines();
+        loop {
+            let item = match self.try_expect_token(Token::Spread) {
+                Some(spread) => ast::Expr::Spread(ast::Spread {
+                    spread,
+                    rest: self.try_parse_expression().map(Box::new),
+                }),
+                None => match self.try_parse_annotated_expression() {
+                    Some(item) => item,
+                    None => break,
+                },
+            };
             items.push(item);
 
             if self.try_expect_token(Token::Comma).is_some() {
@@ -32,6 +32,7 @@ pub enum Header {
     InvalidFloat(),
     InvalidExpression(),
     InvalidAccessor(),
+    InvalidAccess(),
     InvalidType(),
     InvalidTypeArg(),
     InvalidPattern(),
@@ -40,7 +41,9 @@ pub enum Header {
     InvalidTypeAnnotation(),
     InvalidLabel(),
     InvalidField(),
+    UnknownField(String, TypeString),
     InvalidClass(),
+    InvalidInstance(),
     InvalidBreak(Option<String>),
     InvalidSkip(Option<String>),
     InvalidBuiltin(String),
@@ -50,8 +53,12 @@ pub enum Header {
     UnknownClassItem(String, String),
     UnknownExport(String, String),
     TypeMismatch(TypeString, TypeString),
+    AnnotationTooGeneral(),
     UnreachableConditionalBranches(usize),
     RefutablePattern(),
+    InconsistentAlternatives(String),
+    NonExhaustiveMatch(),
+    UnreachableMatchCase(),
     UnionNoArgs(String),
     UnionVariantNoArgs(String),
     IncompleteVariant(String),
@@ -60,18 +67,33 @@ pub enum Header {
     RecordNoArgs(String),
     UnionArgMismatch(String),
     RecordArgMismatch(String),
+    SynonymArgMismatch(String),
     NoAdmissibleRecords(),
     AmbiguousRecord(),
     UninitializedFields(String),
     UnmatchedFields(String),
     RequiredFieldValue(),
     ClassNoArgs(String),
+    InvalidSuperclass(String),
+    NotDerivable(String),
     UninstantiatedItems(String),
     UnsatisfiedConstraint(ConstraintString),
     AmbiguousConstraintSolution(ConstraintString),
+    OverlappingInstances(ConstraintString),
+    OrphanInstance(String),
+    UnselectedInstance(String),
     TopLevelConstraint(),
     ExpressionAlias(),
     InvalidIndexing(),
+    InvalidInterpolation(),
+    InvalidExceptionType(TypeString),
+    AmbiguousExceptionType(TypeString),
+    InvalidAssignment(),
+    ImmutableAssignment(String),
+    CapturedAssignment(String),
+    CapturedMutable(String),
+    MutableSignature(),
+    TypedHole(String),
 }
 
 impl Header {
@@ -103,6 +125,7 @@ impl Header {
             H::InvalidFloat(..) => "invalid_float",
             H::InvalidExpression(..) => "invalid_expression",
             H::InvalidAccessor(..) => "invalid_accessor",
+            H::InvalidAccess(..) => "invalid_access",
             H::InvalidType(..) => "invalid_type",
             H::InvalidTypeArg(..) => "invalid_type_arg",
             H::InvalidPattern(..) => "invalid_pattern",
@@ -111,7 +134,9 @@ impl Header {
             H::InvalidTypeAnnotation(..) => "invalid_type_annotation",
             H::InvalidLabel(..) => "invalid_label",
             H::InvalidField(..) => "invalid_field",
+            H::UnknownField(..) => "unknown_field",
             H::InvalidClass(..) => "invalid_class",
+            H::InvalidInstance(..) => "invalid_instance",
             H::InvalidBreak(..) => "invalid_break",
             H::InvalidSkip(..) => "invalid_skip",
             H::InvalidBuiltin(..) => "invalid_builtin",
@@ -121,8 +146,12 @@ impl Header {
             H::UnknownClassItem(..) => "unknown_class_item",
             H::UnknownExport(..) => "unknown_export",
             H::TypeMismatch(..) => "type_mismatch",
+            H::AnnotationTooGeneral(..) => "annotation_too_general",
             H::UnreachableConditionalBranches(..) => "unreachable_conditional_branches",
             H::RefutablePattern(..) => "refutable_pattern",
+            H::InconsistentAlternatives(..) => "inconsistent_alternatives",
+            H::NonExhaustiveMatch(..) => "non_exhaustive_match",
+            H::UnreachableMatchCase(..) => "unreachable_match_case",
             H::UnionNoArgs(..) => "union_no_args",
             H::UnionVariantNoArgs(..) => "union_variant_no_args",
             H::IncompleteVariant(..) => "incomplete_variant",
@@ -131,18 +160,33 @@ impl Header {
             H::RecordNoArgs(..) => "record_no_args",
             H::UnionArgMismatch(..) => "union_arg_mismatch",
             H::RecordArgMismatch(..) => "record_arg_mismatch",
+            H::SynonymArgMismatch(..) => "synonym_arg_mismatch",
             H::NoAdmissibleRecords(..) => "no_admissible_records",
             H::AmbiguousRecord(..) => "ambiguous_record",
             H::UninitializedFields(..) => "uninitialized_fields",
             H::UnmatchedFields(..) => "unmatched_fields",
             H::RequiredFieldValue(..) => "required_fields_value",
             H::ClassNoArgs(..) => "class_no_args",
+            H::InvalidSuperclass(..) => "invalid_superclass",
+            H::NotDerivable(..) => "not_derivable",
             H::UninstantiatedItems(..) => "uninstantiated_items",
             H::UnsatisfiedConstraint(..) => "unsatisfied_constraint",
             H::AmbiguousConstraintSolution(..) => "ambiguous_constraint_solution",
+            H::OverlappingInstances(..) => "overlapping_instances",
+            H::OrphanInstance(..) => "orphan_instance",
+            H::UnselectedInstance(..) => "unselected_instance",
             H::TopLevelConstraint(..) => "top_level_constraints",
             H::ExpressionAlias(..) => "expression_alias",
             H::InvalidIndexing(..) => "invalid_indexing",
+            H::InvalidInterpolation(..) => "invalid_interpolation",
+            H::InvalidExceptionType(..) => "invalid_exception_type",
+            H::AmbiguousExceptionType(..) => "ambiguous_exception_type",
+            H::InvalidAssignment(..) => "invalid_assignment",
+            H::ImmutableAssignment(..) => "immutable_assignment",
+            H::CapturedAssignment(..) => "captured_assignment",
+            H::CapturedMutable(..) => "captured_mutable",
+            H::MutableSignature(..) => "mutable_signature",
+            H::TypedHole(..) => "typed_hole",
         }
     }
 
@@ -201,6 +245,8 @@ impl Header {
                 => "invalid expression syntax".to_string(),
             H::InvalidAccessor()
                 => "invalid accessor expression syntax".to_string(),
+            H::InvalidAccess()
+                => "invalid access".to_string(),
             H::InvalidType()
                 => "invalid type syntax".to_string(),
             H::InvalidTypeArg()
@@ -217,8 +263,12 @@ impl Header {
                 => "invalid label syntax".to_string(),
             H::InvalidField()
                 => "invalid record field syntax".to_string(),
+            H::UnknownField(name, ty)
+                => format!("type {ty} has no field '{name}'"),
             H::InvalidClass()
                 => "invalid class syntax".to_string(),
+            H::InvalidInstance()
+                => "invalid instance selection".to_string(),
             H::InvalidBreak(None)
                 => "invalid break".to_string(),
             H::InvalidBreak(Some(name))
@@ -241,12 +291,20 @@ impl Header {
                 => format!("unknown exported item '{item_name}' in imported module '{name}'"),
             H::TypeMismatch(left, right)
                 => format!("type mismatch between {left} and {right}"),
+            H::AnnotationTooGeneral()
+                => "type annotation is more general than the annotated value".to_string(),
             H::UnreachableConditionalBranches(1)
                 => "unreachable conditional branch".to_string(),
             H::UnreachableConditionalBranches(_)
                 => "unreachable conditional branches".to_string(),
             H::RefutablePattern()
                 => "refutable pattern".to_string(),
+            H::InconsistentAlternatives(name)
+                => format!("variable '{name}' is not bound in every alternative of the pattern"),
+            H::NonExhaustiveMatch()
+                => "non-exhaustive match".to_string(),
+            H::UnreachableMatchCase()
+                => "unreachable match case".to_string(),
             H::UnionNoArgs(name)
                 => format!("non-constant union type '{name}' has no arguments"),
             H::UnionVariantNoArgs(name)
@@ -267,6 +325,8 @@ impl Header {
                 => format!("invalid number of arguments provided into union type '{name}'"),
             H::RecordArgMismatch(name)
                 => format!("invalid number of arguments provided into record type '{name}'"),
+            H::SynonymArgMismatch(name)
+                => format!("invalid number of arguments provided into type synonym '{name}'"),
             H::NoAdmissibleRecords()
                 => "no admissible record type in the current scope".to_string(),
             H::AmbiguousRecord()
@@ -279,18 +339,46 @@ impl Header {
                 => "record field requires a value to be initialized".to_string(),
             H::ClassNoArgs(name)
                 => format!("class '{name}' has no type arguments"),
+            H::InvalidSuperclass(name)
+                => format!("invalid superclass for class '{name}'"),
+            H::NotDerivable(name)
+                => format!("class '{name}' cannot be derived"),
             H::UninstantiatedItems(class_name)
                 => format!("instantiation of class '{class_name}' is incomplete"),
             H::UnsatisfiedConstraint(constraint)
                 => format!("unsatisfied constraint [{constraint}]"),
             H::AmbiguousConstraintSolution(constraint)
                 => format!("ambiguous solution for constraint [{constraint}]"),
+            H::OverlappingInstances(constraint)
+                => format!("overlapping instances for constraint [{constraint}]"),
+            H::OrphanInstance(class_name)
+                => format!("orphan instance of class '{class_name}'"),
+            H::UnselectedInstance(class_name)
+                => format!("selected instance of class '{class_name}' is never used"),
             H::TopLevelConstraint()
                 => "unallowed top-level statement with unsatisfied class constraints".to_string(),
             H::ExpressionAlias()
                 => "disallowed usage of 'alias' for an expression".to_string(),
             H::InvalidIndexing()
                 => "invalid indexing expression".to_string(),
+            H::InvalidInterpolation()
+                => "invalid string interpolation".to_string(),
+            H::InvalidExceptionType(ty)
+                => format!("values of type {ty} cannot be raised as exceptions"),
+            H::AmbiguousExceptionType(ty)
+                => format!("ambiguous exception type {ty}"),
+            H::InvalidAssignment()
+                => "invalid assignment".to_string(),
+            H::ImmutableAssignment(name)
+                => format!("cannot assign to immutable variable '{name}'"),
+            H::CapturedAssignment(name)
+                => format!("cannot assign to captured variable '{name}'"),
+            H::CapturedMutable(name)
+                => format!("cannot capture mutable variable '{name}'"),
+            H::MutableSignature()
+                => "function signatures cannot be mutable".to_string(),
+            H::TypedHole(name)
+                => format!("found hole '{name}'"),
         }
     }
 }
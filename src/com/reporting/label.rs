@@ -25,29 +25,44 @@ pub enum Label {
     ExhaustiveConditionalBranches(usize),
     UnreachableConditionalBranches(usize),
     VariableDefinition(String),
+    MissingAlternativeBinding(String),
+    ExtraArrayRest,
+    MissingPatterns(Box<[String]>),
+    UnreachableMatchCase,
+    FirstArrayRest,
     NamelessSignature,
     FunctionTypeCannotHaveName(String),
     LetBindingPattern,
+    ForLoopPattern,
     FunctionArgPattern,
     WithinUnionDefinition(String),
+    WithinNewtypeDefinition(String),
     UnionTypeArgCount(String, usize),
     UnionTypeNoArgs(String),
     UnionDefinition(String),
+    UnionNoVariants(String),
     VariantArgCount(String, usize),
     VariantDefinition(String),
     NotAnExpression,
     NotAPattern,
     NotAType,
+    NotATypeConstructor(TypeString),
     NotAClass,
+    NotAnInstance,
     WantFunctionType(TypeString),
     WithinRecordDefinition(String),
     RecordTypeArgCount(String, usize),
     RecordTypeNoArgs(String),
     RecordDefinition(String),
+    SynonymTypeArgCount(String, usize),
+    SynonymTypeNoArgs(String),
+    SynonymDefinition(String),
+    RecordUpdateBase(String),
     NoAdmissibleRecord(usize),
     MissingFields(Box<[String]>, String),
     WithinClassDefinition(String),
     ClassDefinition(String),
+    ClassArgCount(String, usize),
     ClassItemDefinition(String),
     IncorrectClassConstantItemSyntax,
     IncorrectClassFunctionItemSyntax,
@@ -57,13 +72,29 @@ pub enum Label {
     MatchingInstances(ConstraintString),
     UnsatisfiedConstraints(usize),
     SuchInstance,
+    OverlappingInstance(ConstraintString),
+    ExistingInstance,
+    OrphanInstance(String),
+    NoConstraintOfClass(String),
     CannotAliasExpression,
     PublicStatement,
     ImportedHere(String),
-    UnsupportedMultidimensionalIndex,
     MissingIndex,
+    UnclosedInterpolationHole,
+    UnmatchedInterpolationBrace,
+    InterpolationHoleExpression,
     IndexedMustBeArray,
     IndexMustBeInteger,
+    RangeMustBeInteger,
+    TypeAnnotation,
+    TypeVariableInstantiated(String, TypeString),
+    TypeVariablesUnified(String, String),
+    ExceptionMustBeUnion,
+    ExceptionTypeMustBeKnown,
+    AssignmentTargetMustBeVariable,
+    CapturedVariable(String),
+    HoleType(TypeString),
+    NoItems(String),
 }
 
 impl Label {
@@ -125,16 +156,32 @@ impl Label {
                 => "these conditional branches are never reached".to_string(),
             L::VariableDefinition(name)
                 => format!("variable '{name}' is defined here"),
+            L::MissingAlternativeBinding(name)
+                => format!("this alternative does not bind '{name}'"),
+            L::MissingPatterns(patterns) if patterns.len() == 1
+                => format!("pattern not covered: '{}'", &patterns[0]),
+            L::MissingPatterns(patterns)
+                => format!("patterns not covered: {}", patterns.iter().map(|s| format!("'{s}'")).collect::<Vec<_>>().join(", ")),
+            L::UnreachableMatchCase
+                => "this case is never reached, previous cases already match".to_string(),
+            L::ExtraArrayRest
+                => "an array pattern can only have one '..'".to_string(),
+            L::FirstArrayRest
+                => "the first '..' is here".to_string(),
             L::NamelessSignature
                 => "function signature has no name".to_string(),
             L::FunctionTypeCannotHaveName(name)
                 => format!("a function type cannot have a name ('{name}')"),
             L::LetBindingPattern
                 => "let-binding patterns must be irrefutable".to_string(),
+            L::ForLoopPattern
+                => "for-loop patterns must be irrefutable".to_string(),
             L::FunctionArgPattern
                 => "function argument patterns must be irrefutable".to_string(),
             L::WithinUnionDefinition(name)
                 => format!("within the definition of union type '{name}'"),
+            L::WithinNewtypeDefinition(name)
+                => format!("within the definition of newtype '{name}'"),
             L::UnionTypeArgCount(name, 1)
                 => format!("union type '{name}' takes in a single argument"),
             L::UnionTypeArgCount(name, count)
@@ -143,6 +190,8 @@ impl Label {
                 => format!("union type '{name}' does not take in any argument"),
             L::UnionDefinition(name)
                 => format!("union type '{name}' is defined here"),
+            L::UnionNoVariants(name)
+                => format!("union type '{name}' has no variants"),
             L::VariantArgCount(name, 1)
                 => format!("variant '{name}' takes in a single argument"),
             L::VariantArgCount(name, count)
@@ -155,8 +204,12 @@ impl Label {
                 => "this is valid syntax but does not represent a pattern".to_string(),
             L::NotAType
                 => "this is valid syntax but does not represent a type".to_string(),
+            L::NotATypeConstructor(ty)
+                => format!("type '{ty}' does not take arguments"),
             L::NotAClass
                 => "this is valid syntax but does not represent a class".to_string(),
+            L::NotAnInstance
+                => "this is valid syntax but does not represent a named instance".to_string(),
             L::WantFunctionType(ty)
                 => format!("this expression is expected to be a function of type {ty}"),
             L::WithinRecordDefinition(name)
@@ -169,6 +222,16 @@ impl Label {
                 => format!("record type '{name}' does not take in any argument"),
             L::RecordDefinition(name)
                 => format!("record type '{name}' is defined here"),
+            L::SynonymTypeArgCount(name, 1)
+                => format!("type synonym '{name}' takes in a single argument"),
+            L::SynonymTypeArgCount(name, count)
+                => format!("type synonym '{name}' takes in {count} arguments"),
+            L::SynonymTypeNoArgs(name)
+                => format!("type synonym '{name}' does not take in any argument"),
+            L::SynonymDefinition(name)
+                => format!("type synonym '{name}' is defined here"),
+            L::RecordUpdateBase(name)
+                => format!("the updated value must be a '{name}' record"),
             L::NoAdmissibleRecord(0)
                 => "no record type in this scope contains zero fields".to_string(),
             L::NoAdmissibleRecord(1)
@@ -181,6 +244,10 @@ impl Label {
                 => format!("record type '{record}' is missing fields: {}", fields.iter().map(|s| format!("'{s}'")).collect::<Vec<_>>().join(", ")),
             L::WithinClassDefinition(name)
                 => format!("within the definition of class '{name}'"),
+            L::ClassArgCount(name, 1)
+                => format!("class '{name}' takes in a single argument"),
+            L::ClassArgCount(name, count)
+                => format!("class '{name}' takes in {count} arguments"),
             L::ClassDefinition(name)
                 => format!("class '{name}' is defined here"),
             L::ClassItemDefinition(name)
@@ -205,20 +272,52 @@ impl Label {
                 => format!("{count} unsatisfied constraints"),
             L::SuchInstance
                 => "this instance matches".to_string(),
+            L::OverlappingInstance(constraint)
+                => format!("this instance of [{constraint}] overlaps with an existing one"),
+            L::ExistingInstance
+                => "the existing instance is defined here".to_string(),
+            L::NoConstraintOfClass(class_name)
+                => format!("the expression has no constraint of class '{class_name}'"),
+            L::OrphanInstance(class_name)
+                => format!("neither class '{class_name}' nor the instantiated types are defined in this file"),
             L::CannotAliasExpression
                 => "aliasing an expression is not allowed".to_string(),
             L::PublicStatement
                 => "this looks like the beginning of a public statement".to_string(),
             L::ImportedHere(name)
                 => format!("module '{name}' is imported here"),
-            L::UnsupportedMultidimensionalIndex
-                => "multidimensional indexing is not supported".to_string(),
             L::MissingIndex
                 => "missing index when attempting to index an array".to_string(),
+            L::UnclosedInterpolationHole
+                => "this hole is never closed, use '{{' to write '{'".to_string(),
+            L::UnmatchedInterpolationBrace
+                => "unmatched closing brace, use '}}' to write '}'".to_string(),
+            L::InterpolationHoleExpression
+                => "holes must contain exactly one expression".to_string(),
             L::IndexedMustBeArray
                 => "indexed expression must be an array".to_string(),
             L::IndexMustBeInteger
                 => "the index value must be an integer".to_string(),
+            L::RangeMustBeInteger
+                => "range bounds must be integers".to_string(),
+            L::TypeAnnotation
+                => "the type of this value is annotated".to_string(),
+            L::TypeVariableInstantiated(name, ty)
+                => format!("type variable '{name}' is required to be {ty}"),
+            L::TypeVariablesUnified(name, other)
+                => format!("type variables '{name}' and '{other}' are required to be the same"),
+            L::ExceptionMustBeUnion
+                => "exceptions must be values of a union type".to_string(),
+            L::ExceptionTypeMustBeKnown
+                => "the type of this exception must be fully known".to_string(),
+            L::AssignmentTargetMustBeVariable
+                => "only variables can be assigned to".to_string(),
+            L::CapturedVariable(name)
+                => format!("variable '{name}' is captured by value from outside of this function"),
+            L::HoleType(ty)
+                => format!("a value of type {ty} is expected here"),
+            L::NoItems(name)
+                => format!("{name} has no items to access"),
         }
     }
 }
@@ -7,7 +7,12 @@ pub enum Note {
     RecordFieldSyntax,
     UnionSyntax,
     UnionVariantSyntax,
+    SynonymSyntax,
+    NewtypeSyntax,
+    NewtypeVariantSyntax,
     ClassSyntax,
+    SuperclassSyntax,
+    DerivableClasses,
     ClassConstantItemSyntax,
     ClassFunctionItemSyntax,
     UseSimpleRecordSyntax(String),
@@ -18,6 +23,9 @@ pub enum Note {
     PubExpression,
     ItemQuerySyntax,
     TopLevelUnknownTypes,
+    MutableBinding(String),
+    HoleLocals(Vec<String>),
+    HoleConstraints(Vec<String>),
 }
 
 impl Note {
@@ -37,8 +45,18 @@ impl Note {
                 => "a union signature must have a name, and may optionally be followed by one or more arguments within parentheses".to_string(),
             N::UnionVariantSyntax
                 => "a union variant must have a name, and may optionally be followed by one or more arguments within parentheses".to_string(),    
+            N::SynonymSyntax
+                => "a type synonym signature must have a name, and may optionally be followed by one or more arguments within parentheses".to_string(),
+            N::NewtypeSyntax
+                => "a newtype signature must have a name, and may optionally be followed by one or more arguments within parentheses".to_string(),
+            N::NewtypeVariantSyntax
+                => "a newtype constructor must have a name, followed by exactly one type within parentheses".to_string(),
             N::ClassSyntax
                 => "a class signature must have a name, and must be followed by one or more arguments within parentheses".to_string(),
+            N::SuperclassSyntax
+                => "a superclass is a class given the arguments of the class being defined, such as 'class Ord(T) : Eq(T)'".to_string(),
+            N::DerivableClasses
+                => "only the 'Display', 'Eq', 'Ord' and 'Default' classes of the standard library can be derived".to_string(),
             N::ClassConstantItemSyntax
                 => format!("a constant item in a class definition must be an identifier, followed by {} and then a type expression", Token::Colon),
             N::ClassFunctionItemSyntax
@@ -59,6 +77,14 @@ impl Note {
                 => "an imported item from an 'import-from' statement must be an identifier, or the '..' syntax to import every item".to_string(),
             N::TopLevelUnknownTypes
                 => "this occurs because the statement produces types that cannot fully be inferred; consider adding annotations?".to_string(),
+            N::MutableBinding(name)
+                => format!("consider declaring it with 'let mut {name}'"),
+            N::HoleLocals(locals) if locals.is_empty()
+                => "no local variables in scope".to_string(),
+            N::HoleLocals(locals)
+                => format!("local variables in scope\n  {}", locals.join("\n  ")),
+            N::HoleConstraints(constraints)
+                => format!("pending constraints\n  {}", constraints.join("\n  ")),
         }
     }
 }
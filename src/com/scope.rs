@@ -46,6 +46,15 @@ where
         self.depth
     }
 
+    // the amount of blocking scopes this scope is nested in, including itself
+    pub fn blocking_depth(&self) -> usize {
+        let parent_depth = match &self.parent {
+            Some(parent) => parent.blocking_depth(),
+            None => 0,
+        };
+        parent_depth + self.blocking as usize
+    }
+
     pub fn open(&mut self, blocking: bool) {
         let parent = mem::replace(self, Self::new(blocking, self.depth + 1));
         self.parent = Some(Box::new(parent))
@@ -90,19 +99,28 @@ where
         &mut self.info
     }
 
+    pub fn iter_deeper_than(&'_ self, depth: usize) -> ScopeIterator<'_, K, I, T> {
+        ScopeIterator {
+            iter: None,
+            next_scope: Some(self),
+            depth,
+        }
+    }
+
     pub fn infos_iter(&'_ self) -> ScopeInfoIterator<'_, K, I, T> {
         ScopeInfoIterator { scope: Some(self) }
     }
 }
 
-#[allow(dead_code)]
+// bindings of the scopes deeper than a given depth, innermost first
 pub struct ScopeIterator<'a, K, I, T>
 where
     K: Eq + Hash,
     I: Default,
 {
-    iter: hash_map::Iter<'a, K, T>,
+    iter: Option<hash_map::Iter<'a, K, T>>,
     next_scope: Option<&'a Scope<K, I, T>>,
+    depth: usize,
 }
 
 impl<'a, K, I, T> Iterator for ScopeIterator<'a, K, I, T>
@@ -113,16 +131,13 @@ where
     type Item = (&'a K, &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(item) = self.iter.next() {
+        if let Some(item) = self.iter.as_mut().and_then(|iter| iter.next()) {
             return Some(item);
         }
 
-        let scope = self.next_scope?;
-        self.iter = scope.bindings.iter();
-        self.next_scope = match &scope.parent {
-            Some(parent) => Some(parent),
-            None => None,
-        };
+        let scope = self.next_scope.filter(|scope| scope.depth > self.depth)?;
+        self.iter = Some(scope.bindings.iter());
+        self.next_scope = scope.parent.as_deref();
 
         self.next()
     }
@@ -1,8 +1,12 @@
 use super::{checks::CheckModuleOptions, deps};
-use crate::com::{
-    ir::{self, Entities},
-    reporting::Report,
-    scope::Scope,
+use crate::{
+    com::{
+        ir::{self, Entities},
+        loc::{Loc, Span},
+        reporting::Report,
+        scope::Scope,
+    },
+    exe::{LayoutID, LayoutTable},
 };
 use std::collections::{HashMap, HashSet};
 
@@ -21,6 +25,22 @@ pub struct ScopeInfo {
     pub instances: Instances,
 }
 
+// a type variable introduced by an annotation, as in let id(x: a): a = x
+pub struct ImplicitTypeVar<'src> {
+    pub name: &'src str,
+    pub id: ir::TypeID,
+    pub span: Span,
+}
+
+// a placeholder expression, as in ?name, reported once its statement is checked
+pub struct Hole {
+    pub name: String,
+    pub ty: ir::TypeID,
+    pub loc: Loc,
+    pub locals: Vec<(String, ir::TypeID)>,
+    pub constraints: Vec<ir::Constraint>,
+}
+
 pub struct NativeTypes {
     pub int: ir::TypeID,
     pub float: ir::TypeID,
@@ -58,6 +78,16 @@ pub struct Checker<'src, 'e> {
     pub native_types: NativeTypes,
     pub publics: HashSet<ir::AnyID>,
     pub current_constraints: Vec<ir::Constraint>,
+    // constraints solved by named instances, until the enclosing statement is solved
+    pub selected_solutions: Vec<ir::Solution>,
+    pub layouts: LayoutTable,
+    pub user_type_layouts: HashMap<(ir::AnyID, Vec<LayoutID>), LayoutID>,
+    pub mutable_variables: HashMap<ir::VariableID, usize>,
+    // unknown type names in annotations introduce these, when allowed
+    pub implicit_type_vars: Option<Vec<ImplicitTypeVar<'src>>>,
+    pub holes: Vec<Hole>,
+    // the classes of structural field access, by field name
+    pub field_classes: HashMap<String, ir::ClassID>,
 
     generic_counter: usize,
 }
@@ -86,6 +116,13 @@ impl<'e> Checker<'_, 'e> {
             native_types: NativeTypes::blank(),
             publics: HashSet::new(),
             current_constraints: Vec::new(),
+            selected_solutions: Vec::new(),
+            layouts: LayoutTable::new(),
+            user_type_layouts: HashMap::new(),
+            mutable_variables: HashMap::new(),
+            implicit_type_vars: None,
+            holes: Vec::new(),
+            field_classes: HashMap::new(),
 
             generic_counter: 0,
         };
@@ -109,6 +146,12 @@ impl<'e> Checker<'_, 'e> {
         self.label_scope.close();
     }
 
+    // the amount of functions the current scope is nested in
+    // variables of enclosing functions are captured by value
+    pub fn function_depth(&self) -> usize {
+        self.label_scope.blocking_depth()
+    }
+
     pub fn set_scope_name(&mut self, name: String) {
         self.scope.infos_mut().name = name;
     }
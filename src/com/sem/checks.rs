@@ -1,6 +1,10 @@
+mod annotations;
 mod constraints;
 mod entities;
+mod exceptions;
+mod fields;
 mod labels;
+mod layouts;
 mod patterns;
 mod signatures;
 mod variables;
@@ -8,34 +12,47 @@ mod variables;
 mod stmt;
 mod stmt_alias;
 mod stmt_class;
+mod stmt_deriving;
 mod stmt_have;
 mod stmt_import;
 mod stmt_let;
+mod stmt_let_group;
+mod stmt_newtype;
 mod stmt_record;
+mod stmt_synonym;
+mod stmt_type_group;
 mod stmt_union;
 
 mod expr;
 mod expr_access;
+mod expr_annotated;
 mod expr_array;
+mod expr_assign;
 mod expr_block;
 mod expr_break;
 mod expr_builtin;
 mod expr_call;
 mod expr_conditional;
 mod expr_fun;
+mod expr_hole;
 mod expr_index;
+mod expr_interpolation;
 mod expr_literal;
 mod expr_ops;
+mod expr_raise;
 mod expr_record;
 mod expr_skip;
 mod expr_tuple;
 mod expr_var;
+mod expr_with;
 
 mod branch;
 mod branch_else;
+mod branch_for;
 mod branch_if;
 mod branch_loop;
 mod branch_match;
+mod branch_try;
 mod branch_while;
 
 mod type_array;
@@ -48,7 +65,9 @@ mod types;
 mod path;
 mod path_access;
 mod path_access_class;
+mod path_access_field;
 mod path_access_import;
+mod path_access_instance;
 mod path_access_record;
 mod path_access_union;
 mod path_call;
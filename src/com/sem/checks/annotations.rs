@@ -0,0 +1,60 @@
+use crate::com::{
+    Checker, ast, ir,
+    reporting::{Header, Label, Report},
+    sem::checker::ImplicitTypeVar,
+};
+use std::collections::HashMap;
+
+impl<'src> Checker<'src, '_> {
+    // unknown type names are type variables until the matching close_type_annotations
+    pub fn open_type_annotations(&mut self) -> Option<Vec<ImplicitTypeVar<'src>>> {
+        self.implicit_type_vars.replace(Vec::new())
+    }
+
+    pub fn close_type_annotations(
+        &mut self,
+        previous: Option<Vec<ImplicitTypeVar<'src>>>,
+    ) -> Vec<ImplicitTypeVar<'src>> {
+        std::mem::replace(&mut self.implicit_type_vars, previous).unwrap_or_default()
+    }
+
+    // the annotated type is located at the annotation, even for named types
+    pub fn check_annotation_type(&mut self, t: &ast::Expr) -> ir::TypeID {
+        let ty = self.check_type(t);
+        let ty = self.clone_type_repr(ty);
+        self.set_type_span(ty, t.span());
+        ty
+    }
+
+    // make type variables visible to the rest of the current scope,
+    // unless their name is already taken, as in let f(a: a) = ...
+    pub fn declare_implicit_type_vars(&mut self, vars: &[ImplicitTypeVar<'src>]) {
+        for var in vars {
+            if self.scope.search(var.name).is_none() {
+                self.create_user_type(var.name, var.id);
+            }
+        }
+    }
+
+    // type variables of an annotation must remain distinct and unknown,
+    // otherwise the annotation is more general than the annotated value
+    pub fn check_implicit_type_vars(&mut self, vars: &[ImplicitTypeVar<'src>]) {
+        let mut seen: HashMap<ir::TypeID, &str> = HashMap::new();
+        for var in vars {
+            let repr = self.get_type_repr(var.id);
+            let label = if !matches!(self.types[repr.0].ty, ir::Type::Var) {
+                Label::TypeVariableInstantiated(var.name.to_string(), self.get_type_string(repr))
+            } else if let Some(other) = seen.get(&repr) {
+                Label::TypeVariablesUnified(other.to_string(), var.name.to_string())
+            } else {
+                seen.insert(repr, var.name);
+                continue;
+            };
+
+            self.reports.push(
+                Report::error(Header::AnnotationTooGeneral())
+                    .with_primary_label(label, var.span.wrap(self.file)),
+            );
+        }
+    }
+}
@@ -2,15 +2,22 @@ use crate::com::{Checker, ast, ir};
 
 impl Checker<'_, '_> {
     // returns (branch_type, is_exhaustive)
-    pub fn check_branch(&mut self, b: &ast::Branch) -> (ir::Branch, ir::TypeID, bool) {
+    // a branch has a fallback when it is followed by other branches
+    pub fn check_branch(
+        &mut self,
+        b: &ast::Branch,
+        has_fallback: bool,
+    ) -> (ir::Branch, ir::TypeID, bool) {
         use ast::Branch as B;
         let span = b.span();
         match b {
             B::If(b) => self.check_if(b, span),
             B::While(b) => self.check_while(b, span),
+            B::For(b) => self.check_for(b, span),
             B::Loop(b) => self.check_loop(b, span),
             B::Else(b) => self.check_else(b, span),
-            B::Match(b) => self.check_match(b, span),
+            B::Match(b) => self.check_match(b, span, has_fallback),
+            B::Try(b) => self.check_try(b, span),
         }
     }
 }
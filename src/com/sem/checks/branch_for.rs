@@ -0,0 +1,195 @@
+use crate::com::{
+    Checker, ast, ir,
+    loc::Span,
+    reporting::{Header, Label, Report},
+    sem::provenance::Provenance,
+};
+
+impl Checker<'_, '_> {
+    pub fn check_for(&mut self, b: &ast::ForBranch, _: Span) -> (ir::Branch, ir::TypeID, bool) {
+        let (iterable, iterable_type) = self.check_expression(&b.iterable);
+
+        // the state of the loop lives in hidden variables
+        // each iteration produces an item, and advances the state
+        let (state, guard, item, advance, item_type) = match &b.range_end {
+            Some((_, end)) => self.check_for_range(b, iterable, iterable_type, end),
+            None if self.is_array_type(iterable_type) => {
+                self.check_for_array(iterable, iterable_type)
+            }
+            None => self.check_for_iter(b, iterable, iterable_type),
+        };
+
+        self.open_scope(false);
+        let label_id = self.check_label_definition(&b.label, true);
+
+        let pattern = self.check_pattern(&b.pattern);
+        if !pattern.is_irrefutable() {
+            self.reports.push(
+                Report::error(Header::RefutablePattern())
+                    .with_primary_label(Label::Empty, pattern.span().wrap(self.file))
+                    .with_secondary_label(
+                        Label::ForLoopPattern,
+                        Span::combine(b.for_kw, b.in_kw).wrap(self.file),
+                    ),
+            );
+        }
+        let (pattern, pattern_type) = self.declare_pattern(&pattern, false);
+        self.unify(pattern_type, item_type, &[]);
+
+        let mut stmts = Vec::with_capacity(b.body.len() + 2);
+        stmts.push(ir::Stmt::Let {
+            lhs: pattern,
+            rhs: item,
+            is_concrete: true,
+            solutions: vec![],
+        });
+        stmts.push(ir::Stmt::Expr {
+            expr: advance,
+            ty: self.create_type(ir::Type::unit(), None),
+        });
+        for item in &b.body {
+            let s = self.check_statement(item);
+            stmts.push(s);
+        }
+
+        self.close_scope();
+
+        let branch = ir::Branch::For {
+            state: state.into(),
+            guard: Box::new(guard),
+            body: stmts.into(),
+            label: label_id,
+        };
+        (branch, self.get_label(label_id).ty, false)
+    }
+
+    // for <pat> in <start>..<end>
+    //     let mut i = start
+    //     let end = end
+    //     while i < end do
+    //         let <pat> = i
+    //         i = i + 1
+    fn check_for_range(
+        &mut self,
+        b: &ast::ForBranch,
+        start: ir::Expr,
+        start_type: ir::TypeID,
+        end: &ast::Expr,
+    ) -> ForLoopParts {
+        let range_span = Span::combine(b.iterable.span(), end.span());
+        let (end, end_type) = self.check_expression(end);
+
+        let provenances = &[Provenance::RangeMustBeInteger(range_span.wrap(self.file))];
+        self.unify(start_type, self.native_types.int, provenances);
+        self.unify(end_type, self.native_types.int, provenances);
+
+        let index_var = self.entities.create_dummy_variable();
+        let end_var = self.entities.create_dummy_variable();
+
+        let index = || Box::new(ir::Expr::Var { id: index_var });
+        (
+            vec![(index_var, start), (end_var, end)],
+            ir::Expr::Lt(index(), Box::new(ir::Expr::Var { id: end_var })),
+            *index(),
+            ir::Expr::Assign {
+                var: index_var,
+                value: Box::new(ir::Expr::Add(index(), Box::new(ir::Expr::Int { val: 1 }))),
+            },
+            self.native_types.int,
+        )
+    }
+
+    // for <pat> in <array>
+    //     let a = array
+    //     let mut i = 0
+    //     while i < length(a) do
+    //         let <pat> = a[i]
+    //         i = i + 1
+    fn check_for_array(&mut self, iterable: ir::Expr, array_type: ir::TypeID) -> ForLoopParts {
+        let item_type = self.create_fresh_type(None);
+        let expected_array_type = self.create_type(ir::Type::Array(item_type), None);
+        self.unify(array_type, expected_array_type, &[]);
+
+        let array_var = self.entities.create_dummy_variable();
+        let index_var = self.entities.create_dummy_variable();
+
+        let array = || Box::new(ir::Expr::Var { id: array_var });
+        let index = || Box::new(ir::Expr::Var { id: index_var });
+        (
+            vec![(array_var, iterable), (index_var, ir::Expr::Int { val: 0 })],
+            ir::Expr::Lt(index(), Box::new(ir::Expr::ArrayLen(array()))),
+            ir::Expr::Index {
+                indexed: array(),
+                index: index(),
+            },
+            ir::Expr::Assign {
+                var: index_var,
+                value: Box::new(ir::Expr::Add(index(), Box::new(ir::Expr::Int { val: 1 }))),
+            },
+            item_type,
+        )
+    }
+
+    // for <pat> in <iterable>
+    //     let mut c = iterable
+    //     while not Iter.done(c) do
+    //         let <pat> = Iter.head(c)
+    //         c = Iter.tail(c)
+    fn check_for_iter(
+        &mut self,
+        b: &ast::ForBranch,
+        iterable: ir::Expr,
+        iterable_type: ir::TypeID,
+    ) -> ForLoopParts {
+        let Some(prelude_file) = self.deps.info.prelude_file else {
+            panic!("cannot iterate over non-array values without std")
+        };
+
+        let ops_exports = self.get_marin_std_ops_exports(prelude_file);
+        let Some(ir::AnyID::Class(iter_class_id)) = ops_exports.get("Iter").copied() else {
+            panic!("couldn't find ops.Iter class for for-loops")
+        };
+
+        let span = b.iterable.span();
+        let item_type = self.create_fresh_type(None);
+        let ret_types = [self.native_types.bool, item_type, iterable_type];
+
+        let mut iter_items = self.check_class_items_into_exprs(iter_class_id, &[0, 1, 2], span);
+        for ((_, iter_item_ty), ret_type) in iter_items.iter().zip(ret_types) {
+            let expected_ty = self.create_type(
+                ir::Type::Lambda(Box::new([iterable_type]), ret_type),
+                Some(span),
+            );
+            self.unify(*iter_item_ty, expected_ty, &[]);
+        }
+
+        let (tail, _) = iter_items.pop().unwrap();
+        let (head, _) = iter_items.pop().unwrap();
+        let (done, _) = iter_items.pop().unwrap();
+
+        let iterable_var = self.entities.create_dummy_variable();
+        let call = |f: ir::Expr| ir::Expr::Call {
+            callee: Box::new(f),
+            args: Box::new([ir::Expr::Var { id: iterable_var }]),
+        };
+        (
+            vec![(iterable_var, iterable)],
+            ir::Expr::BitNeg(Box::new(call(done))),
+            call(head),
+            ir::Expr::Assign {
+                var: iterable_var,
+                value: Box::new(call(tail)),
+            },
+            item_type,
+        )
+    }
+}
+
+// (state, guard, item, advance, item_type)
+type ForLoopParts = (
+    Vec<(ir::VariableID, ir::Expr)>,
+    ir::Expr,
+    ir::Expr,
+    ir::Expr,
+    ir::TypeID,
+);
@@ -2,21 +2,57 @@ use crate::com::{
     Checker, ast,
     ir::{self, Constructor},
     loc::Span,
+    reporting::{Header, Label, Report},
+    sem::provenance::Provenance,
 };
+use std::collections::{HashMap, HashSet};
 
 impl Checker<'_, '_> {
     pub fn check_match(
         &mut self,
         b: &ast::MatchBranch,
         span: Span,
+        has_fallback: bool,
     ) -> (ir::Branch, ir::TypeID, bool) {
         let result_type = self.create_fresh_type(Some(span));
 
         let (scrut, scrut_type) = self.check_expression(&b.scrutinee);
         let scrut_var = self.entities.create_dummy_variable();
 
-        let mut cases = Vec::new();
-        for case in &b.cases {
+        let (decision, missing_patterns) =
+            self.check_match_cases(&b.cases, scrut_var, scrut_type, result_type);
+
+        // unmatched values fall through to the next branch, if there is one
+        let is_exhaustive = missing_patterns.is_empty();
+        if !is_exhaustive && !has_fallback {
+            self.reports.push(
+                Report::warning(Header::NonExhaustiveMatch()).with_primary_label(
+                    Label::MissingPatterns(missing_patterns.into()),
+                    Span::combine(b.match_kw, b.with_kw).wrap(self.file),
+                ),
+            );
+        }
+
+        (
+            ir::Branch::Match {
+                scrutinee_var: scrut_var,
+                scrutinee: Box::new(scrut),
+                decision: Box::new(decision),
+            },
+            result_type,
+            is_exhaustive,
+        )
+    }
+
+    pub fn check_match_cases(
+        &mut self,
+        cases: &[ast::MatchCase],
+        scrut_var: ir::VariableID,
+        scrut_type: ir::TypeID,
+        result_type: ir::TypeID,
+    ) -> (ir::Decision, Vec<String>) {
+        let mut problem = Vec::new();
+        for (i, case) in cases.iter().enumerate() {
             let pattern = self.check_pattern(&case.pattern);
 
             self.open_scope(false);
@@ -24,34 +60,62 @@ impl Checker<'_, '_> {
             let (pattern, pattern_type) = self.declare_pattern(&pattern, false);
             self.unify(scrut_type, pattern_type, &[]);
 
+            let guard = case.guard.as_ref().map(|(_, guard)| {
+                let (guard_expr, guard_type) = self.check_expression(guard);
+                let provenances = &[Provenance::ConditionalBoolType(
+                    guard.span().wrap(self.file),
+                )];
+                self.unify(guard_type, self.native_types.bool, provenances);
+                guard_expr
+            });
+
             let (val, val_type) = self.check_expression(&case.value);
             self.unify(val_type, result_type, &[]);
 
             self.close_scope();
 
             let lhs = vec![MatchTest(scrut_var, pattern)];
-            let rhs = MatchRhs(val, vec![]);
-            cases.push((lhs, rhs));
+            let rhs = MatchRhs(i, val, guard, vec![]);
+            problem.push((lhs, rhs));
+        }
+
+        let mut reached = HashSet::new();
+        let (decision, counterexamples) = self.build_decision_tree(problem, &mut reached);
+
+        for (i, case) in cases.iter().enumerate() {
+            if !reached.contains(&i) {
+                self.reports.push(
+                    Report::warning(Header::UnreachableMatchCase()).with_primary_label(
+                        Label::UnreachableMatchCase,
+                        case.span().wrap(self.file),
+                    ),
+                );
+            }
         }
 
-        let (decision, is_exhaustive) = self.build_decision_tree(cases);
+        let mut missing_patterns = Vec::new();
+        for counterexample in &counterexamples {
+            let pattern = self.display_counterexample(counterexample, scrut_var);
+            if !missing_patterns.contains(&pattern) {
+                missing_patterns.push(pattern);
+            }
+        }
 
-        (
-            ir::Branch::Match {
-                scrutinee_var: scrut_var,
-                scrutinee: Box::new(scrut),
-                decision: Box::new(decision),
-            },
-            result_type,
-            is_exhaustive,
-        )
+        (decision, missing_patterns)
     }
 
     // algorithm based on: https://julesjacobs.com/notes/patternmatching/patternmatching.pdf
     // and with help from this implementation: https://gitlab.com/yorickpeterse/pattern-matching-in-rust/-/tree/main/jacobs2021
-    fn build_decision_tree(&mut self, mut cases: MatchProblem) -> (ir::Decision, bool) {
+    // along with the tree, returns the values that no case matches, if any
+    fn build_decision_tree(
+        &mut self,
+        cases: MatchProblem,
+        reached: &mut HashSet<usize>,
+    ) -> MatchTree {
+        let mut cases = Self::expand_or_patterns(cases);
+
         // find tests against irrefutable patterns and move them as variable let bindings
-        for (lhs, MatchRhs(_, stmts)) in &mut cases {
+        for (lhs, MatchRhs(_, _, _, stmts)) in &mut cases {
             let (irrefutables, refutables) = std::mem::take(lhs)
                 .into_iter()
                 .partition(|MatchTest(_, pat)| pat.is_exhaustive());
@@ -72,19 +136,42 @@ impl Checker<'_, '_> {
         }
 
         // no cases means the pattern matching is non-exhaustive
+        // and any value reaching this point is a counterexample
         let Some((first_lhs, _)) = cases.first() else {
-            return (ir::Decision::Failure, false);
+            return (ir::Decision::Failure, vec![Counterexample::new()]);
         };
 
         // this case has no test, it always passes at this point
         if first_lhs.is_empty() {
-            let MatchRhs(expr, stmts) = cases.swap_remove(0).1;
+            let MatchRhs(index, expr, guard, stmts) = cases.remove(0).1;
+            reached.insert(index);
+
+            let success = ir::Decision::Success {
+                stmts: stmts.clone(),
+                result: Box::new(expr),
+            };
+
+            let Some(guard) = guard else {
+                return (success, vec![]);
+            };
+
+            // unless it is guarded, in which case the next cases are tried when the guard fails
+            // the guard sees the bindings of the case, in its own block
+            let mut guard_stmts = stmts;
+            guard_stmts.push(ir::Stmt::Expr {
+                expr: guard,
+                ty: self.native_types.bool,
+            });
+            let (failure, counterexamples) = self.build_decision_tree(cases, reached);
             return (
-                ir::Decision::Success {
-                    stmts,
-                    result: Box::new(expr),
+                ir::Decision::Guard {
+                    guard: Box::new(ir::Expr::BlockUnlabelled {
+                        stmts: guard_stmts.into(),
+                    }),
+                    success: Box::new(success),
+                    failure: Box::new(failure),
                 },
-                true,
+                counterexamples,
             );
         }
 
@@ -92,6 +179,10 @@ impl Checker<'_, '_> {
         let &MatchTest(checked_variable, ref checked_pattern) =
             Self::select_match_test(first_lhs, &cases);
 
+        if let ir::Pattern::Array(..) = checked_pattern {
+            return self.build_array_decision_tree(checked_variable, cases, reached);
+        }
+
         let constructor = checked_pattern.constructor();
         let variations = self.get_all_variations(&constructor);
 
@@ -111,19 +202,25 @@ impl Checker<'_, '_> {
                     current_subproblem = failure;
                     all_variants_found &= ok;
 
-                    let variant_decision = self.build_decision_tree(success);
+                    let variant_decision = self.build_decision_tree(success, reached);
                     decisions.push((pat, variant_decision));
                 }
 
                 // if a variation is missing, ensure that it is handled in the failure subproblem
-                let (mut final_decision, mut is_exhaustive) = if all_variants_found {
-                    (ir::Decision::Failure, true)
+                // its counterexamples are already found in the subproblem of the missing variation
+                let mut final_decision = if all_variants_found {
+                    ir::Decision::Failure
                 } else {
-                    self.build_decision_tree(current_subproblem)
+                    self.build_decision_tree(current_subproblem, reached).0
                 };
 
-                for (pat, (decision, decision_exhaustive)) in decisions {
-                    is_exhaustive &= decision_exhaustive;
+                let mut counterexamples = Vec::new();
+                for (pat, (decision, variant_counterexamples)) in decisions {
+                    for mut counterexample in variant_counterexamples {
+                        counterexample.insert(checked_variable, pat.clone());
+                        counterexamples.push(counterexample);
+                    }
+
                     final_decision = ir::Decision::Test {
                         tested_var: checked_variable,
                         pattern: Box::new(pat),
@@ -132,7 +229,7 @@ impl Checker<'_, '_> {
                     };
                 }
 
-                (final_decision, is_exhaustive)
+                (final_decision, counterexamples)
             }
             _ => {
                 // in this case, there are an infinite number of cases for the current constructor
@@ -141,8 +238,15 @@ impl Checker<'_, '_> {
                 let (pat, _, success, failure) =
                     self.create_subproblem(checked_variable, constructor, cases);
 
-                let (success_decision, success_exhaustive) = self.build_decision_tree(success);
-                let (failure_decision, failure_exhaustive) = self.build_decision_tree(failure);
+                let (success_decision, success_counterexamples) =
+                    self.build_decision_tree(success, reached);
+                let (failure_decision, mut counterexamples) =
+                    self.build_decision_tree(failure, reached);
+
+                for mut counterexample in success_counterexamples {
+                    counterexample.insert(checked_variable, pat.clone());
+                    counterexamples.push(counterexample);
+                }
 
                 (
                     ir::Decision::Test {
@@ -151,12 +255,207 @@ impl Checker<'_, '_> {
                         success: Box::new(success_decision),
                         failure: Box::new(failure_decision),
                     },
-                    success_exhaustive && failure_exhaustive,
+                    counterexamples,
                 )
             }
         }
     }
 
+    // arrays have no constructors, instead their length is tested
+    // each length below the longest fixed-length pattern is tested separately
+    // and all remaining lengths can only be matched by patterns with a rest
+    fn build_array_decision_tree(
+        &mut self,
+        x: ir::VariableID,
+        cases: MatchProblem,
+        reached: &mut HashSet<usize>,
+    ) -> MatchTree {
+        let mut open_len = 0;
+        for (lhs, _) in &cases {
+            for MatchTest(y, pat) in lhs {
+                if let (true, ir::Pattern::Array(prefix, rest, suffix)) = (*y == x, pat) {
+                    let fixed_len = prefix.len() + suffix.len();
+                    open_len = open_len.max(match rest {
+                        Some(_) => fixed_len,
+                        None => fixed_len + 1,
+                    });
+                }
+            }
+        }
+
+        let (mut decision, mut counterexamples) = self.build_array_length_decision(
+            x,
+            ArrayLength::AtLeast(open_len),
+            cases.clone(),
+            reached,
+        );
+
+        for len in (0..open_len).rev() {
+            let (success, success_counterexamples) = self.build_array_length_decision(
+                x,
+                ArrayLength::Exact(len),
+                cases.clone(),
+                reached,
+            );
+            counterexamples.extend(success_counterexamples);
+
+            let array_len = ir::Expr::ArrayLen(Box::new(ir::Expr::Var { id: x }));
+            let len = ir::Expr::Int { val: len as i64 };
+            decision = ir::Decision::Guard {
+                guard: Box::new(ir::Expr::Eq(Box::new(array_len), Box::new(len))),
+                success: Box::new(success),
+                failure: Box::new(decision),
+            };
+        }
+
+        (decision, counterexamples)
+    }
+
+    // the subproblem of the cases which match arrays of the given length
+    // elements of the array are bound to variables only when they are tested
+    fn build_array_length_decision(
+        &mut self,
+        x: ir::VariableID,
+        length: ArrayLength,
+        cases: MatchProblem,
+        reached: &mut HashSet<usize>,
+    ) -> MatchTree {
+        let mut elements = Vec::new();
+        let mut subproblem = Vec::new();
+        for (lhs, rhs) in cases {
+            let (x_tests, mut new_tests): (Vec<_>, Vec<_>) =
+                lhs.into_iter().partition(|MatchTest(y, _)| *y == x);
+
+            let Some(MatchTest(_, x_pattern)) = x_tests.into_iter().next() else {
+                subproblem.push((new_tests, rhs));
+                continue;
+            };
+
+            // anything else than an array pattern is a type error
+            let ir::Pattern::Array(prefix, rest, suffix) = x_pattern else {
+                continue;
+            };
+
+            let fixed_len = prefix.len() + suffix.len();
+            let matches = match (length, &rest) {
+                (ArrayLength::Exact(len), None) => fixed_len == len,
+                (ArrayLength::Exact(len), Some(_)) => fixed_len <= len,
+                (ArrayLength::AtLeast(_), None) => false,
+                (ArrayLength::AtLeast(_), Some(_)) => true,
+            };
+            if !matches {
+                continue;
+            }
+
+            let (prefix_len, suffix_len) = (prefix.len(), suffix.len());
+            for (i, item) in prefix.into_vec().into_iter().enumerate() {
+                let element = length.element(ArrayElement::FromStart(i));
+                let var = self.get_array_element_variable(&mut elements, element);
+                new_tests.push(MatchTest(var, item));
+            }
+            for (i, item) in suffix.into_vec().into_iter().rev().enumerate() {
+                let element = length.element(ArrayElement::FromEnd(i));
+                let var = self.get_array_element_variable(&mut elements, element);
+                new_tests.push(MatchTest(var, item));
+            }
+            if let Some(rest) = rest
+                && !matches!(*rest, ir::Pattern::Discard)
+            {
+                let element = ArrayElement::Slice(prefix_len, suffix_len);
+                let var = self.get_array_element_variable(&mut elements, element);
+                new_tests.push(MatchTest(var, *rest));
+            }
+
+            subproblem.push((new_tests, rhs));
+        }
+
+        let (next, mut counterexamples) = self.build_decision_tree(subproblem, reached);
+        let shape = length.counterexample_shape(&elements);
+        for counterexample in &mut counterexamples {
+            counterexample.insert(x, shape.clone());
+        }
+
+        if elements.is_empty() {
+            return (next, counterexamples);
+        }
+
+        let array = || Box::new(ir::Expr::Var { id: x });
+        let int = |val: usize| Box::new(ir::Expr::Int { val: val as i64 });
+        let from_end =
+            |i: usize| Box::new(ir::Expr::Sub(Box::new(ir::Expr::ArrayLen(array())), int(i)));
+
+        let stmts = elements
+            .into_iter()
+            .map(|(element, var)| ir::Stmt::Let {
+                lhs: ir::Pattern::Binding(var),
+                rhs: match element {
+                    ArrayElement::FromStart(i) => ir::Expr::Index {
+                        indexed: array(),
+                        index: int(i),
+                    },
+                    ArrayElement::FromEnd(i) => ir::Expr::Index {
+                        indexed: array(),
+                        index: from_end(i + 1),
+                    },
+                    ArrayElement::Slice(start, end) => {
+                        ir::Expr::ArraySlice(array(), int(start), from_end(end))
+                    }
+                },
+                is_concrete: true,
+                solutions: vec![],
+            })
+            .collect();
+
+        (
+            ir::Decision::Bind {
+                stmts,
+                next: Box::new(next),
+            },
+            counterexamples,
+        )
+    }
+
+    fn get_array_element_variable(
+        &mut self,
+        elements: &mut Vec<(ArrayElement, ir::VariableID)>,
+        element: ArrayElement,
+    ) -> ir::VariableID {
+        if let Some(&(_, var)) = elements.iter().find(|(e, _)| *e == element) {
+            return var;
+        }
+        let var = self.entities.create_dummy_variable();
+        elements.push((element, var));
+        var
+    }
+
+    // a case testing against an or-pattern becomes one case per alternative
+    fn expand_or_patterns(cases: MatchProblem) -> MatchProblem {
+        let mut expanded = Vec::with_capacity(cases.len());
+        for (lhs, rhs) in cases {
+            let or_test = lhs
+                .iter()
+                .position(|MatchTest(_, pat)| matches!(pat, ir::Pattern::Or(_)));
+            let Some(i) = or_test else {
+                expanded.push((lhs, rhs));
+                continue;
+            };
+
+            let MatchTest(x, ir::Pattern::Or(alternatives)) = &lhs[i] else {
+                unreachable!()
+            };
+            let alternative_cases = alternatives
+                .iter()
+                .map(|alt| {
+                    let mut alt_lhs = lhs.clone();
+                    alt_lhs[i] = MatchTest(*x, alt.clone());
+                    (alt_lhs, rhs.clone())
+                })
+                .collect();
+            expanded.extend(Self::expand_or_patterns(alternative_cases));
+        }
+        expanded
+    }
+
     // None means an infinite number of variations for a given constructor
     // for example: the set of string patterns is infinite
     // but for a bool(_) constructor, the two cases are bool(true) and bool(false)
@@ -280,6 +579,63 @@ impl Checker<'_, '_> {
         (arg_patterns, arg_ids)
     }
 
+    fn display_counterexample(&self, counterexample: &Counterexample, x: ir::VariableID) -> String {
+        match counterexample.get(&x) {
+            Some(pat) => self.display_counterexample_pattern(counterexample, pat),
+            None => "_".to_string(),
+        }
+    }
+
+    fn display_counterexample_pattern(
+        &self,
+        counterexample: &Counterexample,
+        pat: &ir::Pattern,
+    ) -> String {
+        use ir::Pattern as P;
+        let show = |item: &ir::Pattern| self.display_counterexample_pattern(counterexample, item);
+        let list = |items: &[ir::Pattern]| items.iter().map(show).collect::<Vec<_>>().join(", ");
+
+        match pat {
+            P::Missing | P::Discard => "_".to_string(),
+            P::Binding(id) => self.display_counterexample(counterexample, *id),
+            P::Int(i) => i.to_string(),
+            P::Float(f) => f.to_string(),
+            P::String(s) => format!("{s:?}"),
+            P::Bool(b) => b.to_string(),
+            P::Tuple(items) => format!("({})", list(items)),
+            P::Variant(id, tag, args) => {
+                let (info, variant) = self.entities.get_union_variant_info(*id, *tag);
+                match args {
+                    Some(args) => format!("{}.{}({})", info.name, variant.name, list(args)),
+                    None => format!("{}.{}", info.name, variant.name),
+                }
+            }
+            P::Record(id, fields) => {
+                let info = self.entities.get_record_info(*id);
+                let fields = info
+                    .fields
+                    .iter()
+                    .zip(fields)
+                    .map(|(field, pat)| format!("{} = {}", field.name, show(pat)))
+                    .collect::<Vec<_>>();
+                format!("{{ {} }}", fields.join(", "))
+            }
+            P::Or(alternatives) => alternatives
+                .iter()
+                .map(show)
+                .collect::<Vec<_>>()
+                .join(" | "),
+            P::Array(prefix, rest, suffix) => {
+                let mut items = prefix.iter().map(show).collect::<Vec<_>>();
+                if rest.is_some() {
+                    items.push("..".to_string());
+                }
+                items.extend(suffix.iter().map(show));
+                format!("[{}]", items.join(", "))
+            }
+        }
+    }
+
     // select a test clause within a case
     fn select_match_test<'a>(
         test: &'a [MatchTest],
@@ -291,8 +647,89 @@ impl Checker<'_, '_> {
 
 type MatchProblem = Vec<(Vec<MatchTest>, MatchRhs)>;
 
+type MatchTree = (ir::Decision, Vec<Counterexample>);
+
+// the patterns of the tested variables that no case matches
+// variables which are not constrained can have any value
+type Counterexample = HashMap<ir::VariableID, ir::Pattern>;
+
+// (case index, result, guard, bindings)
 #[derive(Debug, Clone)]
-struct MatchRhs(ir::Expr, Vec<ir::Stmt>);
+struct MatchRhs(usize, ir::Expr, Option<ir::Expr>, Vec<ir::Stmt>);
 
 #[derive(Debug, Clone)]
 struct MatchTest(ir::VariableID, ir::Pattern);
+
+#[derive(Debug, Clone, Copy)]
+enum ArrayLength {
+    Exact(usize),
+    // any length from the given one, which are not tested separately
+    AtLeast(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArrayElement {
+    FromStart(usize),
+    FromEnd(usize),
+    // (number of items skipped at the start, at the end)
+    Slice(usize, usize),
+}
+
+impl ArrayLength {
+    // when the length is known, elements are always indexed from the start
+    fn element(self, element: ArrayElement) -> ArrayElement {
+        match (self, element) {
+            (Self::Exact(len), ArrayElement::FromEnd(i)) => ArrayElement::FromStart(len - 1 - i),
+            _ => element,
+        }
+    }
+
+    // an array pattern of this length, made of the element variables
+    fn counterexample_shape(self, elements: &[(ArrayElement, ir::VariableID)]) -> ir::Pattern {
+        let find = |element| {
+            elements
+                .iter()
+                .find(|(e, _)| *e == element)
+                .map(|&(_, var)| ir::Pattern::Binding(var))
+                .unwrap_or(ir::Pattern::Discard)
+        };
+
+        match self {
+            Self::Exact(len) => {
+                let items = (0..len).map(|i| find(ArrayElement::FromStart(i)));
+                ir::Pattern::Array(items.collect(), None, Box::new([]))
+            }
+            Self::AtLeast(len) => {
+                let from_start = |e: &&(ArrayElement, _)| match e.0 {
+                    ArrayElement::FromStart(i) => Some(i + 1),
+                    _ => None,
+                };
+                let from_end = |e: &&(ArrayElement, _)| match e.0 {
+                    ArrayElement::FromEnd(i) => Some(i + 1),
+                    _ => None,
+                };
+                let suffix_len = elements
+                    .iter()
+                    .filter_map(|e| from_end(&e))
+                    .max()
+                    .unwrap_or(0);
+                let prefix_len = elements
+                    .iter()
+                    .filter_map(|e| from_start(&e))
+                    .max()
+                    .unwrap_or(0)
+                    .max(len.saturating_sub(suffix_len));
+
+                let prefix = (0..prefix_len).map(|i| find(ArrayElement::FromStart(i)));
+                let suffix = (0..suffix_len)
+                    .rev()
+                    .map(|i| find(ArrayElement::FromEnd(i)));
+                ir::Pattern::Array(
+                    prefix.collect(),
+                    Some(Box::new(ir::Pattern::Discard)),
+                    suffix.collect(),
+                )
+            }
+        }
+    }
+}
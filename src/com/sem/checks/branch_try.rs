@@ -0,0 +1,31 @@
+use crate::com::{Checker, ast, ir, loc::Span};
+
+impl Checker<'_, '_> {
+    pub fn check_try(&mut self, b: &ast::TryBranch, span: Span) -> (ir::Branch, ir::TypeID, bool) {
+        let result_type = self.create_fresh_type(Some(span));
+
+        let (stmts, label_id, body_type) = self.check_expression_block(&b.label, &b.body, span);
+        self.unify(body_type, result_type, &[]);
+
+        let exception_type = self.create_fresh_type(Some(b.catch_kw));
+        let exception = self.create_exception(exception_type, b.catch_kw);
+        let caught_var = self.entities.create_dummy_variable();
+
+        // cases which do not match the caught exception simply raise it again
+        // so the whole branch is always exhaustive
+        let (decision, _) =
+            self.check_match_cases(&b.cases, caught_var, exception_type, result_type);
+
+        (
+            ir::Branch::Try {
+                body: stmts,
+                label: label_id,
+                exception,
+                caught_var,
+                decision: Box::new(decision),
+            },
+            result_type,
+            true,
+        )
+    }
+}
@@ -16,12 +16,28 @@ impl Checker<'_, '_> {
         self.current_constraints = constraints;
     }
 
+    // a let only solves the constraints of its own value, the ones pending on
+    // the enclosing expression (such as a loop condition) are left to its statement
+    pub fn check_in_constraint_scope<T>(&mut self, check: impl FnOnce(&mut Self) -> T) -> T {
+        let outer_constraints = self.take_constraint_context();
+        let outer_solutions = std::mem::take(&mut self.selected_solutions);
+        let result = check(self);
+        let constraints = self.take_constraint_context();
+        self.restore_constraint_context(outer_constraints);
+        self.current_constraints.extend(constraints);
+        let solutions = std::mem::replace(&mut self.selected_solutions, outer_solutions);
+        self.selected_solutions.extend(solutions);
+        result
+    }
+
     pub fn solve_constraints(&mut self) -> (Vec<ir::Solution>, Vec<ir::Constraint>) {
         let mut current_constraints = self.take_constraint_context();
         let mut irrelevant = Vec::new();
-        let mut solutions = Vec::new();
+        let mut solutions = std::mem::take(&mut self.selected_solutions);
 
         loop {
+            self.merge_field_constraints(&current_constraints);
+
             let mut partial = Vec::new();
             let mut concrete = Vec::new();
 
@@ -63,19 +79,38 @@ impl Checker<'_, '_> {
             // so we need to decide whether to continue the loop or not
             for mut constraint in concrete {
                 let trace = std::mem::take(&mut constraint.constraint_trace);
+
+                // field constraints are solved by the record type itself
+                if let Some(field) = self.entities.get_class_info(constraint.id).field.clone() {
+                    if let Some(offset) = self.check_field_constraint(&constraint, &field) {
+                        solutions.push(ir::Solution {
+                            trace,
+                            dictionary: ir::Dictionary::FieldOffset(offset),
+                            additional_constraint_id: self.get_generic_unique_id(),
+                        });
+                    }
+                    continue;
+                }
+
                 if let Some((instance_id, mut additional, additional_constraint_id)) =
                     self.check_constraint(constraint)
                 {
                     solutions.push(ir::Solution {
                         trace,
-                        instance_id,
+                        dictionary: ir::Dictionary::Instance(instance_id),
                         additional_constraint_id,
                     });
+                    solutions
+                        .extend(self.solve_self_constraints(instance_id, additional_constraint_id));
                     current_constraints.append(&mut additional);
                 }
             }
         }
 
+        if !self.holes.is_empty() {
+            self.attach_hole_constraints(&current_constraints);
+        }
+
         self.restore_constraint_context(irrelevant);
         (solutions, current_constraints)
     }
@@ -150,7 +185,73 @@ impl Checker<'_, '_> {
         ))
     }
 
-    fn get_known_instances(&self) -> Vec<(ir::InstanceID, ir::InstanceInfo)> {
+    // an instance solves the constraints its items have on itself,
+    // along with those it solved when it was checked
+    pub fn solve_self_constraints(
+        &self,
+        instance_id: ir::InstanceID,
+        additional_constraint_id: usize,
+    ) -> Vec<ir::Solution> {
+        let info = self.entities.get_instance_info(instance_id);
+        let self_solutions = info.scheme.self_constraints.iter().map(|constraint| {
+            let mut trace = constraint.constraint_trace.clone();
+            trace.constraint_ids.push(additional_constraint_id);
+            ir::Solution {
+                trace,
+                dictionary: ir::Dictionary::Instance(instance_id),
+                additional_constraint_id,
+            }
+        });
+        let solved = info.solutions.iter().map(|solution| {
+            let mut solution = solution.clone();
+            solution.trace.constraint_ids.push(additional_constraint_id);
+            solution
+        });
+        self_solutions.chain(solved).collect()
+    }
+
+    pub fn is_same_constraint(&self, left: &ir::Constraint, right: &ir::Constraint) -> bool {
+        left.id == right.id
+            && left
+                .class_args
+                .iter()
+                .zip(&right.class_args)
+                .all(|(left, right)| self.is_same_type(*left, *right))
+    }
+
+    // a constraint is entailed by another one if its class is a (possibly indirect) superclass
+    // of the other one's, given the same arguments
+    pub fn is_entailed_constraint(
+        &self,
+        wanted: &ir::Constraint,
+        given: &[ir::Constraint],
+    ) -> bool {
+        given
+            .iter()
+            .any(|given| self.is_entailed_by_superclasses(wanted, given.id, &given.class_args))
+    }
+
+    fn is_entailed_by_superclasses(
+        &self,
+        wanted: &ir::Constraint,
+        id: ir::ClassID,
+        args: &[ir::TypeID],
+    ) -> bool {
+        let info = self.entities.get_class_info(id);
+        info.superclasses.iter().any(|superclass| {
+            let superclass_args = superclass.args.iter().map(|i| args[*i]).collect::<Vec<_>>();
+
+            let is_same = superclass.id == wanted.id
+                && superclass_args
+                    .iter()
+                    .zip(&wanted.class_args)
+                    .all(|(left, right)| self.is_same_type(*left, *right));
+
+            is_same || self.is_entailed_by_superclasses(wanted, superclass.id, &superclass_args)
+        })
+    }
+
+    pub fn get_known_instances(&self) -> Vec<(ir::InstanceID, ir::InstanceInfo)> {
         let mut in_scope = self
             .scope
             .infos_iter()
@@ -163,6 +264,11 @@ impl Checker<'_, '_> {
     }
 
     fn is_concrete_constraint(&mut self, constraint: &ir::Constraint) -> bool {
+        // field constraints only need to know which record type they are on
+        if self.entities.get_class_info(constraint.id).field.is_some() {
+            return self.is_known_type_constructor(constraint.class_args[0]);
+        }
+
         constraint
             .class_args
             .iter()
@@ -32,6 +32,10 @@ impl Checker<'_, '_> {
                 let info = self.entities.get_union_info(id);
                 format!("({}) {}", "union".bold(), info.name)
             }
+            ID::Synonym(id) => {
+                let info = self.entities.get_synonym_info(id);
+                format!("({}) {}", "type".bold(), info.name)
+            }
             ID::Class(id) => {
                 let info = self.entities.get_class_info(id);
                 format!("({}) {}", "class".bold(), info.name)
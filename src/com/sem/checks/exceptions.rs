@@ -0,0 +1,50 @@
+use crate::{
+    com::{
+        Checker, ir,
+        loc::Span,
+        reporting::{Header, Label, Report},
+    },
+    exe::LayoutTable,
+};
+
+impl Checker<'_, '_> {
+    pub fn create_exception(&mut self, ty: ir::TypeID, span: Span) -> ir::ExceptionID {
+        self.entities.create_exception(ir::ExceptionInfo {
+            loc: span.wrap(self.file),
+            ty,
+            tag: String::new(),
+            layout: LayoutTable::ANY,
+        })
+    }
+
+    // the type of a raised or caught exception is only known once its whole module is checked
+    // at that point, it must be a fully known union type, whose tag identifies it at runtime
+    pub fn resolve_exception_tags(&mut self, first: ir::ExceptionID) {
+        let last = self.entities.next_exception_id();
+        for id in first.0..last.0 {
+            let info = self.entities.get_exception_info(ir::ExceptionID(id));
+            let (ty, loc) = (info.ty, info.loc);
+
+            if !self.is_union_type(ty) {
+                self.reports.push(
+                    Report::error(Header::InvalidExceptionType(self.get_type_string(ty)))
+                        .with_primary_label(Label::ExceptionMustBeUnion, loc),
+                );
+                continue;
+            }
+
+            let Some(tag) = self.get_type_tag(ty) else {
+                self.reports.push(
+                    Report::error(Header::AmbiguousExceptionType(self.get_type_string(ty)))
+                        .with_primary_label(Label::ExceptionTypeMustBeKnown, loc),
+                );
+                continue;
+            };
+
+            let layout = self.get_type_layout(ty);
+            let info = self.entities.get_exception_info_mut(ir::ExceptionID(id));
+            info.tag = tag;
+            info.layout = layout;
+        }
+    }
+}
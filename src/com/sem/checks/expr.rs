@@ -18,16 +18,24 @@ impl Checker<'_, '_> {
             E::Int(e) => self.check_int(e),
             E::Float(e) => self.check_float(e),
             E::String(e) => self.check_string(e),
+            E::Interpolation(e) => self.check_interpolation(e),
             E::Builtin(e) => self.check_builtin(e),
             E::True(e) => self.check_bool(e, true),
             E::False(e) => self.check_bool(e, false),
             E::Var(e) => self.check_var(e),
+            E::Hole(e) => {
+                let name = format!("?{}", e.name.lexeme(self.source));
+                self.check_hole(name, e.span())
+            }
+            E::Underscores(e) => self.check_hole(e.span.lexeme(self.source).to_string(), e.span),
             E::Tuple(e) => self.check_tuple(e),
             E::Array(e) => self.check_array(e),
             E::Block(e) => self.check_block(e),
             E::Conditional(e) => self.check_conditional(e),
             E::Break(e) => self.check_break(e),
             E::Skip(e) => self.check_skip(e),
+            E::Raise(e) => self.check_raise(e),
+            E::Assign(e) => self.check_assign(e),
             E::Call(e) => self.check_call(e),
             E::Index(e) => self.check_index(e),
             E::Access(e) => self.check_access(e),
@@ -35,6 +43,8 @@ impl Checker<'_, '_> {
             E::RecordValue(e) => self.check_record_value(e),
             E::Binary(e) => self.check_binary(e),
             E::Unary(e) => self.check_unary(e),
+            E::Annotated(e) => self.check_annotated(e),
+            E::With(e) => self.check_with(e),
             _ => {
                 self.reports.push(
                     Report::error(Header::InvalidExpression())
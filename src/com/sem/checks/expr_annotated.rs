@@ -0,0 +1,11 @@
+use crate::com::{Checker, ast, ir, sem::provenance::Provenance};
+
+impl Checker<'_, '_> {
+    pub fn check_annotated(&mut self, e: &ast::Annotated) -> ir::CheckedExpr {
+        let (expr, ty) = self.check_expression(&e.expr);
+        let annotation_type = self.check_annotation_type(&e.ty);
+        let provenances = &[Provenance::TypeAnnotation(e.span().wrap(self.file))];
+        self.unify(ty, annotation_type, provenances);
+        (expr, annotation_type)
+    }
+}
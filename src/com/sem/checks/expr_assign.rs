@@ -0,0 +1,91 @@
+use crate::com::{
+    Checker, ast, ir,
+    reporting::{Header, Label, Note, Report},
+};
+
+impl Checker<'_, '_> {
+    pub fn check_assign(&mut self, e: &ast::Assign) -> ir::CheckedExpr {
+        let (value, value_type) = self.check_expression(&e.value);
+        let unit_type = self.create_type(ir::Type::Tuple(Box::new([])), Some(e.span()));
+
+        // arrays are updated in place
+        if let ast::Expr::Index(target) = &*e.target {
+            let Some((array, index, item_ty)) = self.check_indexed_array(target) else {
+                return (ir::Expr::Missing, unit_type);
+            };
+
+            self.unify(value_type, item_ty, &[]);
+            return (
+                ir::Expr::ArraySet(Box::new(array), Box::new(index), Box::new(value)),
+                unit_type,
+            );
+        }
+
+        let ast::Expr::Var(target) = &*e.target else {
+            self.reports.push(
+                Report::error(Header::InvalidAssignment())
+                    .with_primary_label(
+                        Label::AssignmentTargetMustBeVariable,
+                        e.target.span().wrap(self.file),
+                    )
+                    .with_secondary_label(Label::Empty, e.span().wrap(self.file)),
+            );
+            return (ir::Expr::Missing, unit_type);
+        };
+
+        let var_id = match self.check_var_path(target) {
+            ir::PathQuery::Var(id) => id,
+            ir::PathQuery::Missing => return (ir::Expr::Missing, unit_type),
+            _ => {
+                self.reports.push(
+                    Report::error(Header::InvalidAssignment())
+                        .with_primary_label(
+                            Label::AssignmentTargetMustBeVariable,
+                            target.span.wrap(self.file),
+                        )
+                        .with_secondary_label(Label::Empty, e.span().wrap(self.file)),
+                );
+                return (ir::Expr::Missing, unit_type);
+            }
+        };
+
+        let info = self.entities.get_variable_info(var_id);
+        let name = info.name.clone();
+        let loc = info.loc;
+        let var_type = info.scheme.uninstantiated;
+
+        match self.mutable_variables.get(&var_id) {
+            None => {
+                self.reports.push(
+                    Report::error(Header::ImmutableAssignment(name.clone()))
+                        .with_primary_label(Label::Empty, e.span().wrap(self.file))
+                        .with_secondary_label(Label::VariableDefinition(name.clone()), loc)
+                        .with_note(Note::MutableBinding(name)),
+                );
+                return (ir::Expr::Missing, unit_type);
+            }
+            Some(depth) if *depth != self.function_depth() => {
+                self.reports.push(
+                    Report::error(Header::CapturedAssignment(name.clone()))
+                        .with_primary_label(
+                            Label::CapturedVariable(name.clone()),
+                            e.span().wrap(self.file),
+                        )
+                        .with_secondary_label(Label::VariableDefinition(name), loc),
+                );
+                return (ir::Expr::Missing, unit_type);
+            }
+            Some(_) => {}
+        }
+
+        self.unify(value_type, var_type, &[]);
+
+        (
+            ir::Expr::Assign {
+                var: var_id,
+                value: Box::new(value),
+            },
+            unit_type,
+        )
+    }
+}
@@ -1,5 +1,6 @@
 use crate::com::{
     Checker, ast, ir,
+    loc::Span,
     reporting::{Header, Label, Report},
 };
 
@@ -29,6 +30,7 @@ impl Checker<'_, '_> {
             "int_pos" => builtin_func!(self, span, int_pos :: int -> int),
             "int_neg" => builtin_func!(self, span, int_neg :: int -> int),
             "int_not" => builtin_func!(self, span, int_not :: int -> int),
+            "int_str" => builtin_func!(self, span, int_str :: int -> string),
 
             "float_add" => builtin_func!(self, span, float_add :: float, float -> float),
             "float_sub" => builtin_func!(self, span, float_sub :: float, float -> float),
@@ -43,6 +45,7 @@ impl Checker<'_, '_> {
             "float_ge" => builtin_func!(self, span, float_ge :: float, float -> bool),
             "float_pos" => builtin_func!(self, span, float_pos :: float -> float),
             "float_neg" => builtin_func!(self, span, float_neg :: float -> float),
+            "float_str" => builtin_func!(self, span, float_str :: float -> string),
 
             "string_concat" => builtin_func!(self, span, string_concat :: string, string -> string),
             "string_eq" => builtin_func!(self, span, string_eq :: string, string -> bool),
@@ -69,6 +72,13 @@ impl Checker<'_, '_> {
             "acos" => builtin_func!(self, span, acos :: float -> float),
             "atan" => builtin_func!(self, span, atan :: float -> float),
 
+            "array_len" => self.check_array_builtin(span, ir::Builtin::array_len),
+            "array_get" => self.check_array_builtin(span, ir::Builtin::array_get),
+            "array_set" => self.check_array_builtin(span, ir::Builtin::array_set),
+            "array_push" => self.check_array_builtin(span, ir::Builtin::array_push),
+            "array_pop" => self.check_array_builtin(span, ir::Builtin::array_pop),
+            "array_slice" => self.check_array_builtin(span, ir::Builtin::array_slice),
+
             "panic" => {
                 let arg_ty = self.create_fresh_type(None);
                 let ret_ty = self.create_fresh_type(None);
@@ -92,6 +102,32 @@ impl Checker<'_, '_> {
     }
 }
 
+impl Checker<'_, '_> {
+    // array builtins are generic over the item type
+    fn check_array_builtin(&mut self, span: Span, builtin: ir::Builtin) -> ir::CheckedExpr {
+        use ir::Builtin as Bi;
+        let item = self.create_fresh_type(None);
+        let array = self.create_type(Ty::Array(item), None);
+        let unit = self.create_type(Ty::unit(), None);
+        let int = self.native_types.int;
+
+        let (args, ret): (Box<[_]>, _) = match builtin {
+            Bi::array_len => (Box::new([array]), int),
+            Bi::array_get => (Box::new([array, int]), item),
+            Bi::array_set => (Box::new([array, int, item]), unit),
+            Bi::array_push => (Box::new([array, item]), unit),
+            Bi::array_pop => (Box::new([array]), item),
+            Bi::array_slice => (Box::new([array, int, int]), array),
+            _ => unreachable!("'{builtin}' is not an array builtin"),
+        };
+
+        (
+            ir::Expr::Builtin(builtin),
+            self.create_type(Ty::Lambda(args, ret), Some(span)),
+        )
+    }
+}
+
 macro_rules! builtin_func {
     (
         $self:ident, $span:ident,
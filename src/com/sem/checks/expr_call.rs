@@ -2,7 +2,15 @@ use crate::com::{Checker, ast, ir, sem::provenance::Provenance};
 
 impl Checker<'_, '_> {
     pub fn check_call(&mut self, e: &ast::Call) -> ir::CheckedExpr {
-        let (callee, callee_type) = self.check_expression(&e.callee);
+        let callee = self.check_expression(&e.callee);
+        self.check_call_with_callee(callee, e)
+    }
+
+    pub fn check_call_with_callee(
+        &mut self,
+        (callee, callee_type): ir::CheckedExpr,
+        e: &ast::Call,
+    ) -> ir::CheckedExpr {
         let (args, arg_types) = self.check_expression_list(&e.args);
 
         let ret_type = self.create_fresh_type(Some(e.span()));
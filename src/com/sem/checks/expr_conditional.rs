@@ -11,7 +11,7 @@ impl Checker<'_, '_> {
         let mut branches = Vec::with_capacity(e.else_branches.len() + 1);
         let mut branch_types = Vec::with_capacity(e.else_branches.len() + 1);
         let (first_branch, first_branch_type, mut is_exhaustive) =
-            self.check_branch(&e.first_branch);
+            self.check_branch(&e.first_branch, !e.else_branches.is_empty());
         branches.push(first_branch);
         branch_types.push(first_branch_type);
 
@@ -19,9 +19,11 @@ impl Checker<'_, '_> {
         let mut exhaustive_branch_count = 1;
         let mut unreachable_branches_span = None;
         let mut unreachable_branch_count = 0;
-        for (else_tok, else_branch) in &e.else_branches {
+        for (i, (else_tok, else_branch)) in e.else_branches.iter().enumerate() {
             let branch_span = Span::combine(*else_tok, else_branch.span());
-            let (branch, else_branch_type, is_else_exhaustive) = self.check_branch(else_branch);
+            let has_fallback = i + 1 < e.else_branches.len();
+            let (branch, else_branch_type, is_else_exhaustive) =
+                self.check_branch(else_branch, has_fallback);
             branch_types.push(else_branch_type);
 
             if !is_exhaustive {
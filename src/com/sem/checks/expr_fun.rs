@@ -2,6 +2,7 @@ use crate::com::{
     Checker, ast, ir,
     loc::Span,
     reporting::{Header, Label, Report},
+    sem::provenance::Provenance,
 };
 
 impl Checker<'_, '_> {
@@ -23,12 +24,23 @@ impl Checker<'_, '_> {
         let fun_uid_name = format!("fun_{}", self.get_generic_unique_id());
         self.set_scope_name(fun_uid_name);
 
+        let previous = self.open_type_annotations();
         let (sig, sig_type, ret_type, id) = self.declare_signature(&signature);
+        let type_vars = self.close_type_annotations(previous);
+        self.declare_implicit_type_vars(&type_vars);
+
         self.set_type_span(sig_type, sig_span);
-        self.set_type_span(ret_type, e.value.span());
+        let provenances = match signature.return_annotation() {
+            Some(_) => vec![Provenance::TypeAnnotation(sig_span.wrap(self.file))],
+            None => {
+                self.set_type_span(ret_type, e.value.span());
+                vec![]
+            }
+        };
 
         let (val, val_type) = self.check_expression(&e.value);
-        self.unify(val_type, ret_type, &[]);
+        self.unify(val_type, ret_type, &provenances);
+        self.check_implicit_type_vars(&type_vars);
 
         let fun_name = self.build_scope_name();
         self.close_scope();
@@ -0,0 +1,99 @@
+use std::collections::{BTreeSet, HashSet};
+
+use crate::com::{
+    Checker, ir,
+    loc::Span,
+    reporting::{Header, Label, Note, Report},
+    sem::checker::Hole,
+};
+
+impl Checker<'_, '_> {
+    // a hole stands for a value of any type, and is reported once its statement is solved
+    pub fn check_hole(&mut self, name: String, span: Span) -> ir::CheckedExpr {
+        let ty = self.create_fresh_type(None);
+
+        // the variables bound inside top-level statements, shadowed ones left out
+        let mut seen = HashSet::new();
+        let mut locals = Vec::new();
+        for (&local, id) in self.scope.iter_deeper_than(1) {
+            if !seen.insert(local) {
+                continue;
+            }
+            if let ir::AnyID::Variable(var_id) = id {
+                let info = self.entities.get_variable_info(*var_id);
+                locals.push((local.to_string(), info.scheme.uninstantiated));
+            }
+        }
+        locals.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        self.holes.push(Hole {
+            name,
+            ty,
+            loc: span.wrap(self.file),
+            locals,
+            constraints: Vec::new(),
+        });
+
+        (ir::Expr::Missing, ty)
+    }
+
+    // constraints left after solving are pending on the holes whose type they involve
+    pub fn attach_hole_constraints(&mut self, constraints: &[ir::Constraint]) {
+        for i in 0..self.holes.len() {
+            let mut hole_vars = BTreeSet::new();
+            self.collect_type_variables(self.holes[i].ty, &mut hole_vars);
+            for constraint in constraints {
+                let mut vars = BTreeSet::new();
+                self.collect_constraint_variables(constraint, &mut vars);
+                if !vars.is_disjoint(&hole_vars) {
+                    self.holes[i].constraints.push(constraint.clone());
+                }
+            }
+        }
+    }
+
+    // returns whether there were holes to report
+    pub fn report_holes(&mut self) -> bool {
+        let holes = std::mem::take(&mut self.holes);
+        let has_holes = !holes.is_empty();
+        for hole in holes {
+            let mut domain = BTreeSet::new();
+            self.collect_type_variables(hole.ty, &mut domain);
+            for (_, ty) in &hole.locals {
+                self.collect_type_variables(*ty, &mut domain);
+            }
+            for constraint in &hole.constraints {
+                self.collect_constraint_variables(constraint, &mut domain);
+            }
+            let (name_map, _) = self.create_domain_name_map(&domain);
+
+            let ty = self.get_type_string_map(hole.ty, &name_map, true);
+            let locals = hole
+                .locals
+                .iter()
+                .map(|(name, ty)| {
+                    format!("{name}: {}", self.get_type_string_map(*ty, &name_map, true))
+                })
+                .collect();
+            let mut constraints = Vec::new();
+            for constraint in &hole.constraints {
+                let constr_str = format!(
+                    "[{}]",
+                    self.get_constraint_string_map(constraint, &name_map, true)
+                );
+                if !constraints.contains(&constr_str) {
+                    constraints.push(constr_str);
+                }
+            }
+
+            let mut report = Report::error(Header::TypedHole(hole.name))
+                .with_primary_label(Label::HoleType(ty), hole.loc)
+                .with_note(Note::HoleLocals(locals));
+            if !constraints.is_empty() {
+                report = report.with_note(Note::HoleConstraints(constraints));
+            }
+            self.reports.push(report);
+        }
+        has_holes
+    }
+}
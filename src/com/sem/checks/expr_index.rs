@@ -7,50 +7,71 @@ use crate::com::{
 
 impl Checker<'_, '_> {
     pub fn check_index(&mut self, e: &ast::Index) -> ir::CheckedExpr {
-        let (indexed, indexed_ty) = self.check_expression(&e.indexed);
+        let Some((array, index, item_ty)) = self.check_indexed_array(e) else {
+            return (ir::Expr::Missing, self.create_fresh_type(None));
+        };
+
+        (
+            ir::Expr::Index {
+                indexed: Box::new(array),
+                index: Box::new(index),
+            },
+            item_ty,
+        )
+    }
+
+    // returns the innermost indexed array, its index, and the type of its items
+    // a[i, j] indexes nested arrays, like a[i][j]
+    pub fn check_indexed_array(
+        &mut self,
+        e: &ast::Index,
+    ) -> Option<(ir::Expr, ir::Expr, ir::TypeID)> {
+        let (mut indexed, mut indexed_ty) = self.check_expression(&e.indexed);
         let mut checked_indices = e
             .indices
             .iter()
             .map(|item| self.check_expression(item))
             .collect::<Vec<_>>();
 
-        let item_ty = self.create_fresh_type(None);
-        let array_ty = self.create_type(ir::Type::Array(item_ty), None);
-        let provenances = &[Provenance::IndexedMustBeArray(e.span().wrap(self.file))];
-        self.unify(indexed_ty, array_ty, provenances);
-
         let indices_span = Span::combine(e.left_bracket, e.right_bracket);
 
-        if checked_indices.len() > 1 {
-            self.reports.push(
-                Report::error(Header::InvalidIndexing())
-                    .with_primary_label(
-                        Label::UnsupportedMultidimensionalIndex,
-                        indices_span.wrap(self.file),
-                    )
-                    .with_secondary_label(Label::Empty, e.span().wrap(self.file)),
-            );
-            return (ir::Expr::Missing, item_ty);
-        }
-
-        let Some((index, index_ty)) = checked_indices.pop() else {
+        let Some((last_index, last_index_ty)) = checked_indices.pop() else {
             self.reports.push(
                 Report::error(Header::InvalidIndexing())
                     .with_primary_label(Label::MissingIndex, indices_span.wrap(self.file))
                     .with_secondary_label(Label::Empty, e.span().wrap(self.file)),
             );
-            return (ir::Expr::Missing, item_ty);
+            return None;
         };
 
+        for (index, index_ty) in checked_indices {
+            let item_ty = self.unify_indexing(e, indexed_ty, index_ty);
+            indexed = ir::Expr::Index {
+                indexed: Box::new(indexed),
+                index: Box::new(index),
+            };
+            indexed_ty = item_ty;
+        }
+
+        let item_ty = self.unify_indexing(e, indexed_ty, last_index_ty);
+        Some((indexed, last_index, item_ty))
+    }
+
+    fn unify_indexing(
+        &mut self,
+        e: &ast::Index,
+        indexed_ty: ir::TypeID,
+        index_ty: ir::TypeID,
+    ) -> ir::TypeID {
+        let item_ty = self.create_fresh_type(None);
+        let array_ty = self.create_type(ir::Type::Array(item_ty), None);
+        let provenances = &[Provenance::IndexedMustBeArray(e.span().wrap(self.file))];
+        self.unify(indexed_ty, array_ty, provenances);
+
+        let indices_span = Span::combine(e.left_bracket, e.right_bracket);
         let provenances = &[Provenance::IndexMustBeInteger(indices_span.wrap(self.file))];
         self.unify(index_ty, self.native_types.int, provenances);
 
-        (
-            ir::Expr::Index {
-                indexed: Box::new(indexed),
-                index: Box::new(index),
-            },
-            item_ty,
-        )
+        item_ty
     }
 }
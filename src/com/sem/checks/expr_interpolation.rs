@@ -0,0 +1,60 @@
+use crate::com::{Checker, ast, ir};
+
+impl Checker<'_, '_> {
+    // "a {x} b {y}" is checked as ("a " + Display.str(x)) + " b " + Display.str(y)
+    pub fn check_interpolation(&mut self, e: &ast::Interpolation) -> ir::CheckedExpr {
+        let mut result = None;
+        for part in &e.parts {
+            let part = match part {
+                ast::InterpolationPart::Text(span) => ir::Expr::String {
+                    val: span
+                        .lexeme(self.source)
+                        .replace("{{", "{")
+                        .replace("}}", "}"),
+                },
+                ast::InterpolationPart::Hole(hole) => self.check_interpolation_hole(hole),
+            };
+
+            result = Some(match result {
+                Some(left) => ir::Expr::Add(Box::new(left), Box::new(part)),
+                None => part,
+            });
+        }
+
+        (
+            result.unwrap_or(ir::Expr::String { val: String::new() }),
+            self.create_type(ir::Type::String, Some(e.span)),
+        )
+    }
+
+    fn check_interpolation_hole(&mut self, hole: &ast::Expr) -> ir::Expr {
+        // erroneous holes were already reported, don't require Display for them
+        let (value, value_ty) = self.check_expression(hole);
+        if let ir::Expr::Missing = value {
+            return ir::Expr::Missing;
+        }
+
+        let Some(prelude_file) = self.deps.info.prelude_file else {
+            panic!("cannot use string interpolation without std")
+        };
+
+        let display_exports = self.get_marin_std_module_exports(prelude_file, "Display");
+        let Some(ir::AnyID::Class(display_class_id)) = display_exports.get("Display").copied()
+        else {
+            panic!("couldn't find Display.Display class for string interpolation")
+        };
+
+        let span = hole.span();
+        let (str_fn, str_ty) = self.check_class_item_into_expr(display_class_id, 0, span);
+        let expected_str_ty = self.create_type(
+            ir::Type::Lambda(Box::new([value_ty]), self.native_types.string),
+            Some(span),
+        );
+        self.unify(str_ty, expected_str_ty, &[]);
+
+        ir::Expr::Call {
+            callee: Box::new(str_fn),
+            args: Box::new([value]),
+        }
+    }
+}
@@ -109,14 +109,22 @@ impl Checker<'_, '_> {
         )
     }
 
-    fn get_marin_std_ops_exports(&mut self, prelude_file: usize) -> &HashMap<&str, ir::AnyID> {
+    pub fn get_marin_std_ops_exports(&mut self, prelude_file: usize) -> &HashMap<&str, ir::AnyID> {
+        self.get_marin_std_module_exports(prelude_file, "ops")
+    }
+
+    pub fn get_marin_std_module_exports(
+        &mut self,
+        prelude_file: usize,
+        module: &str,
+    ) -> &HashMap<&str, ir::AnyID> {
         let prelude_exports = &self.exports[prelude_file].exports;
-        let Some(ir::AnyID::Import(ops_import_id)) = prelude_exports.get("ops").copied() else {
-            panic!("couldn't find 'ops' import in 'std.prelude'")
+        let Some(ir::AnyID::Import(import_id)) = prelude_exports.get(module).copied() else {
+            panic!("couldn't find '{module}' import in 'std.prelude'")
         };
-        let ops_import_info = self.entities.get_import_info(ops_import_id);
-        let ops_file = ops_import_info.file;
-        &self.exports[ops_file].exports
+        let import_info = self.entities.get_import_info(import_id);
+        let file = import_info.file;
+        &self.exports[file].exports
     }
 
     fn check_boolean_binary(&mut self, e: &ast::Binary) -> ir::CheckedExpr {
@@ -0,0 +1,16 @@
+use crate::com::{Checker, ast, ir};
+
+impl Checker<'_, '_> {
+    pub fn check_raise(&mut self, e: &ast::Raise) -> ir::CheckedExpr {
+        let (value, value_type) = self.check_expression(&e.value);
+        let exception = self.create_exception(value_type, e.span());
+
+        (
+            ir::Expr::Raise {
+                value: Box::new(value),
+                exception,
+            },
+            self.create_fresh_type(Some(e.span())),
+        )
+    }
+}
@@ -8,6 +8,12 @@ use crate::com::{
 
 impl Checker<'_, '_> {
     pub fn check_record_value(&mut self, e: &ast::RecordValue) -> ir::CheckedExpr {
+        // { ..base, ... } copies the fields that aren't set from the base record
+        let base = e
+            .base
+            .as_ref()
+            .map(|(_, base)| (self.check_expression(base), base.span()));
+
         let mut fields = HashMap::new();
         for (name, expr) in &e.fields {
             // find the field's name
@@ -45,8 +51,21 @@ impl Checker<'_, '_> {
             }
         }
 
+        // the base was already reported as erroneous
+        if let Some(((ir::Expr::Missing, _), _)) = base {
+            return self.check_missing();
+        }
+
         let field_names = fields.keys().copied().collect::<Vec<_>>();
+        let base_record_id = base
+            .as_ref()
+            .and_then(|&((_, ty), _)| self.get_record_type_id(ty));
         let mut record_types = self.get_admissible_records(&field_names);
+        if record_types.len() > 1
+            && let Some(base_record_id) = base_record_id
+        {
+            record_types.retain(|&(id, _)| id == base_record_id);
+        }
 
         if record_types.is_empty() {
             self.reports.push(
@@ -77,14 +96,31 @@ impl Checker<'_, '_> {
         let record_value_type = self.clone_type_repr(record_value_type);
         self.set_type_span(record_value_type, e.span());
 
-        // check that all fields are actually set
+        let base_var = base.map(|((base, base_ty), base_span)| {
+            let provenances = &[Provenance::RecordUpdateBase(
+                record_name.clone(),
+                base_span.wrap(self.file),
+            )];
+            let expected_base_ty = self.clone_type_repr(record_value_type);
+            self.set_type_loc(expected_base_ty, record_loc);
+            self.unify(base_ty, expected_base_ty, provenances);
+            (self.entities.create_dummy_variable(), base)
+        });
+
+        // check that all fields are actually set, or copied from the base
         let mut missing_fields = Vec::new();
         let mut set_fields = Vec::new();
         let info = self.entities.get_record_info(record_id);
         for (i, field_info) in info.fields.clone().iter().enumerate() {
             let Some((field_value, field_value_ty)) = fields.remove(field_info.name.as_str())
             else {
-                missing_fields.push(i);
+                match &base_var {
+                    Some((var, _)) => set_fields.push(ir::Expr::Access {
+                        accessed: Box::new(ir::Expr::Var { id: *var }),
+                        index: i,
+                    }),
+                    None => missing_fields.push(i),
+                }
                 continue;
             };
             set_fields.push(field_value);
@@ -116,9 +152,29 @@ impl Checker<'_, '_> {
             );
         }
 
+        let record = ir::Expr::Record {
+            fields: set_fields.into(),
+        };
+        let Some((var, base)) = base_var else {
+            return (record, record_value_type);
+        };
+
+        // let b = base in { f0 = b.0, f1 = value, ... }
+        let stmts = [
+            ir::Stmt::Let {
+                lhs: ir::Pattern::Binding(var),
+                rhs: base,
+                is_concrete: true,
+                solutions: vec![],
+            },
+            ir::Stmt::Expr {
+                expr: record,
+                ty: record_value_type,
+            },
+        ];
         (
-            ir::Expr::Record {
-                fields: set_fields.into(),
+            ir::Expr::BlockUnlabelled {
+                stmts: Box::new(stmts),
             },
             record_value_type,
         )
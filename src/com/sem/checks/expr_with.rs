@@ -0,0 +1,90 @@
+use crate::com::{
+    Checker, ast, ir,
+    reporting::{Header, Label, Report},
+};
+
+impl Checker<'_, '_> {
+    // the constraints of the expression on the class of the instance are solved by it,
+    // and the other ones are left to the enclosing statement
+    pub fn check_with(&mut self, e: &ast::With) -> ir::CheckedExpr {
+        let outer_constraints = self.take_constraint_context();
+        let (expr, ty) = self.check_expression(&e.expr);
+        let constraints = self.take_constraint_context();
+        self.restore_constraint_context(outer_constraints);
+
+        // the constraints are dropped when there is no instance, as it was already reported
+        let Some(instance_id) = self.check_named_instance(&e.instance) else {
+            return (expr, ty);
+        };
+
+        if !self.select_named_instance(instance_id, constraints) {
+            let class_id = self
+                .entities
+                .get_instance_info(instance_id)
+                .scheme
+                .constraint
+                .id;
+            let class_name = self.entities.get_class_info(class_id).name.clone();
+            self.reports.push(
+                Report::warning(Header::UnselectedInstance(class_name.clone()))
+                    .with_primary_label(
+                        Label::NoConstraintOfClass(class_name),
+                        e.instance.span().wrap(self.file),
+                    )
+                    .with_secondary_label(Label::Empty, e.expr.span().wrap(self.file)),
+            );
+        }
+
+        (expr, ty)
+    }
+
+    // solves the constraints on the class of the named instance with it, and requires the others,
+    // returns whether any constraint was solved
+    pub fn select_named_instance(
+        &mut self,
+        instance_id: ir::InstanceID,
+        constraints: Vec<ir::Constraint>,
+    ) -> bool {
+        let scheme = self.entities.get_instance_info(instance_id).scheme.clone();
+        let mut is_selected = false;
+        for mut constraint in constraints {
+            if constraint.id != scheme.constraint.id {
+                self.require_class_constraint(constraint);
+                continue;
+            }
+
+            let (additional_constraint_id, instance_constraint, additional) =
+                self.instantiate_instance_scheme(scheme.clone());
+            self.unify_constraint(&constraint, &instance_constraint);
+            for additional_constraint in additional {
+                self.require_class_constraint(additional_constraint);
+            }
+
+            let trace = std::mem::take(&mut constraint.constraint_trace);
+            let self_solutions = self.solve_self_constraints(instance_id, additional_constraint_id);
+            self.selected_solutions.push(ir::Solution {
+                trace,
+                dictionary: ir::Dictionary::Instance(instance_id),
+                additional_constraint_id,
+            });
+            self.selected_solutions.extend(self_solutions);
+            is_selected = true;
+        }
+        is_selected
+    }
+
+    fn check_named_instance(&mut self, e: &ast::Expr) -> Option<ir::InstanceID> {
+        use ir::PathQuery as Q;
+        match self.check_path_or_expr(e) {
+            Q::Instance(id) => Some(id),
+            Q::Missing => None,
+            _ => {
+                self.reports.push(
+                    Report::error(Header::InvalidInstance())
+                        .with_primary_label(Label::NotAnInstance, e.span().wrap(self.file)),
+                );
+                None
+            }
+        }
+    }
+}
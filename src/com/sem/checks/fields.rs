@@ -0,0 +1,86 @@
+use crate::com::{
+    Checker, ir,
+    loc::Loc,
+    reporting::{Header, Label, Report},
+};
+
+impl Checker<'_, '_> {
+    // structural access to a field is constrained by a class of its own, as in [.name(r) of t]
+    // which is shared by every access to a field of that name
+    pub fn get_field_class(&mut self, field: &str, loc: Loc) -> ir::ClassID {
+        if let Some(id) = self.field_classes.get(field) {
+            return *id;
+        }
+
+        let id = self.entities.create_class(ir::ClassInfo {
+            name: format!(".{field}"),
+            loc,
+            items: Default::default(),
+            arity: (1, 1),
+            superclasses: Default::default(),
+            field: Some(field.to_string()),
+        });
+        self.field_classes.insert(field.to_string(), id);
+        id
+    }
+
+    // the index of the field within the record type of the constraint,
+    // whose type is unified with the one of the constraint
+    pub fn check_field_constraint(
+        &mut self,
+        constraint: &ir::Constraint,
+        field: &str,
+    ) -> Option<usize> {
+        let record_type = constraint.class_args[0];
+        let repr = self.get_type_repr(record_type);
+        let found = match self.get_type_view(repr) {
+            ir::Type::Record(id, _) => self
+                .entities
+                .get_record_info(id)
+                .fields
+                .iter()
+                .position(|info| info.name == field)
+                .map(|index| (id, index)),
+            _ => None,
+        };
+
+        let Some((record_id, index)) = found else {
+            let ty = self.get_type_string(record_type);
+            self.reports.push(
+                Report::error(Header::UnknownField(field.to_string(), ty))
+                    .with_primary_label(Label::Empty, constraint.loc),
+            );
+            return None;
+        };
+
+        let (info, field_info) = self.entities.get_record_field_info(record_id, index);
+        let domain = info.scheme.forall.clone();
+        let uninstantiated_record = info.scheme.uninstantiated;
+        let uninstantiated_field = field_info.ty;
+        let sub = self.build_type_substitution(domain);
+
+        let instantiated_record = self.apply_type_substitution(uninstantiated_record, &sub);
+        let field_type = self.apply_type_substitution(uninstantiated_field, &sub);
+        self.unify(record_type, instantiated_record, &[]);
+        self.unify(constraint.associated_args[0], field_type, &[]);
+
+        Some(index)
+    }
+
+    // accesses to the same field of values of the same type are of the same type,
+    // even before the record is known
+    pub fn merge_field_constraints(&mut self, constraints: &[ir::Constraint]) {
+        for (i, left) in constraints.iter().enumerate() {
+            if self.entities.get_class_info(left.id).field.is_none() {
+                continue;
+            }
+
+            for right in &constraints[i + 1..] {
+                if left.id == right.id && self.is_same_type(left.class_args[0], right.class_args[0])
+                {
+                    self.unify(left.associated_args[0], right.associated_args[0], &[]);
+                }
+            }
+        }
+    }
+}
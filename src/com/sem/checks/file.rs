@@ -1,10 +1,13 @@
-use crate::com::{
-    Checker, ast, ir,
-    reporting::{Header, Label, Note, Report},
-    sem::checker::{Export, checker_print},
+use crate::{
+    com::{
+        Checker, ast, ir,
+        reporting::{Header, Label, Note, Report},
+        sem::checker::{Export, checker_print},
+    },
+    exe::{Layout, LayoutID},
 };
 use colored::Colorize;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 impl<'src> Checker<'src, '_> {
     pub fn check_module(
@@ -33,39 +36,58 @@ impl<'src> Checker<'src, '_> {
             self.import_std_prelude();
         }
 
+        let first_exception = self.entities.next_exception_id();
+
         let mut stmts = Vec::with_capacity(ast.0.len());
         let mut module_solutions = Vec::new();
-        for e in &ast.0 {
-            let stmt = self.check_statement(e);
-            stmts.push(stmt);
-
-            // constraints on a top-level statement cannot are unallowed
-            // because such a statement cannot be compiled
-            let (mut solutions, constraints) = self.solve_constraints();
-            if !constraints.is_empty() {
-                let constraint_strings = constraints
-                    .iter()
-                    .map(|constr| self.get_constraint_string(constr))
-                    .collect::<Vec<_>>();
-
-                let mut rep = Report::error(Header::TopLevelConstraint());
-                for (constr, constr_str) in constraints.iter().zip(constraint_strings) {
-                    rep = rep.with_secondary_label(Label::ConstraintOrigin(constr_str), constr.loc);
-                }
-
-                self.reports.push(
-                    rep.with_primary_label(
-                        Label::UnsatisfiedConstraints(constraints.len()),
-                        e.span().wrap(self.file),
-                    )
-                    .with_note(Note::TopLevelUnknownTypes),
-                );
+        let mut grouped = BTreeSet::new();
+        let mut declared_types = HashMap::new();
+        for i in 0..ast.0.len() {
+            if grouped.contains(&i) {
+                stmts.push(ir::Stmt::Nothing);
+                continue;
+            }
+
+            // types declared next to each other are bound to their names before being defined
+            if !declared_types.contains_key(&i) {
+                declared_types.extend(self.declare_type_group(&ast.0, i));
             }
 
-            module_solutions.append(&mut solutions);
+            // functions are checked along with the ones they refer to before they are defined,
+            // those referring to each other together, after the ones they depend on
+            let groups = match declared_types.contains_key(&i) {
+                true => vec![vec![i]],
+                false => self.find_function_groups(&ast.0, i),
+            };
+            for group in groups {
+                let e = &ast.0[group[0]];
+                let stmt = if let Some(declared) = declared_types.get(&i) {
+                    match declared {
+                        Some(id) => self.define_type(e, *id),
+                        None => ir::Stmt::Nothing,
+                    }
+                } else if group.len() > 1 {
+                    let lets = group
+                        .iter()
+                        .filter_map(|j| Self::as_function_let(&ast.0[*j]))
+                        .collect::<Vec<_>>();
+                    self.check_let_group(&lets)
+                } else {
+                    self.check_statement(e)
+                };
+                grouped.extend(group);
+                stmts.push(stmt);
+
+                let mut solutions = self.check_top_level_constraints(e);
+                module_solutions.append(&mut solutions);
+            }
         }
 
+        self.resolve_exception_tags(first_exception);
+        let result = self.get_module_result_layout(&stmts);
+
         let (exports, instances) = self.get_public_exports_and_instances();
+        let functions = self.get_module_host_functions(&exports);
         self.close_scope();
 
         if !exports.is_empty() {
@@ -85,7 +107,84 @@ impl<'src> Checker<'src, '_> {
         ir::Module {
             stmts: stmts.into(),
             solutions: module_solutions,
+            result,
+            functions,
+        }
+    }
+
+    // constraints on a top-level statement cannot are unallowed
+    // because such a statement cannot be compiled
+    // unless they are pending on holes, which are reported instead
+    fn check_top_level_constraints(&mut self, e: &ast::Expr) -> Vec<ir::Solution> {
+        let (solutions, constraints) = self.solve_constraints();
+        let has_holes = self.report_holes();
+        if !constraints.is_empty() && !has_holes {
+            let constraint_strings = constraints
+                .iter()
+                .map(|constr| self.get_constraint_string(constr))
+                .collect::<Vec<_>>();
+
+            let mut rep = Report::error(Header::TopLevelConstraint());
+            for (constr, constr_str) in constraints.iter().zip(constraint_strings) {
+                rep = rep.with_secondary_label(Label::ConstraintOrigin(constr_str), constr.loc);
+            }
+
+            self.reports.push(
+                rep.with_primary_label(
+                    Label::UnsatisfiedConstraints(constraints.len()),
+                    e.span().wrap(self.file),
+                )
+                .with_note(Note::TopLevelUnknownTypes),
+            );
+        }
+
+        solutions
+    }
+
+    // the layout of the last value computed by the module
+    // which is the result of the program if it comes last
+    fn get_module_result_layout(&mut self, stmts: &[ir::Stmt]) -> Option<LayoutID> {
+        let last = stmts
+            .iter()
+            .rfind(|stmt| !matches!(stmt, ir::Stmt::Missing | ir::Stmt::Nothing))?;
+
+        Some(match last {
+            ir::Stmt::Expr { ty, .. } => self.get_type_layout(*ty),
+            _ => self.layouts.add(Layout::Tuple(Box::new([]))),
+        })
+    }
+
+    // public functions which the host may call once the program has run
+    // only concrete variables exist at runtime
+    fn get_module_host_functions(
+        &mut self,
+        exports: &HashMap<&'src str, ir::AnyID>,
+    ) -> Vec<(String, ir::VariableID, LayoutID)> {
+        if !self.options.export_functions {
+            return Vec::new();
+        }
+
+        let mut functions = Vec::new();
+        for id in exports.values() {
+            let &ir::AnyID::Variable(var_id) = id else {
+                continue;
+            };
+
+            let info = self.entities.get_variable_info(var_id);
+            if !info.scheme.constraints.is_empty() {
+                continue;
+            }
+
+            let name = info.name.clone();
+            let layout = self.get_type_layout(info.scheme.uninstantiated);
+            if let Layout::Func(..) = self.layouts.get(layout) {
+                functions.push((name, var_id, layout));
+            }
         }
+
+        // keep the export table deterministic
+        functions.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+        functions
     }
 
     // (exports, instances)
@@ -113,6 +212,8 @@ impl<'src> Checker<'src, '_> {
 pub struct CheckModuleOptions {
     pub is_verbose: bool,
     pub import_prelude: bool,
+    pub export_functions: bool,
+    pub warn_orphan_instances: bool,
 }
 
 impl CheckModuleOptions {
@@ -120,6 +221,8 @@ impl CheckModuleOptions {
         Self {
             is_verbose: false,
             import_prelude: false,
+            export_functions: false,
+            warn_orphan_instances: false,
         }
     }
 
@@ -132,4 +235,14 @@ impl CheckModuleOptions {
         self.import_prelude = import_prelude;
         self
     }
+
+    pub fn set_export_functions(mut self, export_functions: bool) -> Self {
+        self.export_functions = export_functions;
+        self
+    }
+
+    pub fn set_warn_orphan_instances(mut self, warn_orphan_instances: bool) -> Self {
+        self.warn_orphan_instances = warn_orphan_instances;
+        self
+    }
 }
@@ -0,0 +1,175 @@
+use crate::{
+    com::{Checker, ir},
+    exe::{Layout, LayoutID, LayoutTable},
+};
+use std::collections::BTreeMap;
+
+impl Checker<'_, '_> {
+    // types which aren't fully known have the 'any' layout
+    // and their values are given back to the host untyped
+    pub fn get_type_layout(&mut self, ty: ir::TypeID) -> LayoutID {
+        use ir::Type as T;
+        let repr = self.get_type_repr(ty);
+        match self.types[repr.0].ty.clone() {
+            T::Var | T::Constructor(..) | T::Apply(..) => LayoutTable::ANY,
+            T::Int => self.layouts.add(Layout::Int),
+            T::Float => self.layouts.add(Layout::Float),
+            T::Bool => self.layouts.add(Layout::Bool),
+            T::String => self.layouts.add(Layout::String),
+            T::Tuple(items) => {
+                let items = self.get_type_layouts(&items);
+                self.layouts.add(Layout::Tuple(items.into()))
+            }
+            T::Array(item) => {
+                let item = self.get_type_layout(item);
+                self.layouts.add(Layout::Array(item))
+            }
+            T::Lambda(args, ret) => {
+                let args = self.get_type_layouts(&args);
+                let ret = self.get_type_layout(ret);
+                self.layouts.add(Layout::Func(args.into(), ret))
+            }
+            T::Record(eid, args) => self.get_record_layout(eid, args.as_deref()),
+            T::Union(eid, args) => self.get_union_layout(eid, args.as_deref()),
+        }
+    }
+
+    fn get_type_layouts(&mut self, types: &[ir::TypeID]) -> Vec<LayoutID> {
+        types.iter().map(|ty| self.get_type_layout(*ty)).collect()
+    }
+
+    // maps the type variables of a user type to the type arguments it is used with
+    fn build_type_argument_substitution(
+        &mut self,
+        uninstantiated: ir::TypeID,
+        args: Option<&[ir::TypeID]>,
+    ) -> BTreeMap<ir::TypeID, ir::TypeID> {
+        use ir::Type as T;
+        let uninstantiated = self.get_type_repr(uninstantiated);
+        let vars = match &self.types[uninstantiated.0].ty {
+            T::Record(_, Some(vars)) | T::Union(_, Some(vars)) => vars.clone(),
+            _ => Box::new([]),
+        };
+
+        vars.iter()
+            .zip(args.unwrap_or_default())
+            .map(|(var, arg)| (self.get_type_repr(*var), *arg))
+            .collect()
+    }
+
+    fn get_record_layout(&mut self, eid: ir::RecordID, args: Option<&[ir::TypeID]>) -> LayoutID {
+        let arg_layouts = self.get_type_layouts(args.unwrap_or_default());
+        let key = (eid.wrap(), arg_layouts);
+        if let Some(id) = self.user_type_layouts.get(&key) {
+            return *id;
+        }
+
+        // records may contain themselves
+        let id = self.layouts.reserve();
+        self.user_type_layouts.insert(key, id);
+
+        let info = self.entities.get_record_info(eid);
+        let name = info.name.clone();
+        let uninstantiated = info.scheme.uninstantiated;
+        let fields = info.fields.clone();
+
+        let sub = self.build_type_argument_substitution(uninstantiated, args);
+        let fields = fields
+            .iter()
+            .map(|field| {
+                let field_ty = self.apply_type_substitution(field.ty, &sub);
+                (field.name.clone(), self.get_type_layout(field_ty))
+            })
+            .collect();
+
+        self.layouts.define(id, Layout::Record(name, fields));
+        id
+    }
+
+    fn get_union_layout(&mut self, eid: ir::UnionID, args: Option<&[ir::TypeID]>) -> LayoutID {
+        let arg_layouts = self.get_type_layouts(args.unwrap_or_default());
+        let key = (eid.wrap(), arg_layouts);
+        if let Some(id) = self.user_type_layouts.get(&key) {
+            return *id;
+        }
+
+        // unions may contain themselves
+        let id = self.layouts.reserve();
+        self.user_type_layouts.insert(key, id);
+
+        let info = self.entities.get_union_info(eid);
+        let name = info.name.clone();
+        let is_newtype = info.is_newtype;
+        let uninstantiated = info.scheme.uninstantiated;
+        let variants = info
+            .variants
+            .iter()
+            .map(|variant| (variant.name.clone(), variant.type_args.clone()))
+            .collect::<Vec<_>>();
+
+        let sub = self.build_type_argument_substitution(uninstantiated, args);
+        let variants = variants
+            .into_iter()
+            .map(|(variant_name, variant_args)| {
+                let variant_args = variant_args
+                    .iter()
+                    .flatten()
+                    .map(|arg| {
+                        let arg_ty = self.apply_type_substitution(*arg, &sub);
+                        self.get_type_layout(arg_ty)
+                    })
+                    .collect::<Box<[_]>>();
+                (variant_name, variant_args)
+            })
+            .collect::<Box<[_]>>();
+
+        // newtypes have the layout of the value they wrap
+        if is_newtype
+            && let [(_, inner)] = &variants[..]
+            && let [inner] = inner[..]
+        {
+            let layout = self.layouts.get(inner).clone();
+            self.layouts.define(id, layout);
+            return id;
+        }
+
+        let list_layout = match self.is_std_list(eid) {
+            true => Self::get_list_layout(id, &variants),
+            false => None,
+        };
+        let layout = list_layout.unwrap_or(Layout::Union(name, variants));
+        self.layouts.define(id, layout);
+        id
+    }
+
+    // only the std list is given back as a list, other unions are given back as variants
+    fn is_std_list(&self, eid: ir::UnionID) -> bool {
+        let Some(prelude_file) = self.deps.info.prelude_file else {
+            return false;
+        };
+        let prelude_exports = &self.exports[prelude_file].exports;
+        let Some(ir::AnyID::Import(import_id)) = prelude_exports.get("List").copied() else {
+            return false;
+        };
+        let file = self.entities.get_import_info(import_id).file;
+        matches!(self.exports[file].exports.get("list"), Some(ir::AnyID::Union(id)) if *id == eid)
+    }
+
+    // the empty variant and the variant of an item and a list, in either order
+    fn get_list_layout(id: LayoutID, variants: &[(String, Box<[LayoutID]>)]) -> Option<Layout> {
+        let [(_, a), (_, b)] = variants else {
+            return None;
+        };
+
+        let (cons_tag, cons_args) = match (&a[..], &b[..]) {
+            ([], [_, _]) => (1, b),
+            ([_, _], []) => (0, a),
+            _ => return None,
+        };
+
+        match cons_args[..] {
+            [item, tail] if tail == id => Some(Layout::List { item, cons_tag }),
+            _ => None,
+        }
+    }
+}
@@ -52,6 +52,17 @@ impl Checker<'_, '_> {
 
         let is_concrete = info.scheme.constraints.is_empty();
 
+        // closures would read a copy of the variable, taken when they are created
+        if let Some(depth) = self.mutable_variables.get(&id)
+            && *depth != self.function_depth()
+        {
+            self.reports.push(
+                Report::error(Header::CapturedMutable(name.clone()))
+                    .with_primary_label(Label::CapturedVariable(name.clone()), span.wrap(self.file))
+                    .with_secondary_label(Label::VariableDefinition(name.clone()), loc),
+            );
+        }
+
         let info = self.entities.get_variable_info(id);
         let (constraint_id, instantiated) =
             self.instantiate_scheme(info.scheme.clone(), Some(span.wrap(self.file)));
@@ -124,6 +135,53 @@ impl Checker<'_, '_> {
         )
     }
 
+    // items checked together share their class constraint
+    pub fn check_class_items_into_exprs(
+        &mut self,
+        id: ir::ClassID,
+        indices: &[usize],
+        span: Span,
+    ) -> Vec<ir::CheckedExpr> {
+        let info = self.entities.get_class_info(id);
+        let class_loc = info.loc;
+        let class_name = info.name.clone();
+
+        let schemes = indices
+            .iter()
+            .map(|&index| self.entities.get_class_item_info(id, index).scheme.clone())
+            .collect::<Vec<_>>();
+        let (constraint_id, item_types) =
+            self.instantiate_schemes_together(&schemes, Some(span.wrap(self.file)));
+
+        let mut items = Vec::with_capacity(indices.len());
+        for (&index, item_ty) in indices.iter().zip(item_types) {
+            let item_info = self.entities.get_class_item_info(id, index);
+            let item_loc = item_info.loc;
+            let item_name = item_info.name.clone();
+
+            let item_ty = self.clone_type_repr(item_ty);
+            self.set_type_span(item_ty, span);
+            self.add_type_provenance(
+                item_ty,
+                ir::TypeProvenance::ClassItemDefinition(
+                    item_loc,
+                    item_name,
+                    class_loc,
+                    class_name.clone(),
+                ),
+            );
+
+            items.push((
+                ir::Expr::ClassItem {
+                    item_id: index,
+                    constraint_id,
+                },
+                item_ty,
+            ));
+        }
+        items
+    }
+
     pub fn check_path_into_type(&mut self, q: Q, span: Span) -> ir::TypeID {
         match q {
             Q::Missing => self.create_fresh_type(Some(span)),
@@ -136,6 +194,10 @@ impl Checker<'_, '_> {
                 Some(ty) => ty,
                 None => self.create_fresh_type(Some(span)),
             },
+            Q::Synonym(synonym_id) => match self.create_synonym_type(synonym_id, None, span) {
+                Some(ty) => ty,
+                None => self.create_fresh_type(Some(span)),
+            },
             _ => {
                 self.reports.push(
                     Report::error(Header::InvalidType())
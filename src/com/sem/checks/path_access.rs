@@ -21,18 +21,38 @@ impl<'src> Checker<'src, '_> {
         }
     }
 
+    // types which are not records, unions or classes have nothing to access
+    fn report_no_items(&mut self, name: String, accessor: &ast::Expr) -> Q {
+        self.reports.push(
+            Report::error(Header::InvalidAccess())
+                .with_primary_label(Label::NoItems(name), accessor.span().wrap(self.file)),
+        );
+        Q::Missing
+    }
+
     pub fn check_access_path(&mut self, e: &ast::Access) -> Q {
         let q = self.check_path_or_expr(&e.accessed);
         match q {
             Q::Missing => Q::Missing,
-            Q::Expr(_) => todo!("access on expr"),
-            Q::Var(_) => todo!("access on var"),
-            Q::Type(_) => todo!("access on type"),
+            Q::Expr(expr) => self.check_field_access_path(expr, &e.accessor, e.span()),
+            Q::Var(id) => {
+                let expr = self.check_path_into_expr(Q::Var(id), e.accessed.span());
+                self.check_field_access_path(expr, &e.accessor, e.span())
+            }
+            Q::Type(ty) => {
+                let name = format!("type {}", self.get_type_string(ty));
+                self.report_no_items(name, &e.accessor)
+            }
             Q::Record(id) => self.check_record_access_path(id, &e.accessor),
             Q::Union(id) => self.check_union_access_path(id, &e.accessor),
             Q::Variant(_, _) => todo!("access on variant"),
+            Q::Synonym(id) => {
+                let name = format!("type synonym '{}'", self.entities.get_synonym_info(id).name);
+                self.report_no_items(name, &e.accessor)
+            }
             Q::Class(id) => self.check_class_access_path(id, &e.accessor),
             Q::ClassItem(_, _) => todo!("access on class item"),
+            Q::Instance(id) => self.check_instance_access_path(id, &e.accessor, e.span()),
             Q::Import(id) => self.check_import_access_path(id, &e.accessor, e.span()),
         }
     }
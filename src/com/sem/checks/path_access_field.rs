@@ -0,0 +1,37 @@
+use crate::com::{Checker, ast, ir, loc::Span};
+
+use ir::PathQuery as Q;
+
+impl Checker<'_, '_> {
+    // r.name is of the type of the field of whichever record type r turns out to be
+    pub fn check_field_access_path(
+        &mut self,
+        (accessed, accessed_type): ir::CheckedExpr,
+        accessor: &ast::Expr,
+        span: Span,
+    ) -> Q {
+        let Some((name, name_span)) = self.check_identifier_accessor(accessor) else {
+            return Q::Missing;
+        };
+
+        let loc = name_span.wrap(self.file);
+        let class_id = self.get_field_class(name, loc);
+        let field_type = self.create_fresh_type(Some(span));
+        let constraint_id = self.get_generic_unique_id();
+        self.require_class_constraint(ir::Constraint {
+            id: class_id,
+            loc,
+            class_args: Box::new([accessed_type]),
+            associated_args: Box::new([field_type]),
+            constraint_trace: ir::ConstraintTrace {
+                constraint_ids: vec![constraint_id],
+            },
+        });
+
+        let expr = ir::Expr::FieldAccess {
+            accessed: Box::new(accessed),
+            constraint_id,
+        };
+        Q::Expr((expr, field_type))
+    }
+}
@@ -0,0 +1,23 @@
+use crate::com::{Checker, ast, ir, loc::Span};
+
+use ir::PathQuery as Q;
+
+impl Checker<'_, '_> {
+    // sum.empty is the item of the class of the named instance, as in Monoid.empty with sum
+    pub fn check_instance_access_path(
+        &mut self,
+        id: ir::InstanceID,
+        accessor: &ast::Expr,
+        span: Span,
+    ) -> Q {
+        let class_id = self.entities.get_instance_info(id).scheme.constraint.id;
+        let outer_constraints = self.take_constraint_context();
+        let q = self.check_class_access_path(class_id, accessor);
+        let expr = self.check_path_into_expr(q, span);
+        let constraints = self.take_constraint_context();
+        self.restore_constraint_context(outer_constraints);
+
+        self.select_named_instance(id, constraints);
+        Q::Expr(expr)
+    }
+}
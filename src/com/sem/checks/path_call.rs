@@ -1,4 +1,8 @@
-use crate::com::{Checker, ast, ir, loc::Span};
+use crate::com::{
+    Checker, ast, ir,
+    loc::Span,
+    reporting::{Header, Label, Report},
+};
 use ir::PathQuery as Q;
 
 impl Checker<'_, '_> {
@@ -6,18 +10,47 @@ impl Checker<'_, '_> {
         let q = self.check_path_or_expr(&e.callee);
         match q {
             Q::Missing => Q::Missing,
-            Q::Expr(_) => todo!("call on expr"),
-            Q::Var(_) => todo!("call on var"),
-            Q::Variant(_, _) => todo!("call on variant"),
-            Q::Type(_) => todo!("call on type"),
+            // values are called as in any call expression, and instances are reported as not values
+            q @ (Q::Expr(_) | Q::Var(_) | Q::Variant(..) | Q::ClassItem(..) | Q::Instance(_)) => {
+                let callee = self.check_path_into_expr(q, e.callee.span());
+                Q::Expr(self.check_call_with_callee(callee, e))
+            }
+            Q::Type(ty) => self.check_type_application_path(ty, &e.args, e.span()),
             Q::Record(id) => self.check_record_call_path(id, &e.args, e.span()),
             Q::Union(id) => self.check_union_call_path(id, &e.args, e.span()),
+            Q::Synonym(id) => self.check_synonym_call_path(id, &e.args, e.span()),
             Q::Class(_) => todo!("call on class"),
-            Q::ClassItem(_, _) => todo!("call on class item"),
             Q::Import(_) => todo!("call on import"),
         }
     }
 
+    // only type variables may be applied, and stand for a partially applied constructor
+    fn check_type_application_path(
+        &mut self,
+        constructor: ir::TypeID,
+        args: &[ast::Expr],
+        span: Span,
+    ) -> Q {
+        let args = args
+            .iter()
+            .map(|ty| self.check_type(ty))
+            .collect::<Box<_>>();
+
+        let repr = self.get_type_repr(constructor);
+        if !matches!(self.types[repr.0].ty, ir::Type::Var) {
+            let ty_string = self.get_type_string(repr);
+            self.reports.push(
+                Report::error(Header::InvalidType()).with_primary_label(
+                    Label::NotATypeConstructor(ty_string),
+                    span.wrap(self.file),
+                ),
+            );
+            return Q::Missing;
+        }
+
+        Q::Type(self.create_type(ir::Type::Apply(constructor, args), Some(span)))
+    }
+
     fn check_union_call_path(
         &mut self,
         union_id: ir::UnionID,
@@ -34,6 +67,22 @@ impl Checker<'_, '_> {
         }
     }
 
+    fn check_synonym_call_path(
+        &mut self,
+        synonym_id: ir::SynonymID,
+        args: &[ast::Expr],
+        span: Span,
+    ) -> Q {
+        let args = args
+            .iter()
+            .map(|ty| self.check_type(ty))
+            .collect::<Box<_>>();
+        match self.create_synonym_type(synonym_id, Some(args), span) {
+            Some(ty) => Q::Type(ty),
+            None => Q::Missing,
+        }
+    }
+
     fn check_record_call_path(
         &mut self,
         record_id: ir::RecordID,
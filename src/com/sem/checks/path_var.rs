@@ -13,8 +13,9 @@ impl Checker<'_, '_> {
             ID::UserType(id) => Q::Type(self.entities.get_user_type_info(id).id),
             ID::Record(id) => Q::Record(id),
             ID::Union(id) => Q::Union(id),
+            ID::Synonym(id) => Q::Synonym(id),
             ID::Class(id) => Q::Class(id),
-            ID::Instance(_) => unreachable!(),
+            ID::Instance(id) => Q::Instance(id),
             ID::Import(id) => Q::Import(id),
             ID::Alias(id) => self.entities.get_alias_info(id).path.clone(),
         }
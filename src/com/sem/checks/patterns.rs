@@ -37,7 +37,35 @@ impl Checker<'_, '_> {
                 e.args.iter().map(|arg| self.check_pattern(arg)).collect(),
             ),
             E::Access(_) => P::Access(Box::new(e.clone())),
+            E::Binary(ast::Binary {
+                op: ast::BinOp::BitOr,
+                left,
+                right,
+                ..
+            }) => {
+                // flatten a | b | c into a single list of alternatives
+                let mut alternatives = Vec::new();
+                for side in [left, right] {
+                    match self.check_pattern(side) {
+                        P::Or(items) => alternatives.extend(items),
+                        pat => alternatives.push(pat),
+                    }
+                }
+                P::Or(alternatives.into())
+            }
+            E::Array(e) => self.check_array_pattern(e),
+            E::Annotated(e) => {
+                P::Annotated(Box::new(self.check_pattern(&e.expr)), e.colon, e.ty.clone())
+            }
             E::RecordValue(e) => {
+                if let Some((spread, base)) = &e.base {
+                    self.reports
+                        .push(Report::error(Header::InvalidPattern()).with_primary_label(
+                            Label::Empty,
+                            Span::combine(*spread, base.span()).wrap(self.file),
+                        ));
+                }
+
                 let mut fields = Vec::new();
                 for (name, expr) in &e.fields {
                     let field_name_span = match name {
@@ -67,6 +95,46 @@ impl Checker<'_, '_> {
         }
     }
 
+    fn check_array_pattern(&mut self, e: &ast::Array) -> ast::Pattern {
+        let mut prefix = Vec::new();
+        let mut rest: Option<(Span, Box<ast::Pattern>)> = None;
+        let mut suffix = Vec::new();
+        for item in &e.items {
+            let ast::Expr::Spread(spread) = item else {
+                let pat = self.check_pattern(item);
+                match rest {
+                    Some(_) => suffix.push(pat),
+                    None => prefix.push(pat),
+                }
+                continue;
+            };
+
+            if let Some((first, _)) = &rest {
+                self.reports.push(
+                    Report::error(Header::InvalidPattern())
+                        .with_primary_label(Label::ExtraArrayRest, spread.span().wrap(self.file))
+                        .with_secondary_label(Label::FirstArrayRest, first.wrap(self.file)),
+                );
+                continue;
+            }
+
+            // a lone '..' discards the rest of the array
+            let rest_pat = match &spread.rest {
+                Some(r) => self.check_pattern(r),
+                None => ast::Pattern::Discard(spread.spread),
+            };
+            rest = Some((spread.spread, Box::new(rest_pat)));
+        }
+
+        ast::Pattern::Array(
+            e.left_bracket,
+            e.right_bracket,
+            prefix.into(),
+            rest,
+            suffix.into(),
+        )
+    }
+
     pub fn declare_pattern(&mut self, p: &ast::Pattern, public: bool) -> (ir::Pattern, ir::TypeID) {
         use ast::Pattern as P;
         use ir::Pattern as I;
@@ -117,9 +185,138 @@ impl Checker<'_, '_> {
             P::Call(_, _, e, args) => self.declare_call_pattern(e, args, span, public),
             P::Access(e) => self.declare_access_pattern(e, span),
             P::Record(_, _, fields) => self.declare_record_pattern(fields, span, public),
+            P::Or(alternatives) => self.declare_or_pattern(alternatives, span, public),
+            P::Array(_, _, prefix, rest, suffix) => {
+                self.declare_array_pattern(prefix, rest.as_ref(), suffix, span, public)
+            }
+            P::Annotated(pattern, _, ty) => {
+                // type names are resolved before the pattern introduces any binding
+                let annotation_type = self.check_annotation_type(ty);
+                let (pattern, pattern_type) = self.declare_pattern(pattern, public);
+                let provenances = &[Provenance::TypeAnnotation(span.wrap(self.file))];
+                self.unify(pattern_type, annotation_type, provenances);
+                (pattern, annotation_type)
+            }
         }
     }
 
+    fn declare_array_pattern(
+        &mut self,
+        prefix: &[ast::Pattern],
+        rest: Option<&(Span, Box<ast::Pattern>)>,
+        suffix: &[ast::Pattern],
+        span: Span,
+        public: bool,
+    ) -> (ir::Pattern, ir::TypeID) {
+        let item_type = self.create_fresh_type(None);
+        let array_type = self.create_type(ir::Type::Array(item_type), Some(span));
+
+        let mut declare_items = |items: &[ast::Pattern]| -> Box<[ir::Pattern]> {
+            items
+                .iter()
+                .map(|item| {
+                    let (pat, ty) = self.declare_pattern(item, public);
+                    self.unify(ty, item_type, &[]);
+                    pat
+                })
+                .collect()
+        };
+        let prefix = declare_items(prefix);
+        let suffix = declare_items(suffix);
+
+        let rest = rest.map(|(_, rest)| {
+            let (pat, ty) = self.declare_pattern(rest, public);
+            self.unify(ty, array_type, &[]);
+            pat
+        });
+
+        // [..rest] is just the rest pattern
+        if let Some(rest) = &rest
+            && prefix.is_empty()
+            && suffix.is_empty()
+        {
+            return (rest.clone(), array_type);
+        }
+
+        (
+            ir::Pattern::Array(prefix, rest.map(Box::new), suffix),
+            array_type,
+        )
+    }
+
+    fn declare_or_pattern(
+        &mut self,
+        alternatives: &[ast::Pattern],
+        span: Span,
+        public: bool,
+    ) -> (ir::Pattern, ir::TypeID) {
+        let ty = self.create_fresh_type(Some(span));
+
+        let mut alts = Vec::new();
+        for alt in alternatives {
+            let (pat, alt_ty) = self.declare_pattern(alt, public);
+            self.unify(alt_ty, ty, &[]);
+
+            let bindings = pat
+                .get_binding_ids()
+                .into_iter()
+                .map(|id| (self.entities.get_variable_info(id).name.clone(), id))
+                .collect::<Vec<_>>();
+            alts.push((pat, alt.span(), bindings));
+        }
+
+        // the variables of the last alternative shadow the others
+        // so every other alternative is made to bind these instead
+        let Some((last_pat, last_span, last_bindings)) = alts.pop() else {
+            return self.declare_missing_pattern();
+        };
+        let find = |bindings: &[(String, ir::VariableID)], name: &str| {
+            bindings.iter().find(|(n, _)| n == name).map(|&(_, id)| id)
+        };
+
+        let mut patterns = Vec::with_capacity(alts.len() + 1);
+        for (mut pat, alt_span, bindings) in alts {
+            let mut renaming = HashMap::new();
+            for (name, id) in &bindings {
+                let Some(last_id) = find(&last_bindings, name) else {
+                    self.report_missing_alternative_binding(name, *id, last_span);
+                    continue;
+                };
+                let alt_ty = self.entities.get_variable_info(*id).scheme.uninstantiated;
+                let last_ty = self
+                    .entities
+                    .get_variable_info(last_id)
+                    .scheme
+                    .uninstantiated;
+                self.unify(alt_ty, last_ty, &[]);
+                renaming.insert(*id, last_id);
+            }
+            for (name, id) in &last_bindings {
+                if find(&bindings, name).is_none() {
+                    self.report_missing_alternative_binding(name, *id, alt_span);
+                }
+            }
+
+            pat.replace_bindings(&renaming);
+            patterns.push(pat);
+        }
+        patterns.push(last_pat);
+
+        (ir::Pattern::Or(patterns.into()), ty)
+    }
+
+    fn report_missing_alternative_binding(&mut self, name: &str, id: ir::VariableID, span: Span) {
+        let loc = self.entities.get_variable_info(id).loc;
+        self.reports.push(
+            Report::error(Header::InconsistentAlternatives(name.to_string()))
+                .with_primary_label(
+                    Label::MissingAlternativeBinding(name.to_string()),
+                    span.wrap(self.file),
+                )
+                .with_secondary_label(Label::VariableDefinition(name.to_string()), loc),
+        );
+    }
+
     fn declare_call_pattern(
         &mut self,
         e: &ast::Expr,
@@ -188,6 +385,15 @@ impl Checker<'_, '_> {
 
                 let ty = self.clone_type_repr(union_type);
                 self.set_type_span(ty, span);
+
+                // newtypes are represented by their only argument
+                let info = self.entities.get_union_info(id);
+                if info.is_newtype
+                    && let [arg] = &args[..]
+                {
+                    return (arg.clone(), ty);
+                }
+
                 (I::Variant(id, tag, Some(args.into())), ty)
             }
             Q::Missing => self.declare_missing_pattern(),
@@ -6,12 +6,18 @@ use crate::com::{
 
 impl<'src> Checker<'src, '_> {
     pub fn check_signature(&mut self, mut e: &ast::Expr, require_name: bool) -> ast::Signature {
+        use ast::Expr as E;
         use ast::Signature as S;
-        let mut signature = S::Empty;
+        let mut signature = match e {
+            E::Annotated(annotated) => {
+                e = &annotated.expr;
+                S::Returns(annotated.ty.clone())
+            }
+            _ => S::Empty,
+        };
         loop {
-            use ast::Expr as E;
             match e {
-                E::Var(lex) if !require_name || !matches!(signature, S::Empty) => {
+                E::Var(lex) if !require_name || !matches!(signature, S::Empty | S::Returns(_)) => {
                     return S::Name(lex.span, Box::new(signature));
                 }
                 E::Tuple(tuple) => {
@@ -129,6 +135,10 @@ impl<'src> Checker<'src, '_> {
                 let ret_type = self.create_fresh_type(None);
                 (I::Done, ret_type, ret_type, None)
             }
+            S::Returns(ty) => {
+                let ret_type = self.check_annotation_type(ty);
+                (I::Done, ret_type, ret_type, None)
+            }
         }
     }
 
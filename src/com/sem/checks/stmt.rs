@@ -14,6 +14,8 @@ impl Checker<'_, '_> {
                 E::ImportFrom(e) => self.check_import_from(e, true),
                 E::Record(e) => self.check_record(e, true),
                 E::Union(e) => self.check_union(e, true),
+                E::Synonym(e) => self.check_synonym(e, true),
+                E::Newtype(e) => self.check_newtype(e, true),
                 E::Class(e) => self.check_class(e, true),
                 E::Have(e) => self.check_have(e, true),
                 _ => {
@@ -33,6 +35,8 @@ impl Checker<'_, '_> {
             E::ImportFrom(e) => self.check_import_from(e, false),
             E::Record(e) => self.check_record(e, false),
             E::Union(e) => self.check_union(e, false),
+            E::Synonym(e) => self.check_synonym(e, false),
+            E::Newtype(e) => self.check_newtype(e, false),
             E::Class(e) => self.check_class(e, false),
             E::Have(e) => self.check_have(e, false),
             _ => {
@@ -53,6 +53,11 @@ impl Checker<'_, '_> {
                 let (info, variant_info) = self.entities.get_union_variant_info(*id, *tag);
                 format!("({}) {}.{}", "variant".bold(), info.name, variant_info.name,)
             }
+            Q::Synonym(id) => format!(
+                "({}) {}",
+                "type".bold(),
+                self.entities.get_synonym_info(*id).name
+            ),
             Q::Class(id) => format!(
                 "({}) {}",
                 "class".bold(),
@@ -68,6 +73,11 @@ impl Checker<'_, '_> {
                     item_info.name
                 )
             }
+            Q::Instance(id) => format!(
+                "({}) {}",
+                "instance".bold(),
+                self.get_instance_scheme_string(&self.entities.get_instance_info(*id).scheme)
+            ),
             Q::Import(id) => format!(
                 "({}) {}",
                 "import".bold(),
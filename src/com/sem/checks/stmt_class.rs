@@ -2,12 +2,12 @@ use crate::com::{
     Checker, ast, ir,
     loc::Span,
     reporting::{Header, Label, Note, Report},
-    sem::checker::checker_print,
+    sem::checker::{ImplicitTypeVar, checker_print},
 };
 use colored::Colorize;
 use either::Either;
 
-impl Checker<'_, '_> {
+impl<'src> Checker<'src, '_> {
     pub fn check_pattern_or_type_signature(
         &mut self,
         e: &ast::Expr,
@@ -19,6 +19,81 @@ impl Checker<'_, '_> {
         }
     }
 
+    fn check_superclass(
+        &mut self,
+        e: &ast::Expr,
+        class_name: &str,
+        arg_names: &[Option<&str>],
+        within_label: &Label,
+        span: Span,
+    ) -> Option<ir::SuperclassInfo> {
+        use ast::Expr as E;
+        let E::Call(call) = e else {
+            self.reports.push(
+                Report::error(Header::InvalidSuperclass(class_name.to_string()))
+                    .with_primary_label(Label::Empty, e.span().wrap(self.file))
+                    .with_secondary_label(within_label.clone(), span.wrap(self.file))
+                    .with_note(Note::SuperclassSyntax),
+            );
+            return None;
+        };
+
+        use ir::PathQuery as Q;
+        let id = match self.check_path_or_type(&call.callee) {
+            Q::Class(id) => id,
+            Q::Missing => return None,
+            _ => {
+                self.reports.push(
+                    Report::error(Header::InvalidClass())
+                        .with_primary_label(Label::NotAClass, call.callee.span().wrap(self.file))
+                        .with_secondary_label(within_label.clone(), span.wrap(self.file)),
+                );
+                return None;
+            }
+        };
+
+        let info = self.entities.get_class_info(id);
+        if call.args.len() != info.arity.0 {
+            self.reports.push(
+                Report::error(Header::InvalidSuperclass(class_name.to_string()))
+                    .with_primary_label(
+                        Label::ClassArgCount(info.name.clone(), info.arity.0),
+                        e.span().wrap(self.file),
+                    )
+                    .with_secondary_label(Label::ClassDefinition(info.name.clone()), info.loc),
+            );
+            return None;
+        }
+
+        // superclasses may only be given the arguments of the class being defined
+        let mut args = Vec::new();
+        for arg in &call.args {
+            let index = match arg {
+                E::Var(v) => {
+                    let name = v.span.lexeme(self.source);
+                    arg_names.iter().position(|n| *n == Some(name))
+                }
+                _ => None,
+            };
+
+            let Some(index) = index else {
+                self.reports.push(
+                    Report::error(Header::InvalidSuperclass(class_name.to_string()))
+                        .with_primary_label(Label::Empty, arg.span().wrap(self.file))
+                        .with_secondary_label(within_label.clone(), span.wrap(self.file))
+                        .with_note(Note::SuperclassSyntax),
+                );
+                return None;
+            };
+            args.push(index);
+        }
+
+        Some(ir::SuperclassInfo {
+            id,
+            args: args.into(),
+        })
+    }
+
     pub fn check_class(&mut self, e: &ast::Class, public: bool) -> ir::Stmt {
         let span = e.span();
         let Some((class_name_span, args)) = Self::extract_simple_signature_with_args(&e.signature)
@@ -46,9 +121,14 @@ impl Checker<'_, '_> {
         }
 
         let mut arg_ids = Vec::new();
+        let mut arg_names = Vec::new();
         for arg in args {
             let (arg_id, _) = self.declare_type_argument(arg);
             arg_ids.push(arg_id);
+            arg_names.push(match arg {
+                ast::Expr::Var(arg) => Some(arg.span.lexeme(self.source)),
+                _ => None,
+            });
         }
 
         let mut associated_arg_ids = Vec::new();
@@ -61,88 +141,46 @@ impl Checker<'_, '_> {
 
         let arity = (arg_ids.len(), associated_arg_ids.len());
 
+        let mut superclasses = Vec::new();
+        for superclass in e.superclasses.iter().flatten() {
+            if let Some(info) =
+                self.check_superclass(superclass, class_name, &arg_names, &within_label, span)
+            {
+                superclasses.push(info);
+            }
+        }
+
         let class_id = self.entities.create_class(ir::ClassInfo {
             name: class_name.to_string(),
             loc: span.wrap(self.file),
             items: Default::default(),
             arity,
+            superclasses: superclasses.into(),
+            field: None,
         });
         self.set_entity_public(class_id.wrap(), public);
 
         let mut items = Vec::new();
+        let mut item_names = Vec::new();
         let mut constraint = ir::Constraint {
             id: class_id,
             loc: span.wrap(self.file),
-            class_args: arg_ids.into(),
+            class_args: arg_ids.clone().into(),
             associated_args: associated_arg_ids.into(),
             constraint_trace: ir::ConstraintTrace::default(),
         };
 
-        use ast::ClassItem as K;
-        use ast::Pattern as P;
         for (kind, lhs, rhs) in &e.items {
             let item_span = Span::combine(lhs.span(), rhs.span());
-            let (item_name, item_name_span, item_type) = match self
-                .check_pattern_or_type_signature(lhs)
-            {
-                Either::Left(pattern) => {
-                    let P::Var(item_name_span) = pattern else {
-                        self.reports.push(
-                            Report::error(Header::InvalidPattern())
-                                .with_primary_label(Label::Empty, lhs.span().wrap(self.file))
-                                .with_secondary_label(within_label.clone(), span.wrap(self.file))
-                                .with_note(Note::ClassConstantItemSyntax),
-                        );
-                        continue;
-                    };
-
-                    if !matches!(kind, K::Constant | K::Unknown) {
-                        self.reports.push(
-                            Report::error(Header::InvalidTypeAnnotation())
-                                .with_primary_label(
-                                    Label::IncorrectClassConstantItemSyntax,
-                                    item_span.wrap(self.file),
-                                )
-                                .with_secondary_label(within_label.clone(), span.wrap(self.file))
-                                .with_note(Note::ClassConstantItemSyntax),
-                        );
-                    }
-
-                    let item_name = item_name_span.lexeme(self.source);
-                    let item_type = self.check_type(rhs);
-
-                    (item_name, item_name_span, item_type)
-                }
-                Either::Right((signature, name_span)) => {
-                    let Some(item_name_span) = name_span else {
-                        self.reports.push(
-                            Report::error(Header::InvalidSignature())
-                                .with_primary_label(Label::Empty, lhs.span().wrap(self.file))
-                                .with_secondary_label(within_label.clone(), span.wrap(self.file))
-                                .with_note(Note::ClassFunctionItemSyntax),
-                        );
-                        continue;
-                    };
-
-                    if !matches!(kind, K::Function | K::Unknown) {
-                        self.reports.push(
-                            Report::error(Header::InvalidTypeAnnotation())
-                                .with_primary_label(
-                                    Label::IncorrectClassFunctionItemSyntax,
-                                    item_span.wrap(self.file),
-                                )
-                                .with_secondary_label(within_label.clone(), span.wrap(self.file))
-                                .with_note(Note::ClassFunctionItemSyntax),
-                        );
-                    }
-
-                    let item_name = item_name_span.lexeme(self.source);
-                    let (item_type, sig_ret_type) = self.declare_type_signature(&signature);
-                    let ret_type = self.check_type(rhs);
-                    self.unify(ret_type, sig_ret_type, &[]);
-
-                    (item_name, item_name_span, item_type)
-                }
+
+            // names other than the class arguments are type variables of the item alone
+            let previous_vars = self.open_type_annotations();
+            let item =
+                self.check_class_item_signature(kind, lhs, rhs, item_span, &within_label, span);
+            self.close_type_annotations(previous_vars);
+
+            let Some((item_name, item_name_span, item_type)) = item else {
+                continue;
             };
 
             let mut scheme = self.generalize_type(item_type);
@@ -154,12 +192,16 @@ impl Checker<'_, '_> {
                 name: item_name.to_string(),
                 loc: item_span.wrap(self.file),
                 scheme,
+                default: None,
             });
+            item_names.push(item_name);
         }
 
         let info = self.entities.get_class_info_mut(class_id);
         info.items = items.into();
 
+        let stmts = self.check_class_defaults(e, class_id, &item_names, &arg_ids, &arg_names);
+
         self.close_scope();
         self.scope.insert(class_name, class_id.wrap());
 
@@ -183,6 +225,162 @@ impl Checker<'_, '_> {
         }
         checker_print!(self, "{}", "end".bold());
 
-        ir::Stmt::Nothing
+        ir::Stmt::Have {
+            stmts: stmts.into(),
+        }
+    }
+
+    fn check_class_item_signature(
+        &mut self,
+        kind: &ast::ClassItem,
+        lhs: &ast::Expr,
+        rhs: &ast::Expr,
+        item_span: Span,
+        within_label: &Label,
+        span: Span,
+    ) -> Option<(&'src str, Span, ir::TypeID)> {
+        use ast::ClassItem as K;
+        use ast::Pattern as P;
+        match self.check_pattern_or_type_signature(lhs) {
+            Either::Left(pattern) => {
+                let P::Var(item_name_span) = pattern else {
+                    self.reports.push(
+                        Report::error(Header::InvalidPattern())
+                            .with_primary_label(Label::Empty, lhs.span().wrap(self.file))
+                            .with_secondary_label(within_label.clone(), span.wrap(self.file))
+                            .with_note(Note::ClassConstantItemSyntax),
+                    );
+                    return None;
+                };
+
+                if !matches!(kind, K::Constant | K::Unknown) {
+                    self.reports.push(
+                        Report::error(Header::InvalidTypeAnnotation())
+                            .with_primary_label(
+                                Label::IncorrectClassConstantItemSyntax,
+                                item_span.wrap(self.file),
+                            )
+                            .with_secondary_label(within_label.clone(), span.wrap(self.file))
+                            .with_note(Note::ClassConstantItemSyntax),
+                    );
+                }
+
+                let item_name = item_name_span.lexeme(self.source);
+                let item_type = self.check_type(rhs);
+
+                Some((item_name, item_name_span, item_type))
+            }
+            Either::Right((signature, name_span)) => {
+                let Some(item_name_span) = name_span else {
+                    self.reports.push(
+                        Report::error(Header::InvalidSignature())
+                            .with_primary_label(Label::Empty, lhs.span().wrap(self.file))
+                            .with_secondary_label(within_label.clone(), span.wrap(self.file))
+                            .with_note(Note::ClassFunctionItemSyntax),
+                    );
+                    return None;
+                };
+
+                if !matches!(kind, K::Function | K::Unknown) {
+                    self.reports.push(
+                        Report::error(Header::InvalidTypeAnnotation())
+                            .with_primary_label(
+                                Label::IncorrectClassFunctionItemSyntax,
+                                item_span.wrap(self.file),
+                            )
+                            .with_secondary_label(within_label.clone(), span.wrap(self.file))
+                            .with_note(Note::ClassFunctionItemSyntax),
+                    );
+                }
+
+                let item_name = item_name_span.lexeme(self.source);
+                let (item_type, sig_ret_type) = self.declare_type_signature(&signature);
+                let ret_type = self.check_type(rhs);
+                self.unify(ret_type, sig_ret_type, &[]);
+
+                Some((item_name, item_name_span, item_type))
+            }
+        }
+    }
+
+    // default items are checked within the class, where its items are available by name
+    fn check_class_defaults(
+        &mut self,
+        e: &ast::Class,
+        class_id: ir::ClassID,
+        item_names: &[&'src str],
+        arg_ids: &[ir::TypeID],
+        arg_names: &[Option<&'src str>],
+    ) -> Vec<ir::Stmt> {
+        let class_name = self.entities.get_class_info(class_id).name.clone();
+        for (index, item_name) in item_names.iter().enumerate() {
+            let alias_id = self.entities.create_alias(ir::AliasInfo {
+                name: item_name.to_string(),
+                path: ir::PathQuery::ClassItem(class_id, index),
+            });
+            self.scope.insert(item_name, alias_id.wrap());
+        }
+
+        let mut stmts = Vec::new();
+        for default in &e.defaults {
+            let (stmt, bindings) = self.check_let_bindings(default, false);
+            stmts.push(stmt);
+
+            for binding in bindings {
+                let binding_info = self.entities.get_variable_info(binding);
+                let name = binding_info.name.clone();
+                let loc = binding_info.loc;
+
+                let Some(index) = item_names.iter().position(|item| **item == name) else {
+                    self.reports.push(
+                        Report::error(Header::UnknownClassItem(name, class_name.clone()))
+                            .with_primary_label(Label::Empty, loc),
+                    );
+                    continue;
+                };
+
+                self.check_class_default(class_id, index, binding, arg_ids, arg_names);
+                self.entities.get_class_info_mut(class_id).items[index].default = Some(binding);
+            }
+        }
+
+        stmts
+    }
+
+    // a default must be at least as general as the item it implements
+    fn check_class_default(
+        &mut self,
+        class_id: ir::ClassID,
+        index: usize,
+        binding: ir::VariableID,
+        arg_ids: &[ir::TypeID],
+        arg_names: &[Option<&'src str>],
+    ) {
+        let item_scheme = self
+            .entities
+            .get_class_item_info(class_id, index)
+            .scheme
+            .clone();
+        let binding_info = self.entities.get_variable_info(binding);
+        let default_scheme = binding_info.scheme.clone();
+        let span = binding_info.loc.span;
+
+        let sub = self.build_type_substitution(item_scheme.forall.clone());
+        let expected = self.apply_type_substitution(item_scheme.uninstantiated, &sub);
+        let (found, _) = self.instantiate_scheme_keep_constraints(default_scheme);
+        self.unify(found, expected, &[]);
+
+        let mut vars = Vec::new();
+        for (arg_id, arg_name) in arg_ids.iter().zip(arg_names) {
+            let repr = self.get_type_repr(*arg_id);
+            if let (Some(id), Some(name)) = (sub.get(&repr), arg_name) {
+                vars.push(ImplicitTypeVar {
+                    name,
+                    id: *id,
+                    span,
+                });
+            }
+        }
+        self.check_implicit_type_vars(&vars);
     }
 }
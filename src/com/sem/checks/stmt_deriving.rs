@@ -0,0 +1,608 @@
+use std::collections::HashMap;
+
+use crate::com::{
+    Checker, ast, ir,
+    loc::Span,
+    reporting::{Header, Label, Note, Report},
+};
+
+// the classes of the standard library whose instances can be derived
+#[derive(Debug, Clone, Copy)]
+enum Derivable {
+    Display,
+    Eq,
+    Ord,
+    Default,
+}
+
+// the fields or variants of a derived type, instantiated with fresh type arguments
+enum Components {
+    Fields(Vec<(String, ir::TypeID)>),
+    Variants(ir::UnionID, Vec<(String, Option<Vec<ir::TypeID>>)>),
+}
+
+impl Checker<'_, '_> {
+    // the instances are derived structurally, constrained on the instances of the components
+    pub fn check_deriving(
+        &mut self,
+        deriving: Option<&[ast::Expr]>,
+        derived: ir::AnyID,
+        public: bool,
+    ) -> ir::Stmt {
+        let Some(deriving) = deriving else {
+            return ir::Stmt::Nothing;
+        };
+
+        let stmts = deriving
+            .iter()
+            .filter_map(|e| self.check_derived_instance(e, derived, public))
+            .collect();
+        ir::Stmt::Have { stmts }
+    }
+
+    fn check_derived_instance(
+        &mut self,
+        e: &ast::Expr,
+        derived: ir::AnyID,
+        public: bool,
+    ) -> Option<ir::Stmt> {
+        let span = e.span();
+
+        use ir::PathQuery as Q;
+        let class_id = match self.check_path_or_type(e) {
+            Q::Class(id) => id,
+            Q::Missing => return None,
+            _ => {
+                self.reports.push(
+                    Report::error(Header::InvalidClass())
+                        .with_primary_label(Label::NotAClass, span.wrap(self.file)),
+                );
+                return None;
+            }
+        };
+
+        let class_name = self.entities.get_class_info(class_id).name.clone();
+        let Some(derivable) = self.get_derivable_class(class_id) else {
+            self.reports.push(
+                Report::error(Header::NotDerivable(class_name))
+                    .with_primary_label(Label::Empty, span.wrap(self.file))
+                    .with_note(Note::DerivableClasses),
+            );
+            return None;
+        };
+
+        // a default value is built from the first variant
+        if let (Derivable::Default, ir::AnyID::Union(union_id)) = (derivable, derived) {
+            let info = self.entities.get_union_info(union_id);
+            if info.variants.is_empty() {
+                let union_name = info.name.clone();
+                let union_loc = info.loc;
+                self.reports.push(
+                    Report::error(Header::NotDerivable(class_name))
+                        .with_primary_label(
+                            Label::UnionNoVariants(union_name.clone()),
+                            span.wrap(self.file),
+                        )
+                        .with_secondary_label(Label::UnionDefinition(union_name), union_loc),
+                );
+                return None;
+            }
+        }
+
+        let (stmt, binding) = self.derive_instance_item(derivable, class_id, derived, span);
+        let item_name = self.entities.get_class_item_info(class_id, 0).name.clone();
+        let registered = HashMap::from([(item_name, binding)]);
+
+        let instance_id = self.check_instance(class_id, &registered, span, span)?;
+        if !self.check_instance_coherence(instance_id, span) {
+            return None;
+        }
+        self.scope.infos_mut().instances.insert(instance_id);
+        self.set_entity_public(instance_id.wrap(), public);
+
+        Some(stmt)
+    }
+
+    fn get_derivable_class(&mut self, class_id: ir::ClassID) -> Option<Derivable> {
+        let prelude_file = self.deps.info.prelude_file?;
+        let id = Some(class_id.wrap());
+
+        let ops_exports = self.get_marin_std_ops_exports(prelude_file);
+        if id == ops_exports.get("Eq").copied() {
+            return Some(Derivable::Eq);
+        }
+        if id == ops_exports.get("Ord").copied() {
+            return Some(Derivable::Ord);
+        }
+
+        let display_exports = self.get_marin_std_module_exports(prelude_file, "Display");
+        if id == display_exports.get("Display").copied() {
+            return Some(Derivable::Display);
+        }
+
+        // the prelude imports the Default class on its own
+        let prelude_exports = &self.exports[prelude_file].exports;
+        if let Some(ir::AnyID::Alias(alias_id)) = prelude_exports.get("Default").copied()
+            && let ir::PathQuery::Class(default_id) = self.entities.get_alias_info(alias_id).path
+            && default_id == class_id
+        {
+            return Some(Derivable::Default);
+        }
+
+        None
+    }
+
+    // builds the binding of the only item of the derived class
+    fn derive_instance_item(
+        &mut self,
+        derivable: Derivable,
+        class_id: ir::ClassID,
+        derived: ir::AnyID,
+        span: Span,
+    ) -> (ir::Stmt, ir::VariableID) {
+        let item_name = self.entities.get_class_item_info(class_id, 0).name.clone();
+
+        self.open_scope(true);
+
+        let (derived_ty, type_name, components) = self.instantiate_derived_type(derived);
+        let fun_name = format!("{type_name}.{item_name}");
+        let bool_ty = self.native_types.bool;
+        let string_ty = self.native_types.string;
+
+        let (value, value_ty) = match derivable {
+            Derivable::Eq => {
+                let args = self.create_derived_args::<2>();
+                let expr = self.derive_eq(class_id, args, &components, span);
+                let ty = self.create_type(
+                    ir::Type::Lambda(Box::new([derived_ty, derived_ty]), bool_ty),
+                    Some(span),
+                );
+                (Self::build_derived_fun(fun_name, &args, expr), ty)
+            }
+            Derivable::Ord => {
+                let args = self.create_derived_args::<2>();
+                let expr = self.derive_ord(class_id, args, &components, span);
+                let ty = self.create_type(
+                    ir::Type::Lambda(Box::new([derived_ty, derived_ty]), bool_ty),
+                    Some(span),
+                );
+                (Self::build_derived_fun(fun_name, &args, expr), ty)
+            }
+            Derivable::Display => {
+                let [arg] = self.create_derived_args::<1>();
+                let expr = self.derive_display(class_id, arg, &components, span);
+                let ty = self.create_type(
+                    ir::Type::Lambda(Box::new([derived_ty]), string_ty),
+                    Some(span),
+                );
+                (Self::build_derived_fun(fun_name, &[arg], expr), ty)
+            }
+            Derivable::Default => {
+                let expr = self.derive_default(class_id, &components, span);
+                (expr, derived_ty)
+            }
+        };
+
+        // constraints on the derived type itself are solved by the instance being derived
+        let (self_constraints, constraints) =
+            self.take_constraint_context()
+                .into_iter()
+                .partition(|constraint: &ir::Constraint| {
+                    constraint.id == class_id
+                        && self.is_same_type(constraint.class_args[0], derived_ty)
+                });
+        self.restore_constraint_context(constraints);
+
+        let (solutions, mut relevant_constraints) = self.solve_constraints();
+        relevant_constraints.extend(self_constraints);
+        let is_concrete = relevant_constraints.is_empty();
+
+        self.close_scope();
+
+        let mut scheme = self.generalize_type(value_ty);
+        for constraint in relevant_constraints {
+            self.add_class_constraint(&mut scheme, constraint);
+        }
+
+        let binding = self.entities.create_variable(ir::VariableInfo {
+            name: item_name,
+            scheme,
+            loc: span.wrap(self.file),
+        });
+
+        (
+            ir::Stmt::Let {
+                lhs: ir::Pattern::Binding(binding),
+                rhs: value,
+                is_concrete,
+                solutions,
+            },
+            binding,
+        )
+    }
+
+    fn instantiate_derived_type(&mut self, derived: ir::AnyID) -> (ir::TypeID, String, Components) {
+        match derived {
+            ir::AnyID::Record(record_id) => {
+                let info = self.entities.get_record_info(record_id);
+                let name = info.name.clone();
+                let scheme = info.scheme.clone();
+                let fields = info
+                    .fields
+                    .iter()
+                    .map(|field| (field.name.clone(), field.ty))
+                    .collect::<Vec<_>>();
+
+                let sub = self.build_type_substitution(scheme.forall);
+                let ty = self.apply_type_substitution(scheme.uninstantiated, &sub);
+                let fields = fields
+                    .into_iter()
+                    .map(|(name, ty)| (name, self.apply_type_substitution(ty, &sub)))
+                    .collect();
+
+                (ty, name, Components::Fields(fields))
+            }
+            ir::AnyID::Union(union_id) => {
+                let info = self.entities.get_union_info(union_id);
+                let name = info.name.clone();
+                let scheme = info.scheme.clone();
+                let variants = info
+                    .variants
+                    .iter()
+                    .map(|variant| (variant.name.clone(), variant.type_args.clone()))
+                    .collect::<Vec<_>>();
+
+                let sub = self.build_type_substitution(scheme.forall);
+                let ty = self.apply_type_substitution(scheme.uninstantiated, &sub);
+                let variants = variants
+                    .into_iter()
+                    .map(|(name, args)| {
+                        let args = args.map(|args| {
+                            args.iter()
+                                .map(|arg| self.apply_type_substitution(*arg, &sub))
+                                .collect()
+                        });
+                        (name, args)
+                    })
+                    .collect();
+
+                (ty, name, Components::Variants(union_id, variants))
+            }
+            _ => unreachable!("only records and unions can be derived"),
+        }
+    }
+
+    // x == y if all their components are equal
+    fn derive_eq(
+        &mut self,
+        class_id: ir::ClassID,
+        [left, right]: [ir::VariableID; 2],
+        components: &Components,
+        span: Span,
+    ) -> ir::Expr {
+        let bool_ty = self.native_types.bool;
+        match components {
+            Components::Fields(fields) => {
+                let eqs = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (_, ty))| {
+                        let args = vec![Self::access(left, index), Self::access(right, index)];
+                        self.call_derived_item(class_id, *ty, args, bool_ty, span)
+                    })
+                    .collect();
+                Self::conjunction(eqs)
+            }
+            Components::Variants(union_id, variants) => {
+                let scrutinee_var = self.entities.create_dummy_variable();
+                let mut decision = ir::Decision::Failure;
+                for (tag, (_, args)) in variants.iter().enumerate().rev() {
+                    let (left_pattern, xs) = self.bind_variant(*union_id, tag, args);
+                    let (right_pattern, ys) = self.bind_variant(*union_id, tag, args);
+                    let eqs = args
+                        .iter()
+                        .flatten()
+                        .zip(xs.into_iter().zip(ys))
+                        .map(|(ty, (x, y))| {
+                            let args = vec![ir::Expr::Var { id: x }, ir::Expr::Var { id: y }];
+                            self.call_derived_item(class_id, *ty, args, bool_ty, span)
+                        })
+                        .collect();
+
+                    decision = ir::Decision::Test {
+                        tested_var: scrutinee_var,
+                        pattern: Box::new(left_pattern),
+                        success: Box::new(ir::Decision::Test {
+                            tested_var: right,
+                            pattern: Box::new(right_pattern),
+                            success: Box::new(Self::success(Self::conjunction(eqs))),
+                            failure: Box::new(Self::success(ir::Expr::Bool { val: false })),
+                        }),
+                        failure: Box::new(decision),
+                    };
+                }
+                Self::build_derived_match(scrutinee_var, left, decision)
+            }
+        }
+    }
+
+    // x < y if x's variant comes first, or the components of the same variant compare less
+    fn derive_ord(
+        &mut self,
+        class_id: ir::ClassID,
+        [left, right]: [ir::VariableID; 2],
+        components: &Components,
+        span: Span,
+    ) -> ir::Expr {
+        match components {
+            Components::Fields(fields) => {
+                let compared = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (_, ty))| {
+                        (*ty, Self::access(left, index), Self::access(right, index))
+                    })
+                    .collect();
+                self.derive_lexicographic_lt(class_id, compared, span)
+            }
+            Components::Variants(union_id, variants) => {
+                let scrutinee_var = self.entities.create_dummy_variable();
+                let mut decision = ir::Decision::Failure;
+                for (tag, (_, args)) in variants.iter().enumerate().rev() {
+                    let (left_pattern, xs) = self.bind_variant(*union_id, tag, args);
+                    let (right_pattern, ys) = self.bind_variant(*union_id, tag, args);
+                    let compared = args
+                        .iter()
+                        .flatten()
+                        .zip(xs.into_iter().zip(ys))
+                        .map(|(ty, (x, y))| (*ty, ir::Expr::Var { id: x }, ir::Expr::Var { id: y }))
+                        .collect();
+                    let lt = self.derive_lexicographic_lt(class_id, compared, span);
+
+                    // otherwise, x is less if y is any of the following variants
+                    let mut later = Self::success(ir::Expr::Bool { val: false });
+                    for (later_tag, (_, later_args)) in
+                        variants.iter().enumerate().skip(tag + 1).rev()
+                    {
+                        let discards = later_args
+                            .as_ref()
+                            .map(|args| args.iter().map(|_| ir::Pattern::Discard).collect());
+                        later = ir::Decision::Test {
+                            tested_var: right,
+                            pattern: Box::new(ir::Pattern::Variant(*union_id, later_tag, discards)),
+                            success: Box::new(Self::success(ir::Expr::Bool { val: true })),
+                            failure: Box::new(later),
+                        };
+                    }
+
+                    decision = ir::Decision::Test {
+                        tested_var: scrutinee_var,
+                        pattern: Box::new(left_pattern),
+                        success: Box::new(ir::Decision::Test {
+                            tested_var: right,
+                            pattern: Box::new(right_pattern),
+                            success: Box::new(Self::success(lt)),
+                            failure: Box::new(later),
+                        }),
+                        failure: Box::new(decision),
+                    };
+                }
+                Self::build_derived_match(scrutinee_var, left, decision)
+            }
+        }
+    }
+
+    // (x1, x2, ...) < (y1, y2, ...) if x1 < y1, or neither x1 < y1 nor y1 < x1 and the rest compares less
+    fn derive_lexicographic_lt(
+        &mut self,
+        class_id: ir::ClassID,
+        compared: Vec<(ir::TypeID, ir::Expr, ir::Expr)>,
+        span: Span,
+    ) -> ir::Expr {
+        let bool_ty = self.native_types.bool;
+        let mut result = None;
+        for (ty, x, y) in compared.into_iter().rev() {
+            let lt =
+                self.call_derived_item(class_id, ty, vec![x.clone(), y.clone()], bool_ty, span);
+            result = Some(match result {
+                None => lt,
+                Some(rest) => {
+                    let gt = self.call_derived_item(class_id, ty, vec![y, x], bool_ty, span);
+                    ir::Expr::ShortOr(
+                        Box::new(lt),
+                        Box::new(ir::Expr::ShortAnd(
+                            Box::new(ir::Expr::BitNeg(Box::new(gt))),
+                            Box::new(rest),
+                        )),
+                    )
+                }
+            });
+        }
+        result.unwrap_or(ir::Expr::Bool { val: false })
+    }
+
+    // records are displayed as '{ x = ..., y = ... }', and variants as 'name(..., ...)'
+    fn derive_display(
+        &mut self,
+        class_id: ir::ClassID,
+        arg: ir::VariableID,
+        components: &Components,
+        span: Span,
+    ) -> ir::Expr {
+        let string_ty = self.native_types.string;
+        match components {
+            Components::Fields(fields) => {
+                let mut parts = vec![Self::string("{ ")];
+                for (index, (name, ty)) in fields.iter().enumerate() {
+                    let separator = if index == 0 { "" } else { ", " };
+                    parts.push(Self::string(&format!("{separator}{name} = ")));
+                    let args = vec![Self::access(arg, index)];
+                    parts.push(self.call_derived_item(class_id, *ty, args, string_ty, span));
+                }
+                parts.push(Self::string(" }"));
+                Self::concatenation(parts)
+            }
+            Components::Variants(union_id, variants) => {
+                let scrutinee_var = self.entities.create_dummy_variable();
+                let mut decision = ir::Decision::Failure;
+                for (tag, (name, args)) in variants.iter().enumerate().rev() {
+                    let (pattern, xs) = self.bind_variant(*union_id, tag, args);
+                    let mut parts = vec![Self::string(name)];
+                    if let Some(args) = args {
+                        parts.push(Self::string("("));
+                        for (index, (ty, x)) in args.iter().zip(xs).enumerate() {
+                            if index > 0 {
+                                parts.push(Self::string(", "));
+                            }
+                            let args = vec![ir::Expr::Var { id: x }];
+                            parts
+                                .push(self.call_derived_item(class_id, *ty, args, string_ty, span));
+                        }
+                        parts.push(Self::string(")"));
+                    }
+
+                    decision = ir::Decision::Test {
+                        tested_var: scrutinee_var,
+                        pattern: Box::new(pattern),
+                        success: Box::new(Self::success(Self::concatenation(parts))),
+                        failure: Box::new(decision),
+                    };
+                }
+                Self::build_derived_match(scrutinee_var, arg, decision)
+            }
+        }
+    }
+
+    // records default all of their fields, and unions build their first variant
+    fn derive_default(
+        &mut self,
+        class_id: ir::ClassID,
+        components: &Components,
+        span: Span,
+    ) -> ir::Expr {
+        let default = |checker: &mut Self, ty: ir::TypeID| {
+            let (item, item_ty) = checker.check_class_item_into_expr(class_id, 0, span);
+            checker.unify(item_ty, ty, &[]);
+            item
+        };
+
+        match components {
+            Components::Fields(fields) => ir::Expr::Record {
+                fields: fields.iter().map(|(_, ty)| default(self, *ty)).collect(),
+            },
+            Components::Variants(_, variants) => {
+                let (_, args) = &variants[0];
+                ir::Expr::Variant {
+                    tag: 0,
+                    items: args
+                        .as_ref()
+                        .map(|args| args.iter().map(|ty| default(self, *ty)).collect()),
+                }
+            }
+        }
+    }
+
+    // applies the item of the derived class to values of a component's type
+    fn call_derived_item(
+        &mut self,
+        class_id: ir::ClassID,
+        component_ty: ir::TypeID,
+        args: Vec<ir::Expr>,
+        ret_ty: ir::TypeID,
+        span: Span,
+    ) -> ir::Expr {
+        let (item, item_ty) = self.check_class_item_into_expr(class_id, 0, span);
+        let expected_ty = self.create_type(
+            ir::Type::Lambda(vec![component_ty; args.len()].into(), ret_ty),
+            Some(span),
+        );
+        self.unify(item_ty, expected_ty, &[]);
+
+        ir::Expr::Call {
+            callee: Box::new(item),
+            args: args.into(),
+        }
+    }
+
+    fn create_derived_args<const N: usize>(&mut self) -> [ir::VariableID; N] {
+        std::array::from_fn(|_| self.entities.create_dummy_variable())
+    }
+
+    fn bind_variant(
+        &mut self,
+        union_id: ir::UnionID,
+        tag: usize,
+        args: &Option<Vec<ir::TypeID>>,
+    ) -> (ir::Pattern, Vec<ir::VariableID>) {
+        let vars = args
+            .iter()
+            .flatten()
+            .map(|_| self.entities.create_dummy_variable())
+            .collect::<Vec<_>>();
+        let patterns = args
+            .as_ref()
+            .map(|_| vars.iter().map(|id| ir::Pattern::Binding(*id)).collect());
+        (ir::Pattern::Variant(union_id, tag, patterns), vars)
+    }
+
+    fn build_derived_fun(name: String, args: &[ir::VariableID], expr: ir::Expr) -> ir::Expr {
+        ir::Expr::Fun {
+            name,
+            recursive_binding: None,
+            signature: Box::new(ir::Signature::Args {
+                args: args.iter().map(|id| ir::Pattern::Binding(*id)).collect(),
+                next: Box::new(ir::Signature::Done),
+            }),
+            expr: Box::new(expr),
+        }
+    }
+
+    fn build_derived_match(
+        scrutinee_var: ir::VariableID,
+        scrutinee: ir::VariableID,
+        decision: ir::Decision,
+    ) -> ir::Expr {
+        ir::Expr::Conditional {
+            branches: Box::new([ir::Branch::Match {
+                scrutinee_var,
+                scrutinee: Box::new(ir::Expr::Var { id: scrutinee }),
+                decision: Box::new(decision),
+            }]),
+            is_exhaustive: true,
+        }
+    }
+
+    fn success(result: ir::Expr) -> ir::Decision {
+        ir::Decision::Success {
+            stmts: Vec::new(),
+            result: Box::new(result),
+        }
+    }
+
+    fn access(var: ir::VariableID, index: usize) -> ir::Expr {
+        ir::Expr::Access {
+            accessed: Box::new(ir::Expr::Var { id: var }),
+            index,
+        }
+    }
+
+    fn string(val: &str) -> ir::Expr {
+        ir::Expr::String {
+            val: val.to_string(),
+        }
+    }
+
+    fn conjunction(exprs: Vec<ir::Expr>) -> ir::Expr {
+        exprs
+            .into_iter()
+            .reduce(|left, right| ir::Expr::ShortAnd(Box::new(left), Box::new(right)))
+            .unwrap_or(ir::Expr::Bool { val: true })
+    }
+
+    fn concatenation(exprs: Vec<ir::Expr>) -> ir::Expr {
+        exprs
+            .into_iter()
+            .reduce(|left, right| ir::Expr::Add(Box::new(left), Box::new(right)))
+            .unwrap_or(ir::Expr::String { val: String::new() })
+    }
+}
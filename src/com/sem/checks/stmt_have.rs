@@ -2,6 +2,7 @@ use std::collections::{BTreeSet, HashMap};
 
 use crate::com::{
     Checker, ast, ir,
+    loc::Span,
     reporting::{Header, Label, Note, Report},
     sem::checker::checker_print,
 };
@@ -51,13 +52,51 @@ impl Checker<'_, '_> {
             }
         }
 
+        let instance_id = self.check_instance(class_id, &registered, e.class.span(), span);
+        self.close_scope();
+
+        let Some(instance_id) = instance_id else {
+            return ir::Stmt::Nothing;
+        };
+
+        // a named instance is only ever selected explicitly, so it can't overlap
+        match e.name {
+            Some(name_span) => {
+                let name = name_span.lexeme(self.source);
+                self.scope.insert(name, instance_id.wrap());
+            }
+            None => {
+                if !self.check_instance_coherence(instance_id, span) {
+                    return ir::Stmt::Nothing;
+                }
+                self.scope.infos_mut().instances.insert(instance_id);
+            }
+        }
+        self.set_entity_public(instance_id.wrap(), public);
+
+        ir::Stmt::Have {
+            stmts: stmts.into(),
+        }
+    }
+
+    // checks the items of an instance against its class
+    // the instance is created if it is complete, but isn't brought into scope
+    pub fn check_instance(
+        &mut self,
+        class_id: ir::ClassID,
+        registered: &HashMap<String, ir::VariableID>,
+        class_span: Span,
+        span: Span,
+    ) -> Option<ir::InstanceID> {
         let info = self.entities.get_class_info(class_id);
+        let class_name = info.name.clone();
         let arity = info.arity;
         let mut instantiated_items = Vec::new();
         let mut missing_items = Vec::new();
         let mut item_infos = Vec::new();
         for (i, item) in info.items.iter().enumerate() {
-            let Some(binding) = registered.get(&item.name).copied() else {
+            // omitted items fall back to their default, if any
+            let Some(binding) = registered.get(&item.name).copied().or(item.default) else {
                 missing_items.push(i);
                 continue;
             };
@@ -94,17 +133,15 @@ impl Checker<'_, '_> {
 
         let current_constraint = ir::Constraint {
             id: class_id,
-            loc: e.class.span().wrap(self.file),
+            loc: class_span.wrap(self.file),
             class_args: (0..arity.0).map(|_| self.create_fresh_type(None)).collect(),
             associated_args: (0..arity.1).map(|_| self.create_fresh_type(None)).collect(),
             constraint_trace: ir::ConstraintTrace::default(),
         };
 
         for (wanted_scheme, found_scheme) in instantiated_items {
-            let found_type = self.instantiate_scheme_same_constraint_trace(
-                found_scheme,
-                Some(e.span().wrap(self.file)),
-            );
+            let found_type = self
+                .instantiate_scheme_same_constraint_trace(found_scheme, Some(span.wrap(self.file)));
             let (expected_type, current_constraints) =
                 self.instantiate_scheme_keep_constraints(wanted_scheme);
             debug_assert_eq!(current_constraints.len(), 1);
@@ -113,10 +150,42 @@ impl Checker<'_, '_> {
             self.unify_constraint(&current_constraint, current_constraints.first().unwrap());
         }
 
+        // the instances of the superclasses must exist as well
+        let superclasses = self
+            .entities
+            .get_class_info(class_id)
+            .superclasses
+            .iter()
+            .map(|superclass| (superclass.id, superclass.args.clone()))
+            .collect::<Vec<_>>();
+        for (superclass_id, superclass_args) in superclasses {
+            let associated_arity = self.entities.get_class_info(superclass_id).arity.1;
+            let superclass_constraint = ir::Constraint {
+                id: superclass_id,
+                loc: class_span.wrap(self.file),
+                class_args: superclass_args
+                    .iter()
+                    .map(|i| current_constraint.class_args[*i])
+                    .collect(),
+                associated_args: (0..associated_arity)
+                    .map(|_| self.create_fresh_type(None))
+                    .collect(),
+                constraint_trace: ir::ConstraintTrace::default(),
+            };
+            self.require_class_constraint(superclass_constraint);
+        }
+
+        // items may refer to the instance being defined, which solves them itself
+        let (self_constraints, constraints) = self
+            .take_constraint_context()
+            .into_iter()
+            .partition(|constraint| self.is_same_constraint(constraint, &current_constraint));
+        self.restore_constraint_context(constraints);
+
         let mut instantiation_domain = BTreeSet::new();
         self.collect_constraint_variables(&current_constraint, &mut instantiation_domain);
 
-        let (_, instantiation_constraints) = self.solve_constraints();
+        let (solutions, instantiation_constraints) = self.solve_constraints();
         for constraint in &instantiation_constraints {
             self.collect_constraint_variables(constraint, &mut instantiation_domain);
         }
@@ -125,32 +194,92 @@ impl Checker<'_, '_> {
             forall: instantiation_domain,
             constraint: current_constraint,
             required_constraints: instantiation_constraints,
+            self_constraints,
         };
 
         if is_complete {
             checker_print!(self, "{}", self.get_instance_scheme_string(&scheme));
         }
 
-        self.close_scope();
         self.close_scope();
 
-        if is_complete {
-            let instance_id = self.entities.next_instance_id();
-            self.entities.create_instance(ir::InstanceInfo {
-                loc: span.wrap(self.file),
-                scheme,
-                original: instance_id,
-                items: item_infos.into(),
-            });
-            self.scope.infos_mut().instances.insert(instance_id);
-            self.set_entity_public(instance_id.wrap(), public);
+        if !is_complete {
+            return None;
+        }
 
-            return ir::Stmt::Have {
-                stmts: stmts.into(),
-            };
+        let instance_id = self.entities.next_instance_id();
+        self.entities.create_instance(ir::InstanceInfo {
+            loc: span.wrap(self.file),
+            scheme,
+            original: instance_id,
+            items: item_infos.into(),
+            solutions: solutions.into(),
+        });
+        Some(instance_id)
+    }
+
+    // an instance must not overlap with another one in scope, in which case it is dropped
+    // otherwise it is only warned about when it is an orphan, if asked to
+    pub fn check_instance_coherence(&mut self, instance_id: ir::InstanceID, span: Span) -> bool {
+        let scheme = self.entities.get_instance_info(instance_id).scheme.clone();
+        for (_, other) in self.get_known_instances() {
+            if other.scheme.constraint.id != scheme.constraint.id {
+                continue;
+            }
+
+            let (_, current, _) = self.instantiate_instance_scheme(scheme.clone());
+            let (_, existing, _) = self.instantiate_instance_scheme(other.scheme);
+            if !self.try_unify_constraint_args(&current, &existing) {
+                continue;
+            }
+
+            let constr_string = self.get_constraint_string(&current);
+            self.reports.push(
+                Report::error(Header::OverlappingInstances(constr_string.clone()))
+                    .with_primary_label(
+                        Label::OverlappingInstance(constr_string),
+                        span.wrap(self.file),
+                    )
+                    .with_secondary_label(Label::ExistingInstance, other.loc),
+            );
+            return false;
+        }
+
+        if self.options.warn_orphan_instances && self.is_orphan_instance(&scheme.constraint) {
+            let class_name = self
+                .entities
+                .get_class_info(scheme.constraint.id)
+                .name
+                .clone();
+            self.reports.push(
+                Report::warning(Header::OrphanInstance(class_name.clone()))
+                    .with_primary_label(Label::OrphanInstance(class_name), span.wrap(self.file)),
+            );
         }
 
-        // fail
-        ir::Stmt::Nothing
+        true
+    }
+
+    // neither the class nor the type constructor of any of its arguments is defined in this file
+    fn is_orphan_instance(&mut self, constraint: &ir::Constraint) -> bool {
+        if self.entities.get_class_info(constraint.id).loc.file == self.file {
+            return false;
+        }
+
+        !constraint.class_args.iter().any(|arg| {
+            let repr = self.get_type_repr(*arg);
+            use ir::Type as T;
+            use ir::TypeConstructor as C;
+            let loc = match self.types[repr.0].ty {
+                T::Record(id, _) | T::Constructor(C::Record(id), _) => {
+                    self.entities.get_record_info(id).loc
+                }
+                T::Union(id, _) | T::Constructor(C::Union(id), _) => {
+                    self.entities.get_union_info(id).loc
+                }
+                _ => return false,
+            };
+            loc.file == self.file
+        })
     }
 }
@@ -5,10 +5,10 @@ use crate::com::{
     Checker, ast, ir,
     loc::Span,
     reporting::{Header, Label, Report},
-    sem::checker::checker_print,
+    sem::{checker::checker_print, provenance::Provenance},
 };
 
-impl Checker<'_, '_> {
+impl<'src> Checker<'src, '_> {
     fn check_pattern_or_signature(
         &mut self,
         e: &ast::Expr,
@@ -16,12 +16,16 @@ impl Checker<'_, '_> {
         use ast::Expr as E;
         match e {
             E::Call(..) => Either::Right(self.check_signature(e, true)),
+            E::Annotated(annotated) if matches!(*annotated.expr, E::Call(..)) => {
+                Either::Right(self.check_signature(e, true))
+            }
             _ => Either::Left(self.check_pattern(e)),
         }
     }
 
     pub fn check_let(&mut self, e: &ast::Let, public: bool) -> ir::Stmt {
-        let (stmt, bindings) = self.check_let_bindings(e, public);
+        let (stmt, bindings) =
+            self.check_in_constraint_scope(|checker| checker.check_let_bindings(e, public));
 
         for binding in bindings {
             let info = self.entities.get_variable_info(binding);
@@ -46,11 +50,11 @@ impl Checker<'_, '_> {
         let binding_span = Span::combine(e.let_kw, e.pattern.span());
         let lhs = self.check_pattern_or_signature(&e.pattern);
         match lhs {
-            Either::Left(pattern) => {
-                if !pattern.is_irrefutable() {
+            Either::Left(lhs) => {
+                if !lhs.is_irrefutable() {
                     self.reports.push(
                         Report::error(Header::RefutablePattern())
-                            .with_primary_label(Label::Empty, pattern.span().wrap(self.file))
+                            .with_primary_label(Label::Empty, lhs.span().wrap(self.file))
                             .with_secondary_label(
                                 Label::LetBindingPattern,
                                 binding_span.wrap(self.file),
@@ -59,9 +63,47 @@ impl Checker<'_, '_> {
                 }
 
                 let (value, ty) = self.check_expression(&e.value);
-                let (pattern, pattern_type) = self.declare_pattern(&pattern, public);
+                let previous = self.open_type_annotations();
+                let (pattern, pattern_type) = self.declare_pattern(&lhs, public);
+                let type_vars = self.close_type_annotations(previous);
+
+                let provenances = match lhs {
+                    ast::Pattern::Annotated(..) => {
+                        vec![Provenance::TypeAnnotation(lhs.span().wrap(self.file))]
+                    }
+                    _ => vec![],
+                };
+                self.unify(ty, pattern_type, &provenances);
+                self.check_implicit_type_vars(&type_vars);
+
+                // mutable bindings and values which may allocate arrays are not generalized
+                // constraints that can't be solved yet are left to the enclosing statement
+                if e.mut_kw.is_some() || !value.is_nonexpansive() {
+                    let (solutions, pending_constraints) = self.solve_constraints();
+                    for constraint in pending_constraints {
+                        self.require_class_constraint(constraint);
+                    }
+
+                    let bindings = pattern.get_binding_ids();
+                    let function_depth = self.function_depth();
+                    for var_id in bindings.iter().copied() {
+                        if e.mut_kw.is_some() {
+                            self.mutable_variables.insert(var_id, function_depth);
+                        }
+                        self.set_entity_public(var_id.wrap(), public);
+                    }
+
+                    return (
+                        ir::Stmt::Let {
+                            lhs: pattern,
+                            rhs: value,
+                            is_concrete: true,
+                            solutions,
+                        },
+                        bindings,
+                    );
+                }
 
-                self.unify(ty, pattern_type, &[]);
                 let (solutions, relevant_constraints) = self.solve_constraints();
                 let is_concrete = relevant_constraints.is_empty();
 
@@ -93,50 +135,11 @@ impl Checker<'_, '_> {
                 )
             }
             Either::Right(signature) => {
-                for arg_pattern in signature.arg_patterns() {
-                    if !arg_pattern.is_irrefutable() {
-                        self.reports.push(
-                            Report::error(Header::RefutablePattern())
-                                .with_primary_label(
-                                    Label::Empty,
-                                    arg_pattern.span().wrap(self.file),
-                                )
-                                .with_secondary_label(
-                                    Label::FunctionArgPattern,
-                                    binding_span.wrap(self.file),
-                                ),
-                        );
-                    }
-                }
-
-                self.open_scope(true);
-
-                let name = self.signature_name(&signature);
-                if let Some((name, _)) = name {
-                    self.set_scope_name(name.to_string());
-                }
-
-                let (sig, sig_type, ret_type, rec_id) = self.declare_signature(&signature);
-                self.set_type_span(sig_type, e.pattern.span());
-                self.set_type_span(ret_type, e.value.span());
-
-                let (val, val_type) = self.check_expression(&e.value);
-                self.unify(val_type, ret_type, &[]);
+                let function = self.check_let_function(e, &signature, None);
                 let (solutions, relevant_constraints) = self.solve_constraints();
                 let is_concrete = relevant_constraints.is_empty();
 
-                let full_function_name = self.build_scope_name();
-                self.close_scope();
-
-                let Some((name, name_span)) = name else {
-                    if !matches!(signature, ast::Signature::Missing) {
-                        self.reports.push(
-                            Report::error(Header::InvalidSignature()).with_primary_label(
-                                Label::NamelessSignature,
-                                e.pattern.span().wrap(self.file),
-                            ),
-                        );
-                    }
+                let Some((name, name_span, sig_type, lambda)) = function else {
                     return (ir::Stmt::Missing, vec![]);
                 };
 
@@ -147,17 +150,10 @@ impl Checker<'_, '_> {
 
                 let var_id = self.create_variable_poly(name, scheme, name_span);
                 self.set_entity_public(var_id.wrap(), public);
-                let pattern = ir::Pattern::Binding(var_id);
-                let lambda = ir::Expr::Fun {
-                    name: full_function_name,
-                    recursive_binding: rec_id,
-                    signature: Box::new(sig),
-                    expr: Box::new(val),
-                };
 
                 (
                     ir::Stmt::Let {
-                        lhs: pattern,
+                        lhs: ir::Pattern::Binding(var_id),
                         rhs: lambda,
                         is_concrete,
                         solutions,
@@ -167,4 +163,87 @@ impl Checker<'_, '_> {
             }
         }
     }
+
+    // checks the function of a let binding, leaving its constraints to be solved
+    // a function of a recursive group is given the type by which the others refer to it
+    pub fn check_let_function(
+        &mut self,
+        e: &ast::Let,
+        signature: &ast::Signature,
+        group_type: Option<ir::TypeID>,
+    ) -> Option<(&'src str, Span, ir::TypeID, ir::Expr)> {
+        let binding_span = Span::combine(e.let_kw, e.pattern.span());
+        if let Some(mut_kw) = e.mut_kw {
+            self.reports.push(
+                Report::error(Header::MutableSignature())
+                    .with_primary_label(Label::Empty, mut_kw.wrap(self.file))
+                    .with_secondary_label(Label::Empty, binding_span.wrap(self.file)),
+            );
+        }
+
+        for arg_pattern in signature.arg_patterns() {
+            if !arg_pattern.is_irrefutable() {
+                self.reports.push(
+                    Report::error(Header::RefutablePattern())
+                        .with_primary_label(Label::Empty, arg_pattern.span().wrap(self.file))
+                        .with_secondary_label(
+                            Label::FunctionArgPattern,
+                            binding_span.wrap(self.file),
+                        ),
+                );
+            }
+        }
+
+        self.open_scope(true);
+
+        let name = self.signature_name(signature);
+        if let Some((name, _)) = name {
+            self.set_scope_name(name.to_string());
+        }
+
+        let previous = self.open_type_annotations();
+        let (sig, sig_type, ret_type, rec_id) = self.declare_signature(signature);
+        let type_vars = self.close_type_annotations(previous);
+        self.declare_implicit_type_vars(&type_vars);
+
+        if let Some(group_type) = group_type {
+            self.unify(group_type, sig_type, &[]);
+        }
+
+        self.set_type_span(sig_type, e.pattern.span());
+        let provenances = match signature.return_annotation() {
+            Some(_) => vec![Provenance::TypeAnnotation(e.pattern.span().wrap(self.file))],
+            None => {
+                self.set_type_span(ret_type, e.value.span());
+                vec![]
+            }
+        };
+
+        let (val, val_type) = self.check_expression(&e.value);
+        self.unify(val_type, ret_type, &provenances);
+        self.check_implicit_type_vars(&type_vars);
+
+        let full_function_name = self.build_scope_name();
+        self.close_scope();
+
+        let Some((name, name_span)) = name else {
+            if !matches!(signature, ast::Signature::Missing) {
+                self.reports.push(
+                    Report::error(Header::InvalidSignature()).with_primary_label(
+                        Label::NamelessSignature,
+                        e.pattern.span().wrap(self.file),
+                    ),
+                );
+            }
+            return None;
+        };
+
+        let lambda = ir::Expr::Fun {
+            name: full_function_name,
+            recursive_binding: rec_id,
+            signature: Box::new(sig),
+            expr: Box::new(val),
+        };
+        Some((name, name_span, sig_type, lambda))
+    }
 }
@@ -0,0 +1,180 @@
+use colored::Colorize;
+use petgraph::{algo, prelude::DiGraphMap};
+use std::collections::HashMap;
+
+use crate::com::{Checker, ast, ir, loc::Span, sem::checker::checker_print};
+
+impl<'src> Checker<'src, '_> {
+    // the top-level statements checked at the given index, which are the functions it refers to
+    // before they are defined, and so on, grouped when referring to each other,
+    // each group coming after the ones it depends on
+    pub fn find_function_groups(&self, stmts: &[ast::Expr], index: usize) -> Vec<Vec<usize>> {
+        if Self::as_function_let(&stmts[index]).is_none() {
+            return vec![vec![index]];
+        }
+
+        // only the functions defined right after, which aren't known yet, can be referred to,
+        // past the statements that don't bind anything
+        let mut names = HashMap::new();
+        for (i, e) in stmts.iter().enumerate().skip(index) {
+            let Some((e, _)) = Self::as_function_let(e) else {
+                match Self::is_binding_statement(e) {
+                    true => break,
+                    false => continue,
+                }
+            };
+            let Some(name) = Self::function_let_name(e) else {
+                break;
+            };
+            let name = name.lexeme(self.source);
+            if names.contains_key(name) {
+                break;
+            }
+            if self.scope.search(name).is_none() {
+                names.insert(name, i);
+            }
+        }
+
+        let mut graph = DiGraphMap::<usize, ()>::new();
+        graph.add_node(index);
+        let mut pending = vec![index];
+        while let Some(i) = pending.pop() {
+            let (e, _) = Self::as_function_let(&stmts[i]).unwrap();
+            for item in ast::preorder_traversal(&e.value) {
+                if let ast::Expr::Var(lexeme) = item
+                    && let Some(&j) = names.get(lexeme.span.lexeme(self.source))
+                {
+                    if !graph.contains_node(j) {
+                        pending.push(j);
+                    }
+                    graph.add_edge(i, j, ());
+                }
+            }
+        }
+
+        // strongly connected components come after the ones they refer to
+        algo::tarjan_scc(&graph)
+            .into_iter()
+            .map(|mut group| {
+                group.sort();
+                group
+            })
+            .collect()
+    }
+
+    fn is_binding_statement(e: &ast::Expr) -> bool {
+        use ast::Expr as E;
+        matches!(
+            e,
+            E::Pub(_)
+                | E::Let(_)
+                | E::Alias(_)
+                | E::Import(_)
+                | E::ImportFrom(_)
+                | E::Record(_)
+                | E::Union(_)
+                | E::Synonym(_)
+                | E::Newtype(_)
+                | E::Class(_)
+                | E::Have(_)
+        )
+    }
+
+    // (let binding, public)
+    pub fn as_function_let(e: &ast::Expr) -> Option<(&ast::Let, bool)> {
+        use ast::Expr as E;
+        let (e, public) = match e {
+            E::Let(e) => (e, false),
+            E::Pub(e) => match &*e.expr {
+                E::Let(e) => (e, true),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let pattern = match &*e.pattern {
+            E::Annotated(annotated) => &*annotated.expr,
+            pattern => pattern,
+        };
+        match (pattern, e.mut_kw) {
+            (E::Call(..), None) => Some((e, public)),
+            _ => None,
+        }
+    }
+
+    fn function_let_name(e: &ast::Let) -> Option<Span> {
+        use ast::Expr as E;
+        let mut pattern = match &*e.pattern {
+            E::Annotated(annotated) => &*annotated.expr,
+            pattern => pattern,
+        };
+        loop {
+            match pattern {
+                E::Call(call) => pattern = &call.callee,
+                E::Var(lexeme) => return Some(lexeme.span),
+                _ => return None,
+            }
+        }
+    }
+
+    // the functions of a group refer to each other through monomorphic bindings,
+    // and are generalized together once they are all checked
+    pub fn check_let_group(&mut self, lets: &[(&ast::Let, bool)]) -> ir::Stmt {
+        let mut group_bindings = Vec::with_capacity(lets.len());
+        let mut group_types = Vec::with_capacity(lets.len());
+        for (e, _) in lets {
+            let name_span = Self::function_let_name(e).unwrap();
+            let ty = self.create_fresh_type(None);
+            let name = name_span.lexeme(self.source);
+            group_bindings.push(self.create_variable_mono(name, ty, name_span));
+            group_types.push(ty);
+        }
+
+        let functions = lets
+            .iter()
+            .zip(group_types)
+            .map(|((e, _), ty)| {
+                let signature = self.check_signature(&e.pattern, true);
+                self.check_let_function(e, &signature, Some(ty))
+            })
+            .collect::<Vec<_>>();
+
+        let (solutions, relevant_constraints) = self.solve_constraints();
+        let is_concrete = relevant_constraints.is_empty();
+
+        let mut bindings = Vec::with_capacity(lets.len());
+        let mut funs = Vec::with_capacity(lets.len());
+        for (function, (_, public)) in functions.into_iter().zip(lets) {
+            let Some((name, name_span, sig_type, lambda)) = function else {
+                return ir::Stmt::Missing;
+            };
+
+            let mut scheme = self.generalize_type(sig_type);
+            for constraint in relevant_constraints.clone() {
+                self.add_class_constraint(&mut scheme, constraint);
+            }
+
+            checker_print!(
+                self,
+                "{} {name} :: {}",
+                "let".bold(),
+                self.get_scheme_string(&scheme)
+            );
+
+            let var_id = self.create_variable_poly(name, scheme, name_span);
+            self.set_entity_public(var_id.wrap(), *public);
+            bindings.push(ir::Pattern::Binding(var_id));
+            funs.push(lambda);
+        }
+
+        ir::Stmt::Let {
+            lhs: ir::Pattern::Tuple(bindings.into()),
+            rhs: ir::Expr::FunGroup {
+                bindings: group_bindings.into(),
+                funs: funs.into(),
+            },
+            is_concrete,
+            solutions,
+        }
+    }
+}
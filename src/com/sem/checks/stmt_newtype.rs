@@ -0,0 +1,93 @@
+use crate::com::{
+    Checker, ast, ir,
+    reporting::{Header, Label, Note, Report},
+};
+
+impl Checker<'_, '_> {
+    // a newtype is a union with a single variant of a single argument,
+    // which is erased when lowering so that it costs nothing at runtime
+    pub fn check_newtype(&mut self, e: &ast::Newtype, public: bool) -> ir::Stmt {
+        // ensure the newtype's signature syntax is valid
+        let span = e.span();
+        let Some((name_span, args)) = Self::extract_simple_signature(&e.signature) else {
+            self.reports.push(
+                Report::error(Header::InvalidSignature())
+                    .with_primary_label(Label::Empty, e.signature.span().wrap(self.file))
+                    .with_note(Note::NewtypeSyntax),
+            );
+            return ir::Stmt::Nothing;
+        };
+
+        let newtype_name = name_span.lexeme(self.source);
+        self.open_scope(false);
+
+        // analyse and declare its type arguments if there are any
+        let (arg_info, arg_ids) = match args {
+            Some(args) => {
+                let (arg_info, arg_ids): (Vec<_>, Vec<_>) = args
+                    .iter()
+                    .map(|arg| {
+                        let (arg_id, arg_name) = self.declare_type_argument(arg);
+                        (ir::UnionArgInfo { name: arg_name }, arg_id)
+                    })
+                    .unzip();
+                (Some(arg_info.into()), Some(arg_ids.into()))
+            }
+            None => (None, None),
+        };
+
+        // register the newtype now, so that it can be used recursively
+        let union_id = self.entities.next_union_id();
+        let union_type = self.create_type(ir::Type::Union(union_id, arg_ids), None);
+        let union_scheme = self.generalize_type(union_type);
+        self.entities.create_union(ir::UnionInfo {
+            name: newtype_name.to_string(),
+            type_args: arg_info,
+            loc: span.wrap(self.file),
+            scheme: union_scheme,
+            variants: Box::new([]),
+            is_newtype: true,
+        });
+        self.set_entity_public(union_id.wrap(), public);
+        self.scope.insert(newtype_name, union_id.wrap());
+
+        // check its only variant
+        let variant = match Self::extract_simple_signature_with_args(&e.variant) {
+            Some((variant_name_span, [arg])) => {
+                let arg_type = self.check_type(arg);
+                let variant_type = self.create_type(
+                    ir::Type::Lambda(Box::new([arg_type]), union_type),
+                    Some(variant_name_span),
+                );
+                Some(ir::VariantInfo {
+                    name: variant_name_span.lexeme(self.source).to_string(),
+                    loc: e.variant.span().wrap(self.file),
+                    expr: ir::Expr::Newtype,
+                    scheme: self.generalize_type(variant_type),
+                    type_args: Some(Box::new([arg_type])),
+                })
+            }
+            _ => {
+                self.reports.push(
+                    Report::error(Header::InvalidSignature())
+                        .with_primary_label(Label::Empty, e.variant.span().wrap(self.file))
+                        .with_secondary_label(
+                            Label::WithinNewtypeDefinition(newtype_name.to_string()),
+                            span.wrap(self.file),
+                        )
+                        .with_note(Note::NewtypeVariantSyntax),
+                );
+                None
+            }
+        };
+
+        // close scope, but export the newtype's name binding
+        self.close_scope();
+        self.scope.insert(newtype_name, union_id.wrap());
+
+        let info = self.entities.get_union_info_mut(union_id);
+        info.variants = variant.into_iter().collect();
+
+        ir::Stmt::Nothing
+    }
+}
@@ -6,6 +6,15 @@ use crate::com::{
 
 impl Checker<'_, '_> {
     pub fn check_record(&mut self, e: &ast::Record, public: bool) -> ir::Stmt {
+        match self.declare_record(e, public) {
+            Some(record_id) => self.define_record(e, record_id, public),
+            None => ir::Stmt::Nothing,
+        }
+    }
+
+    // binds the record to its name before its fields are checked
+    // allows for recursion
+    pub fn declare_record(&mut self, e: &ast::Record, public: bool) -> Option<ir::RecordID> {
         // ensure the record's signature syntax is valid
         let span = e.span();
         let Some((name_span, args)) = Self::extract_simple_signature(&e.signature) else {
@@ -14,7 +23,7 @@ impl Checker<'_, '_> {
                     .with_primary_label(Label::Empty, e.signature.span().wrap(self.file))
                     .with_note(Note::RecordSyntax),
             );
-            return ir::Stmt::Nothing;
+            return None;
         };
 
         let record_name = name_span.lexeme(self.source);
@@ -50,7 +59,6 @@ impl Checker<'_, '_> {
         };
 
         // register the record type now
-        // allows for recursion
         let record_id = self.entities.next_record_id();
         let record_type = self.create_type(ir::Type::Record(record_id, arg_ids), None);
         let record_scheme = self.generalize_type(record_type);
@@ -64,9 +72,26 @@ impl Checker<'_, '_> {
         });
         self.set_entity_public(record_id.wrap(), public);
 
-        // bind it to its name now so that it can be used recursively
+        self.close_scope();
         self.scope.insert(record_name, record_id.wrap());
 
+        Some(record_id)
+    }
+
+    pub fn define_record(
+        &mut self,
+        e: &ast::Record,
+        record_id: ir::RecordID,
+        public: bool,
+    ) -> ir::Stmt {
+        self.open_scope(false);
+        let record_type = self
+            .entities
+            .get_record_info(record_id)
+            .scheme
+            .uninstantiated;
+        self.redeclare_type_arguments(&e.signature, record_type);
+
         // check fields
         let mut fields = Vec::new();
         for (name, ty) in &e.fields {
@@ -90,15 +115,12 @@ impl Checker<'_, '_> {
             });
         }
 
-        // close scope, but export the record's name binding
         self.close_scope();
-        self.scope.insert(record_name, record_id.wrap());
 
         let info = self.entities.get_record_info_mut(record_id);
         info.fields = fields.into();
 
-        // done
-        ir::Stmt::Nothing
+        self.check_deriving(e.deriving.as_deref(), record_id.wrap(), public)
     }
 
     pub fn get_admissible_records(
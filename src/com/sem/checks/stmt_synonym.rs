@@ -0,0 +1,102 @@
+use crate::com::{
+    Checker, ast, ir,
+    loc::Span,
+    reporting::{Header, Label, Note, Report},
+};
+
+impl Checker<'_, '_> {
+    pub fn check_synonym(&mut self, e: &ast::Synonym, public: bool) -> ir::Stmt {
+        // ensure the synonym's signature syntax is valid
+        let span = e.span();
+        let Some((name_span, args)) = Self::extract_simple_signature(&e.signature) else {
+            self.reports.push(
+                Report::error(Header::InvalidSignature())
+                    .with_primary_label(Label::Empty, e.signature.span().wrap(self.file))
+                    .with_note(Note::SynonymSyntax),
+            );
+            return ir::Stmt::Nothing;
+        };
+
+        let synonym_name = name_span.lexeme(self.source);
+        self.open_scope(false);
+
+        // type arguments are only visible in the synonym's type
+        let type_args = args.map(|args| {
+            args.iter()
+                .map(|arg| self.declare_type_argument(arg).0)
+                .collect::<Box<_>>()
+        });
+
+        // synonyms are not in scope of their own type, so they can't be recursive
+        let ty = self.check_type(&e.ty);
+        let mut scheme = self.generalize_type(ty);
+        for arg in type_args.iter().flatten() {
+            // arguments which don't appear in the type are still instantiated
+            let arg = self.get_type_repr(*arg);
+            scheme.forall.insert(arg);
+        }
+
+        self.close_scope();
+
+        let synonym_id = self.entities.create_synonym(ir::SynonymInfo {
+            name: synonym_name.to_string(),
+            loc: span.wrap(self.file),
+            scheme,
+            type_args,
+        });
+        self.set_entity_public(synonym_id.wrap(), public);
+        self.scope.insert(synonym_name, synonym_id.wrap());
+
+        ir::Stmt::Nothing
+    }
+
+    /// Produces an error report if the provided arguments don't match the synonym's signature
+    pub fn create_synonym_type(
+        &mut self,
+        synonym_id: ir::SynonymID,
+        args: Option<Box<[ir::TypeID]>>,
+        span: Span,
+    ) -> Option<ir::TypeID> {
+        let info = self.entities.get_synonym_info(synonym_id);
+        let name = info.name.to_string();
+        let label = match (&info.type_args, &args) {
+            (None, None) => None,
+            (Some(type_args), Some(args)) if type_args.len() == args.len() => None,
+            (Some(type_args), _) => Some(Label::SynonymTypeArgCount(name.clone(), type_args.len())),
+            (None, Some(_)) => Some(Label::SynonymTypeNoArgs(name.clone())),
+        };
+
+        if let Some(label) = label {
+            self.reports.push(
+                Report::error(Header::SynonymArgMismatch(name.clone()))
+                    .with_primary_label(label, span.wrap(self.file))
+                    .with_secondary_label(Label::SynonymDefinition(name), info.loc),
+            );
+            return None;
+        }
+
+        // expand the synonym with its type arguments
+        let scheme = info.scheme.clone();
+        let type_args = info.type_args.clone().unwrap_or_default();
+        let sub = self.build_type_substitution(scheme.forall);
+        let ty = self.apply_type_substitution(scheme.uninstantiated, &sub);
+        let ty = self.clone_type_repr(ty);
+        self.set_type_span(ty, span);
+
+        let type_args = type_args
+            .iter()
+            .map(|arg| self.apply_type_substitution(*arg, &sub))
+            .collect::<Box<_>>();
+        for (type_arg, arg) in type_args.iter().zip(args.iter().flatten()) {
+            self.unify(*type_arg, *arg, &[]);
+        }
+
+        // remember the synonym so that the type is displayed by its name
+        let repr = self.get_type_repr(ty);
+        if !matches!(self.types[repr.0].ty, ir::Type::Var) {
+            self.types[repr.0].synonym = Some((synonym_id, type_args));
+        }
+
+        Some(ty)
+    }
+}
@@ -0,0 +1,61 @@
+use either::Either;
+use std::collections::{HashMap, HashSet};
+
+use crate::com::{Checker, ast, ir};
+
+impl Checker<'_, '_> {
+    // records and unions declared right after each other may refer to each other,
+    // so they are all bound to their names before any of them is defined
+    // (index -> declared type, unless its signature is invalid)
+    pub fn declare_type_group(
+        &mut self,
+        stmts: &[ast::Expr],
+        index: usize,
+    ) -> HashMap<usize, Option<ir::AnyID>> {
+        let mut names = HashSet::new();
+        let mut declared = HashMap::new();
+        for (i, e) in stmts.iter().enumerate().skip(index) {
+            let Some((declaration, public)) = Self::as_type_declaration(e) else {
+                break;
+            };
+
+            let signature = declaration.either(|e| &e.signature, |e| &e.signature);
+            if let Some((name_span, _)) = Self::extract_simple_signature(signature)
+                && !names.insert(name_span.lexeme(self.source))
+            {
+                break;
+            }
+
+            let id = match declaration {
+                Either::Left(e) => self.declare_record(e, public).map(|id| id.wrap()),
+                Either::Right(e) => self.declare_union(e, public).map(|id| id.wrap()),
+            };
+            declared.insert(i, id);
+        }
+        declared
+    }
+
+    pub fn define_type(&mut self, e: &ast::Expr, id: ir::AnyID) -> ir::Stmt {
+        let (declaration, public) =
+            Self::as_type_declaration(e).expect("definition of a statement which isn't a type");
+        match (declaration, id) {
+            (Either::Left(e), ir::AnyID::Record(id)) => self.define_record(e, id, public),
+            (Either::Right(e), ir::AnyID::Union(id)) => self.define_union(e, id, public),
+            _ => unreachable!("definition of a type declared as another kind of entity"),
+        }
+    }
+
+    // (record or union, public)
+    fn as_type_declaration(e: &ast::Expr) -> Option<(Either<&ast::Record, &ast::Union>, bool)> {
+        use ast::Expr as E;
+        let (e, public) = match e {
+            E::Pub(e) => (&*e.expr, true),
+            e => (e, false),
+        };
+        match e {
+            E::Record(e) => Some((Either::Left(e), public)),
+            E::Union(e) => Some((Either::Right(e), public)),
+            _ => None,
+        }
+    }
+}
@@ -6,6 +6,15 @@ use crate::com::{
 
 impl Checker<'_, '_> {
     pub fn check_union(&mut self, e: &ast::Union, public: bool) -> ir::Stmt {
+        match self.declare_union(e, public) {
+            Some(union_id) => self.define_union(e, union_id, public),
+            None => ir::Stmt::Nothing,
+        }
+    }
+
+    // binds the union to its name before its variants are checked
+    // allows for recursion
+    pub fn declare_union(&mut self, e: &ast::Union, public: bool) -> Option<ir::UnionID> {
         // ensure the union's signature syntax is valid
         let span = e.span();
         let Some((name_span, args)) = Self::extract_simple_signature(&e.signature) else {
@@ -14,7 +23,7 @@ impl Checker<'_, '_> {
                     .with_primary_label(Label::Empty, e.signature.span().wrap(self.file))
                     .with_note(Note::UnionSyntax),
             );
-            return ir::Stmt::Nothing;
+            return None;
         };
 
         let union_name = name_span.lexeme(self.source);
@@ -50,7 +59,6 @@ impl Checker<'_, '_> {
         };
 
         // register the union type now
-        // allows for recursion
         let union_id = self.entities.next_union_id();
         let union_type = self.create_type(ir::Type::Union(union_id, arg_ids), None);
         let union_scheme = self.generalize_type(union_type);
@@ -61,12 +69,31 @@ impl Checker<'_, '_> {
             loc: union_loc,
             scheme: union_scheme,
             variants: Box::new([]),
+            is_newtype: false,
         });
         self.set_entity_public(union_id.wrap(), public);
 
-        // bind it to its name now so that it can be used recursively
+        self.close_scope();
         self.scope.insert(union_name, union_id.wrap());
 
+        Some(union_id)
+    }
+
+    pub fn define_union(
+        &mut self,
+        e: &ast::Union,
+        union_id: ir::UnionID,
+        public: bool,
+    ) -> ir::Stmt {
+        let span = e.span();
+        let info = self.entities.get_union_info(union_id);
+        let union_name = info.name.clone();
+        let union_type = info.scheme.uninstantiated;
+        let within_label = Label::WithinUnionDefinition(union_name.clone());
+
+        self.open_scope(false);
+        self.redeclare_type_arguments(&e.signature, union_type);
+
         // check variants
         let mut variants = Vec::new();
         for (tag, variant) in e.variants.iter().enumerate() {
@@ -126,15 +153,12 @@ impl Checker<'_, '_> {
             });
         }
 
-        // close scope, but export the union's name binding
         self.close_scope();
-        self.scope.insert(union_name, union_id.wrap());
 
         let info = self.entities.get_union_info_mut(union_id);
         info.variants = variants.into();
 
-        // done
-        ir::Stmt::Nothing
+        self.check_deriving(e.deriving.as_deref(), union_id.wrap(), public)
     }
 
     fn build_variant_expr(&mut self, tag: usize, arity: Option<usize>, name: String) -> ir::Expr {
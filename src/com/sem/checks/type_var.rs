@@ -1,8 +1,34 @@
-use crate::com::{Checker, ast, ir};
+use crate::com::{Checker, ast, ir, sem::checker::ImplicitTypeVar};
 
 impl Checker<'_, '_> {
     pub fn check_var_type(&mut self, t: &ast::Lexeme) -> ir::TypeID {
+        if self.implicit_type_vars.is_some() && self.try_check_var_path(t).is_none() {
+            return self.declare_implicit_type_var(t);
+        }
+
         let q = self.check_var_path(t);
         self.check_path_into_type(q, t.span)
     }
+
+    // the same name within a single annotation refers to the same type variable,
+    // which is reported at its first occurrence in the source
+    fn declare_implicit_type_var(&mut self, t: &ast::Lexeme) -> ir::TypeID {
+        let name = t.span.lexeme(self.source);
+        let vars = self.implicit_type_vars.as_mut().unwrap();
+        if let Some(var) = vars.iter_mut().find(|var| var.name == name) {
+            if t.span.start < var.span.start {
+                var.span = t.span;
+            }
+            return var.id;
+        }
+
+        let id = self.create_fresh_type(Some(t.span));
+        let vars = self.implicit_type_vars.as_mut().unwrap();
+        vars.push(ImplicitTypeVar {
+            name,
+            id,
+            span: t.span,
+        });
+        id
+    }
 }
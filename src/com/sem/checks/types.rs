@@ -16,6 +16,7 @@ impl<'src> Checker<'src, '_> {
             loc: span.map(|s| s.wrap(self.file)),
             provenances: Vec::new(),
             depth: self.scope.depth(),
+            synonym: None,
         });
         id
     }
@@ -56,6 +57,25 @@ impl<'src> Checker<'src, '_> {
         }
     }
 
+    // binds the arguments of a declared record or union type again, to check its definition
+    pub fn redeclare_type_arguments(&mut self, signature: &ast::Expr, ty: ir::TypeID) {
+        let Some((_, Some(args))) = Self::extract_simple_signature(signature) else {
+            return;
+        };
+        let repr = self.get_type_repr(ty);
+        let (ir::Type::Record(_, Some(arg_ids)) | ir::Type::Union(_, Some(arg_ids))) =
+            self.types[repr.0].ty.clone()
+        else {
+            return;
+        };
+
+        for (arg, arg_id) in args.iter().zip(arg_ids) {
+            if let ast::Expr::Var(e) = arg {
+                self.create_user_type(e.span.lexeme(self.source), arg_id);
+            }
+        }
+    }
+
     pub fn add_type_provenance(&mut self, id: ir::TypeID, prov: TypeProvenance) {
         self.types[id.0].provenances.push(prov)
     }
@@ -79,6 +99,7 @@ impl<'src> Checker<'src, '_> {
         let loc = self.types[ty.0].loc;
         let provenances = self.types[ty.0].provenances.clone();
         let depth = self.types[ty.0].depth;
+        let synonym = self.types[ty.0].synonym.clone();
 
         let new_ty = self.create_type(self.types[ty.0].ty.clone(), None);
         let new_node = &mut self.types[new_ty.0];
@@ -86,21 +107,115 @@ impl<'src> Checker<'src, '_> {
         new_node.loc = loc;
         new_node.provenances = provenances;
         new_node.depth = depth;
+        new_node.synonym = synonym;
         self.unify(ty, new_ty, &[]); // should never fail
 
         new_ty
     }
 
-    fn get_type_repr(&mut self, id: ir::TypeID) -> ir::TypeID {
+    pub fn get_type_repr(&mut self, id: ir::TypeID) -> ir::TypeID {
+        let repr = self.find_type_repr(id);
+
+        // once its constructor is known, an application is replaced by the full type
+        if let ir::Type::Apply(constructor, args) = &self.types[repr.0].ty
+            && let Some(ty) = self.saturate_type_application(*constructor, args)
+        {
+            self.types[repr.0].ty = ty;
+        }
+
+        repr
+    }
+
+    fn find_type_repr(&mut self, id: ir::TypeID) -> ir::TypeID {
         if self.types[id.0].parent == id {
             return id;
         }
 
-        let r = self.get_type_repr(self.types[id.0].parent);
+        let r = self.find_type_repr(self.types[id.0].parent);
         self.types[id.0].parent = r;
         r
     }
 
+    // the full type of a constructor applied to its remaining arguments, if they are all given
+    fn saturate_type_application(
+        &self,
+        constructor: ir::TypeID,
+        args: &[ir::TypeID],
+    ) -> Option<ir::Type> {
+        let constructor = self.get_type_repr_immut(constructor);
+        let ir::Type::Constructor(constructor, prefix) = &self.types[constructor.0].ty else {
+            return None;
+        };
+
+        let items = prefix.iter().chain(args).copied().collect::<Box<[_]>>();
+        if items.len() != self.get_type_constructor_arity(*constructor) {
+            return None;
+        }
+
+        use ir::TypeConstructor as C;
+        Some(match constructor {
+            C::Array => ir::Type::Array(items[0]),
+            C::Record(id) => ir::Type::Record(*id, Some(items)),
+            C::Union(id) => ir::Type::Union(*id, Some(items)),
+        })
+    }
+
+    fn try_unify_application(
+        &mut self,
+        constructor: ir::TypeID,
+        args: &[ir::TypeID],
+        ty: &ir::Type,
+        provenances: &[Provenance],
+        quiet: bool,
+    ) -> Option<bool> {
+        use ir::TypeConstructor as C;
+        let (head, items) = match ty {
+            ir::Type::Array(item) => (C::Array, std::slice::from_ref(item)),
+            ir::Type::Record(id, Some(items)) => (C::Record(*id), &items[..]),
+            ir::Type::Union(id, Some(items)) => (C::Union(*id), &items[..]),
+            _ => return None,
+        };
+
+        let split = items.len().checked_sub(args.len())?;
+        let (prefix, suffix) = items.split_at(split);
+        let partial = self.create_type(ir::Type::Constructor(head, prefix.into()), None);
+
+        let mut all = self.try_unify(constructor, partial, provenances, quiet);
+        for (arg, item) in args.iter().zip(suffix) {
+            all &= self.try_unify(*arg, *item, provenances, quiet);
+        }
+        Some(all)
+    }
+
+    fn get_type_constructor_arity(&self, constructor: ir::TypeConstructor) -> usize {
+        use ir::TypeConstructor as C;
+        match constructor {
+            C::Array => 1,
+            C::Record(id) => self
+                .entities
+                .get_record_info(id)
+                .type_args
+                .as_ref()
+                .map_or(0, |args| args.len()),
+            C::Union(id) => self
+                .entities
+                .get_union_info(id)
+                .type_args
+                .as_ref()
+                .map_or(0, |args| args.len()),
+        }
+    }
+
+    // the type behind a representative type, seeing through known applications
+    pub fn get_type_view(&self, repr: ir::TypeID) -> ir::Type {
+        match &self.types[repr.0].ty {
+            ir::Type::Apply(constructor, args) => self
+                .saturate_type_application(*constructor, args)
+                .unwrap_or_else(|| self.types[repr.0].ty.clone()),
+            ty => ty.clone(),
+        }
+    }
+
     fn get_type_repr_immut(&self, id: ir::TypeID) -> ir::TypeID {
         match self.types[id.0].parent == id {
             true => id,
@@ -112,6 +227,15 @@ impl<'src> Checker<'src, '_> {
         self.types[left.0].parent = right;
     }
 
+    // whether the outermost type constructor is known, regardless of its arguments
+    pub fn is_known_type_constructor(&mut self, ty: ir::TypeID) -> bool {
+        let repr = self.get_type_repr(ty);
+        !matches!(
+            self.get_type_view(repr),
+            ir::Type::Var | ir::Type::Apply(..)
+        )
+    }
+
     pub fn is_concrete_type(&mut self, ty: ir::TypeID) -> bool {
         let ty = self.get_type_repr(ty);
         use ir::Type as T;
@@ -130,6 +254,11 @@ impl<'src> Checker<'src, '_> {
             T::Record(_, Some(items)) => items.iter().all(|item| self.is_concrete_type(*item)),
             T::Union(_, None) => true,
             T::Union(_, Some(items)) => items.iter().all(|item| self.is_concrete_type(*item)),
+            T::Constructor(_, items) => items.iter().all(|item| self.is_concrete_type(*item)),
+            T::Apply(constructor, args) => {
+                self.is_concrete_type(constructor)
+                    && args.iter().all(|arg| self.is_concrete_type(*arg))
+            }
         }
     }
 
@@ -145,14 +274,19 @@ impl<'src> Checker<'src, '_> {
             T::Bool => false,
             T::String => false,
             T::Tuple(items) => items.iter().any(|item| self.is_relevant_type(*item)),
-            T::Array(item) => self.is_concrete_type(item),
+            T::Array(item) => self.is_relevant_type(item),
             T::Lambda(args, ret) => {
-                args.iter().any(|arg| self.is_concrete_type(*arg)) || self.is_concrete_type(ret)
+                args.iter().any(|arg| self.is_relevant_type(*arg)) || self.is_relevant_type(ret)
+            }
+            T::Record(_, None) => false,
+            T::Record(_, Some(items)) => items.iter().any(|item| self.is_relevant_type(*item)),
+            T::Union(_, None) => false,
+            T::Union(_, Some(items)) => items.iter().any(|item| self.is_relevant_type(*item)),
+            T::Constructor(_, items) => items.iter().any(|item| self.is_relevant_type(*item)),
+            T::Apply(constructor, args) => {
+                self.is_relevant_type(constructor)
+                    || args.iter().any(|arg| self.is_relevant_type(*arg))
             }
-            T::Record(_, None) => true,
-            T::Record(_, Some(items)) => items.iter().any(|item| self.is_concrete_type(*item)),
-            T::Union(_, None) => true,
-            T::Union(_, Some(items)) => items.iter().any(|item| self.is_concrete_type(*item)),
         }
     }
 
@@ -177,6 +311,11 @@ impl<'src> Checker<'src, '_> {
             T::Record(_, None) => false,
             T::Union(_, Some(items)) => items.iter().any(|&item| self.occurs_in_type(left, item)),
             T::Union(_, None) => false,
+            T::Constructor(_, items) => items.iter().any(|&item| self.occurs_in_type(left, item)),
+            T::Apply(constructor, args) => {
+                self.occurs_in_type(left, constructor)
+                    || args.iter().any(|&arg| self.occurs_in_type(left, arg))
+            }
         }
     }
 
@@ -215,6 +354,17 @@ impl<'src> Checker<'src, '_> {
                     self.propagate_lower_depth(item, new_depth);
                 }
             }
+            T::Constructor(_, items) => {
+                for item in items {
+                    self.propagate_lower_depth(item, new_depth);
+                }
+            }
+            T::Apply(constructor, args) => {
+                self.propagate_lower_depth(constructor, new_depth);
+                for arg in args {
+                    self.propagate_lower_depth(arg, new_depth);
+                }
+            }
         }
     }
 
@@ -275,29 +425,29 @@ impl<'src> Checker<'src, '_> {
             (T::String, T::String) => return true,
             (T::Bool, T::Bool) => return true,
 
-            (T::Tuple(left_items), T::Tuple(right_items)) => {
-                if left_items.len() == right_items.len() {
-                    let mut all = true;
-                    for (&left_item, &right_item) in left_items.iter().zip(right_items.iter()) {
-                        all &= self.try_unify(left_item, right_item, provenances, quiet);
-                    }
-                    return all;
+            (T::Tuple(left_items), T::Tuple(right_items))
+                if left_items.len() == right_items.len() =>
+            {
+                let mut all = true;
+                for (&left_item, &right_item) in left_items.iter().zip(right_items.iter()) {
+                    all &= self.try_unify(left_item, right_item, provenances, quiet);
                 }
+                return all;
             }
 
             (T::Array(left_item), T::Array(right_item)) => {
                 return self.try_unify(left_item, right_item, provenances, quiet);
             }
 
-            (T::Lambda(left_args, left_ret), T::Lambda(right_args, right_ret)) => {
-                if left_args.len() == right_args.len() {
-                    let mut all = true;
-                    for (&left_arg, &right_arg) in left_args.iter().zip(right_args.iter()) {
-                        all &= self.try_unify(right_arg, left_arg, provenances, quiet);
-                    }
-                    all &= self.try_unify(left_ret, right_ret, provenances, quiet);
-                    return all;
+            (T::Lambda(left_args, left_ret), T::Lambda(right_args, right_ret))
+                if left_args.len() == right_args.len() =>
+            {
+                let mut all = true;
+                for (&left_arg, &right_arg) in left_args.iter().zip(right_args.iter()) {
+                    all &= self.try_unify(right_arg, left_arg, provenances, quiet);
                 }
+                all &= self.try_unify(left_ret, right_ret, provenances, quiet);
+                return all;
             }
 
             (T::Record(left_rec, Some(left_items)), T::Record(right_rec, Some(right_items)))
@@ -332,6 +482,40 @@ impl<'src> Checker<'src, '_> {
                 return true;
             }
 
+            (T::Constructor(left_ctor, left_items), T::Constructor(right_ctor, right_items))
+                if left_ctor == right_ctor && left_items.len() == right_items.len() =>
+            {
+                let mut all = true;
+                for (left_item, right_item) in left_items.iter().zip(right_items.iter()) {
+                    all &= self.try_unify(*left_item, *right_item, provenances, quiet);
+                }
+                return all;
+            }
+
+            (T::Apply(left_ctor, left_args), T::Apply(right_ctor, right_args))
+                if left_args.len() == right_args.len() =>
+            {
+                let mut all = self.try_unify(left_ctor, right_ctor, provenances, quiet);
+                for (left_arg, right_arg) in left_args.iter().zip(right_args.iter()) {
+                    all &= self.try_unify(*left_arg, *right_arg, provenances, quiet);
+                }
+                return all;
+            }
+
+            // an application against a known type splits it into a constructor and its last arguments
+            (T::Apply(ctor, args), ty) => {
+                if let Some(all) = self.try_unify_application(ctor, &args, &ty, provenances, quiet)
+                {
+                    return all;
+                }
+            }
+            (ty, T::Apply(ctor, args)) => {
+                if let Some(all) = self.try_unify_application(ctor, &args, &ty, provenances, quiet)
+                {
+                    return all;
+                }
+            }
+
             _ => {}
         }
 
@@ -411,7 +595,7 @@ impl<'src> Checker<'src, '_> {
         all
     }
 
-    fn collect_type_variables(&mut self, ty: ir::TypeID, ids: &mut BTreeSet<ir::TypeID>) {
+    pub fn collect_type_variables(&mut self, ty: ir::TypeID, ids: &mut BTreeSet<ir::TypeID>) {
         let id = self.get_type_repr(ty);
         let node = &self.types[id.0];
 
@@ -450,6 +634,17 @@ impl<'src> Checker<'src, '_> {
                 }
             }
             T::Union(_, None) => {}
+            T::Constructor(_, items) => {
+                for item in items {
+                    self.collect_type_variables(item, ids);
+                }
+            }
+            T::Apply(constructor, args) => {
+                self.collect_type_variables(constructor, ids);
+                for arg in args {
+                    self.collect_type_variables(arg, ids);
+                }
+            }
         }
     }
 
@@ -498,6 +693,38 @@ impl<'src> Checker<'src, '_> {
         )
     }
 
+    // the schemes must share the same constraints, like the items of one class
+    // they are instantiated together, and their constraints are required once
+    pub fn instantiate_schemes_together(
+        &mut self,
+        schemes: &[ir::Scheme],
+        constraint_loc: Option<Loc>,
+    ) -> (usize, Vec<ir::TypeID>) {
+        let forall = schemes
+            .iter()
+            .flat_map(|s| s.forall.iter().copied())
+            .collect();
+        let sub = self.build_type_substitution(forall);
+        let new_constraint_id = self.get_generic_unique_id();
+
+        let constraints = schemes
+            .first()
+            .map(|s| s.constraints.clone())
+            .unwrap_or_default();
+        for constraint in constraints {
+            let mut subbed =
+                self.apply_constraint_substitution(constraint, &sub, Some(new_constraint_id));
+            subbed.loc = constraint_loc.unwrap_or(subbed.loc);
+            self.require_class_constraint(subbed);
+        }
+
+        let types = schemes
+            .iter()
+            .map(|s| self.apply_type_substitution(s.uninstantiated, &sub))
+            .collect();
+        (new_constraint_id, types)
+    }
+
     pub fn instantiate_scheme_same_constraint_trace(
         &mut self,
         scheme: ir::Scheme,
@@ -569,7 +796,26 @@ impl<'src> Checker<'src, '_> {
         sub: &BTreeMap<ir::TypeID, ir::TypeID>,
     ) -> ir::TypeID {
         let ty = self.get_type_repr(ty);
+        let new_ty = self.apply_type_substitution_inner(ty, sub);
 
+        // keep the synonym the type was expanded from
+        if new_ty != ty
+            && let Some((id, args)) = self.types[ty.0].synonym.clone()
+        {
+            let args = args
+                .iter()
+                .map(|arg| self.apply_type_substitution(*arg, sub))
+                .collect();
+            self.types[new_ty.0].synonym = Some((id, args));
+        }
+        new_ty
+    }
+
+    fn apply_type_substitution_inner(
+        &mut self,
+        ty: ir::TypeID,
+        sub: &BTreeMap<ir::TypeID, ir::TypeID>,
+    ) -> ir::TypeID {
         use ir::Type as T;
         match self.types[ty.0].ty.clone() {
             T::Var => match sub.get(&ty) {
@@ -615,6 +861,21 @@ impl<'src> Checker<'src, '_> {
                 self.create_type(T::Union(eid, Some(new_items)), None)
             }
             T::Union(_, None) => ty,
+            T::Constructor(constructor, items) => {
+                let new_items = items
+                    .iter()
+                    .map(|item| self.apply_type_substitution(*item, sub))
+                    .collect();
+                self.create_type(T::Constructor(constructor, new_items), None)
+            }
+            T::Apply(constructor, args) => {
+                let new_constructor = self.apply_type_substitution(constructor, sub);
+                let new_args = args
+                    .iter()
+                    .map(|arg| self.apply_type_substitution(*arg, sub))
+                    .collect();
+                self.create_type(T::Apply(new_constructor, new_args), None)
+            }
         }
     }
 
@@ -647,7 +908,7 @@ impl<'src> Checker<'src, '_> {
         }
     }
 
-    fn get_type_string_map(
+    pub fn get_type_string_map(
         &self,
         id: ir::TypeID,
         name_map: &BTreeMap<ir::TypeID, String>,
@@ -656,7 +917,20 @@ impl<'src> Checker<'src, '_> {
         use ir::Type as T;
         use ir::TypeString as S;
         let repr = self.get_type_repr_immut(id);
-        match &self.types[repr.0].ty {
+        if let Some((synonym_id, args)) = &self.types[repr.0].synonym {
+            let name = self.entities.get_synonym_info(*synonym_id).name.clone();
+            return match args.is_empty() {
+                true => S::Name(name),
+                false => S::Constructor(
+                    name,
+                    args.iter()
+                        .map(|arg| self.get_type_string_map(*arg, name_map, hide))
+                        .collect(),
+                ),
+            };
+        }
+
+        match &self.get_type_view(repr) {
             T::Var => match name_map.get(&repr) {
                 Some(name) => S::Name(name.clone()),
                 None if hide => S::Hidden,
@@ -711,6 +985,35 @@ impl<'src> Checker<'src, '_> {
                 let name = info.name.clone();
                 S::Name(name)
             }
+            T::Constructor(constructor, items) => {
+                let name = self.get_type_constructor_name(*constructor);
+                match items.is_empty() {
+                    true => S::Name(name),
+                    false => S::Constructor(
+                        name,
+                        items
+                            .iter()
+                            .map(|item| self.get_type_string_map(*item, name_map, hide))
+                            .collect(),
+                    ),
+                }
+            }
+            T::Apply(constructor, args) => S::Constructor(
+                self.get_type_string_map(*constructor, name_map, hide)
+                    .to_string(),
+                args.iter()
+                    .map(|arg| self.get_type_string_map(*arg, name_map, hide))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn get_type_constructor_name(&self, constructor: ir::TypeConstructor) -> String {
+        use ir::TypeConstructor as C;
+        match constructor {
+            C::Array => "[]".to_string(),
+            C::Record(id) => self.entities.get_record_info(id).name.clone(),
+            C::Union(id) => self.entities.get_union_info(id).name.clone(),
         }
     }
 
@@ -723,7 +1026,137 @@ impl<'src> Checker<'src, '_> {
         self.get_type_string_map(id, &BTreeMap::new(), false)
     }
 
-    fn create_domain_name_map(
+    // newtypes are not unions at runtime
+    pub fn is_same_type(&self, left: ir::TypeID, right: ir::TypeID) -> bool {
+        let left = self.get_type_repr_immut(left);
+        let right = self.get_type_repr_immut(right);
+        if left == right {
+            return true;
+        }
+
+        use ir::Type as T;
+        match (&self.get_type_view(left), &self.get_type_view(right)) {
+            (T::Int, T::Int) => true,
+            (T::Float, T::Float) => true,
+            (T::Bool, T::Bool) => true,
+            (T::String, T::String) => true,
+            (T::Tuple(left), T::Tuple(right)) => self.are_same_types(left, right),
+            (T::Array(left), T::Array(right)) => self.is_same_type(*left, *right),
+            (T::Lambda(left_args, left_ret), T::Lambda(right_args, right_ret)) => {
+                self.are_same_types(left_args, right_args)
+                    && self.is_same_type(*left_ret, *right_ret)
+            }
+            (T::Record(left_id, left), T::Record(right_id, right)) => {
+                left_id == right_id
+                    && self.are_same_types(
+                        left.as_deref().unwrap_or(&[]),
+                        right.as_deref().unwrap_or(&[]),
+                    )
+            }
+            (T::Union(left_id, left), T::Union(right_id, right)) => {
+                left_id == right_id
+                    && self.are_same_types(
+                        left.as_deref().unwrap_or(&[]),
+                        right.as_deref().unwrap_or(&[]),
+                    )
+            }
+            (T::Constructor(left_ctor, left), T::Constructor(right_ctor, right)) => {
+                left_ctor == right_ctor && self.are_same_types(left, right)
+            }
+            (T::Apply(left_ctor, left), T::Apply(right_ctor, right)) => {
+                self.is_same_type(*left_ctor, *right_ctor) && self.are_same_types(left, right)
+            }
+            _ => false,
+        }
+    }
+
+    fn are_same_types(&self, left: &[ir::TypeID], right: &[ir::TypeID]) -> bool {
+        left.len() == right.len()
+            && left
+                .iter()
+                .zip(right)
+                .all(|(left, right)| self.is_same_type(*left, *right))
+    }
+
+    pub fn is_union_type(&mut self, ty: ir::TypeID) -> bool {
+        let ty = self.get_type_repr(ty);
+        match self.types[ty.0].ty {
+            ir::Type::Union(id, _) => !self.entities.get_union_info(id).is_newtype,
+            _ => false,
+        }
+    }
+
+    pub fn is_array_type(&mut self, ty: ir::TypeID) -> bool {
+        let ty = self.get_type_repr(ty);
+        matches!(self.types[ty.0].ty, ir::Type::Array(_))
+    }
+
+    pub fn get_record_type_id(&mut self, ty: ir::TypeID) -> Option<ir::RecordID> {
+        let ty = self.get_type_repr(ty);
+        match self.types[ty.0].ty {
+            ir::Type::Record(id, _) => Some(id),
+            _ => None,
+        }
+    }
+
+    // unlike type strings, tags tell apart user types with identical names
+    // returns None if the type isn't fully known
+    pub fn get_type_tag(&self, id: ir::TypeID) -> Option<String> {
+        use ir::Type as T;
+        let repr = self.get_type_repr_immut(id);
+        let tags = |items: &[ir::TypeID]| -> Option<String> {
+            let tags = items
+                .iter()
+                .map(|item| self.get_type_tag(*item))
+                .collect::<Option<Vec<_>>>()?;
+            Some(tags.join(", "))
+        };
+        match &self.get_type_view(repr) {
+            T::Var => None,
+            T::Int => Some("int".to_string()),
+            T::Float => Some("float".to_string()),
+            T::Bool => Some("bool".to_string()),
+            T::String => Some("string".to_string()),
+            T::Tuple(items) => Some(format!("({})", tags(items)?)),
+            T::Array(item) => Some(format!("[]{}", self.get_type_tag(*item)?)),
+            T::Lambda(args, ret) => Some(format!(
+                "fun({}) => {}",
+                tags(args)?,
+                self.get_type_tag(*ret)?
+            )),
+            T::Record(eid, items) => {
+                let name = &self.entities.get_record_info(*eid).name;
+                match items {
+                    Some(items) => Some(format!("{name}#{}({})", eid.0, tags(items)?)),
+                    None => Some(format!("{name}#{}", eid.0)),
+                }
+            }
+            T::Union(eid, items) => {
+                let name = &self.entities.get_union_info(*eid).name;
+                match items {
+                    Some(items) => Some(format!("{name}#{}({})", eid.0, tags(items)?)),
+                    None => Some(format!("{name}#{}", eid.0)),
+                }
+            }
+            T::Constructor(constructor, items) => {
+                let name = match constructor {
+                    ir::TypeConstructor::Array => "[]".to_string(),
+                    ir::TypeConstructor::Record(eid) => {
+                        let name = &self.entities.get_record_info(*eid).name;
+                        format!("{name}#{}", eid.0)
+                    }
+                    ir::TypeConstructor::Union(eid) => {
+                        let name = &self.entities.get_union_info(*eid).name;
+                        format!("{name}#{}", eid.0)
+                    }
+                };
+                Some(format!("{name}({})", tags(items)?))
+            }
+            T::Apply(..) => None,
+        }
+    }
+
+    pub fn create_domain_name_map(
         &self,
         domain: &BTreeSet<ir::TypeID>,
     ) -> (BTreeMap<ir::TypeID, String>, Vec<ir::TypeID>) {
@@ -764,6 +1197,9 @@ impl<'src> Checker<'src, '_> {
 
         let mut constraints = HashSet::new();
         for constraint in &scheme.constraints {
+            if self.is_entailed_constraint(constraint, &scheme.constraints) {
+                continue;
+            }
             constraints.insert(self.get_constraint_string_map(constraint, &name_map, true));
         }
         let constraints = constraints.into_iter().collect();
@@ -789,6 +1225,9 @@ impl<'src> Checker<'src, '_> {
 
         let mut required_constraints = HashSet::new();
         for constraint in &scheme.required_constraints {
+            if self.is_entailed_constraint(constraint, &scheme.required_constraints) {
+                continue;
+            }
             required_constraints
                 .insert(self.get_constraint_string_map(constraint, &name_map, true));
         }
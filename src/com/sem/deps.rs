@@ -12,18 +12,15 @@ use crate::com::{
     reporting::{Header, Label, Note, Report},
 };
 
-pub fn get_marin_path_builtin(dir: &str) -> PathBuf {
+// builtin directories such as the std library live next to the executable
+pub fn get_marin_dir() -> PathBuf {
     std::env::current_exe()
         .expect("need access to current executable directory")
         .canonicalize()
         .expect("couldn't normalize current executable directory")
         .parent()
         .unwrap()
-        .join(dir)
-}
-
-pub fn get_marin_std_path() -> PathBuf {
-    get_marin_path_builtin("std")
+        .to_path_buf()
 }
 
 pub struct Dependencies {
@@ -41,6 +38,7 @@ pub struct DepInfo {
 
 pub fn analyse_dependencies(
     files: &Files<Parsed>,
+    marin_dir: &Path,
     is_std_staged: bool,
     reports: &mut Vec<Report>,
 ) -> Dependencies {
@@ -57,7 +55,7 @@ pub fn analyse_dependencies(
 
     // use prelude file
     if is_std_staged {
-        let prelude_path = get_marin_path_builtin("std").join("prelude.mar");
+        let prelude_path = marin_dir.join("std").join("prelude.mar");
         let prelude_file = file_tree.get_by_path(&prelude_path).copied();
         info.prelude_file = prelude_file;
     }
@@ -124,7 +122,7 @@ pub fn analyse_dependencies(
             }
 
             let Some((full_dep_path, used_builtin)) =
-                navigate_query(path.parent().unwrap(), &query)
+                navigate_query(path.parent().unwrap(), marin_dir, &query)
             else {
                 reports.push(
                     Report::error(Header::InvalidDependencyPath())
@@ -201,7 +199,11 @@ pub fn analyse_dependencies(
     Dependencies { graph, info }
 }
 
-fn navigate_query(from: impl AsRef<Path>, query: &Query) -> Option<(PathBuf, bool)> {
+fn navigate_query(
+    from: impl AsRef<Path>,
+    marin_dir: &Path,
+    query: &Query,
+) -> Option<(PathBuf, bool)> {
     let mut path = from.as_ref().to_path_buf();
     let mut is_first = true;
     let mut used_builtin = false;
@@ -218,7 +220,7 @@ fn navigate_query(from: impl AsRef<Path>, query: &Query) -> Option<(PathBuf, boo
                     return None;
                 } else {
                     used_builtin = true;
-                    path = get_marin_path_builtin(name);
+                    path = marin_dir.join(name);
                 }
             }
         }
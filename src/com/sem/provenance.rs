@@ -11,8 +11,11 @@ pub enum Provenance {
     ConditionalReturnValues(Loc),
     FunctionCall(TypeString, Loc),
     RecordFieldTypes(String, Loc),
+    RecordUpdateBase(String, Loc),
     IndexedMustBeArray(Loc),
     IndexMustBeInteger(Loc),
+    RangeMustBeInteger(Loc),
+    TypeAnnotation(Loc),
 }
 
 impl Provenance {
@@ -35,8 +38,19 @@ impl Provenance {
             Pr::RecordFieldTypes(record, loc) => {
                 report.with_secondary_label(Label::RecordDefinition(record.clone()), *loc)
             }
-            Pr::IndexedMustBeArray(loc) => report.with_secondary_label(Label::IndexedMustBeArray, *loc),
-            Pr::IndexMustBeInteger(loc) => report.with_secondary_label(Label::IndexMustBeInteger, *loc),
+            Pr::RecordUpdateBase(record, loc) => {
+                report.with_secondary_label(Label::RecordUpdateBase(record.clone()), *loc)
+            }
+            Pr::IndexedMustBeArray(loc) => {
+                report.with_secondary_label(Label::IndexedMustBeArray, *loc)
+            }
+            Pr::IndexMustBeInteger(loc) => {
+                report.with_secondary_label(Label::IndexMustBeInteger, *loc)
+            }
+            Pr::RangeMustBeInteger(loc) => {
+                report.with_secondary_label(Label::RangeMustBeInteger, *loc)
+            }
+            Pr::TypeAnnotation(loc) => report.with_secondary_label(Label::TypeAnnotation, *loc),
         }
     }
 }
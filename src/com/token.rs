@@ -1,4 +1,4 @@
-use logos::Logos;
+use logos::{Lexer, Logos};
 use std::fmt::Display;
 
 #[derive(Debug, Copy, Clone, Logos, PartialEq, Eq)]
@@ -64,6 +64,8 @@ pub enum Token {
     Spread,
     #[token(".")]
     Dot,
+    #[token("?")]
+    Question,
 
     #[token("do")]
     Do,
@@ -71,6 +73,8 @@ pub enum Token {
     End,
     #[token("let")]
     Let,
+    #[token("mut")]
+    Mut,
     #[token("pub")]
     Pub,
     #[token("fun")]
@@ -83,6 +87,10 @@ pub enum Token {
     Else,
     #[token("while")]
     While,
+    #[token("for")]
+    For,
+    #[token("in")]
+    In,
     #[token("loop")]
     Loop,
     #[token("match")]
@@ -93,6 +101,12 @@ pub enum Token {
     Break,
     #[token("skip")]
     Skip,
+    #[token("try")]
+    Try,
+    #[token("catch")]
+    Catch,
+    #[token("raise")]
+    Raise,
     #[token("true")]
     True,
     #[token("false")]
@@ -111,12 +125,18 @@ pub enum Token {
     Record,
     #[token("union")]
     Union,
+    #[token("type")]
+    Type,
+    #[token("newtype")]
+    Newtype,
     #[token("class")]
     Class,
     #[token("of")]
     Of,
     #[token("have")]
     Have,
+    #[token("deriving")]
+    Deriving,
     #[token("and")]
     And,
     #[token("or")]
@@ -134,7 +154,7 @@ pub enum Token {
     Int,
     #[regex(r"\d+\.\d+")]
     Float,
-    #[regex(r#""[^"]*""#)]
+    #[token("\"", lex_string)]
     String,
     #[regex(r"@[^\W]+")]
     Builtin,
@@ -175,21 +195,28 @@ impl Display for Token {
             Token::Comma => write!(f, "','"),
             Token::Spread => write!(f, "'..'"),
             Token::Dot => write!(f, "'.'"),
+            Token::Question => write!(f, "'?'"),
 
             Token::Do => write!(f, "'do' keyword"),
             Token::End => write!(f, "'end' keyword"),
             Token::Let => write!(f, "'let' keyword"),
+            Token::Mut => write!(f, "'mut' keyword"),
             Token::Pub => write!(f, "'pub' keyword"),
             Token::Fun => write!(f, "'fun' keyword"),
             Token::If => write!(f, "'if' keyword"),
             Token::Then => write!(f, "'then' keyword"),
             Token::Else => write!(f, "'else' keyword"),
             Token::While => write!(f, "'while' keyword"),
+            Token::For => write!(f, "'for' keyword"),
+            Token::In => write!(f, "'in' keyword"),
             Token::Loop => write!(f, "'loop' keyword"),
             Token::Match => write!(f, "'match' keyword"),
             Token::With => write!(f, "'with' keyword"),
             Token::Break => write!(f, "'break' keyword"),
             Token::Skip => write!(f, "'skip' keyword"),
+            Token::Try => write!(f, "'try' keyword"),
+            Token::Catch => write!(f, "'catch' keyword"),
+            Token::Raise => write!(f, "'raise' keyword"),
             Token::True => write!(f, "'true' keyword"),
             Token::False => write!(f, "'false' keyword"),
             Token::Import => write!(f, "'import' keyword"),
@@ -199,9 +226,12 @@ impl Display for Token {
             Token::Super => write!(f, "'super' keyword"),
             Token::Record => write!(f, "'record' keyword"),
             Token::Union => write!(f, "'union' keyword"),
+            Token::Type => write!(f, "'type' keyword"),
+            Token::Newtype => write!(f, "'newtype' keyword"),
             Token::Class => write!(f, "'class' keyword"),
             Token::Of => write!(f, "'of' keyword"),
             Token::Have => write!(f, "'have' keyword"),
+            Token::Deriving => write!(f, "'deriving' keyword"),
             Token::And => write!(f, "'and' keyword"),
             Token::Or => write!(f, "'or' keyword"),
             Token::Xor => write!(f, "'xor' keyword"),
@@ -216,3 +246,53 @@ impl Display for Token {
         }
     }
 }
+
+// strings end at the first quote outside of their interpolation holes,
+// so that holes may contain strings themselves
+fn lex_string(lex: &mut Lexer<Token>) -> bool {
+    let rest = lex.remainder().as_bytes();
+    // strings with unclosed holes end at their first quote, the hole is reported when parsing
+    let len = string_len(rest).or_else(|| rest.iter().position(|&b| b == b'"').map(|i| i + 1));
+    let Some(len) = len else {
+        return false;
+    };
+    lex.bump(len);
+    true
+}
+
+// the length of the rest of a string up to its closing quote,
+// where '{{' escapes a brace instead of opening a hole
+fn string_len(rest: &[u8]) -> Option<usize> {
+    let mut i = 0;
+    while i < rest.len() {
+        match rest[i] {
+            b'"' => return Some(i + 1),
+            b'{' if rest.get(i + 1) == Some(&b'{') => i += 2,
+            b'{' => i += 1 + hole_len(&rest[i + 1..])?,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+// the length of the rest of an interpolation hole up to its closing brace
+pub fn hole_len(rest: &[u8]) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < rest.len() {
+        match rest[i] {
+            b'"' => i += 1 + string_len(&rest[i + 1..])?,
+            b'}' if depth == 0 => return Some(i + 1),
+            b'{' => {
+                depth += 1;
+                i += 1;
+            }
+            b'}' => {
+                depth -= 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
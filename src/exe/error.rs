@@ -0,0 +1,46 @@
+use std::fmt::Display;
+
+// misuses of the vm by the host, and failures of the functions it calls
+#[derive(Debug, Clone, PartialEq)]
+pub enum CallError {
+    Running,
+    UnknownFunction(String),
+    AmbiguousFunction(String),
+    ArgCount { expected: usize, given: usize },
+    InvalidClosure,
+    MissingField(String),
+    UnknownVariant(String),
+    VariantArgCount { variant: String, expected: usize },
+    Untyped(String),
+    Uncaught(String),
+    Panic(String),
+}
+
+impl Display for CallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CallError::Running => write!(f, "cannot call a closure while the program is running"),
+            CallError::UnknownFunction(name) => write!(f, "no public function named '{name}'"),
+            CallError::AmbiguousFunction(name) => {
+                write!(f, "several public functions are named '{name}'")
+            }
+            CallError::ArgCount { expected, given } => {
+                write!(
+                    f,
+                    "closure expects {expected} arguments, but {given} were given"
+                )
+            }
+            CallError::InvalidClosure => write!(f, "invalid closure handle"),
+            CallError::MissingField(name) => write!(f, "missing record field '{name}'"),
+            CallError::UnknownVariant(name) => write!(f, "unknown union variant '{name}'"),
+            CallError::VariantArgCount { variant, expected } => {
+                write!(f, "union variant '{variant}' expects {expected} arguments")
+            }
+            CallError::Untyped(value) => {
+                write!(f, "value '{value}' cannot be stored without its type")
+            }
+            CallError::Uncaught(exception) => write!(f, "uncaught exception: {exception}"),
+            CallError::Panic(msg) => write!(f, "panic: {msg}"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::HashMap, fmt};
+
+pub type LayoutID = usize;
+
+// the shape of the values of a type at runtime
+// it is only used to give typed values back to the host
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Layout {
+    Any,
+    Int,
+    Float,
+    Bool,
+    String,
+    Func(Box<[LayoutID]>, LayoutID),
+    Tuple(Box<[LayoutID]>),
+    Array(LayoutID),
+    Record(String, Box<[(String, LayoutID)]>),
+    Union(String, Box<[(String, Box<[LayoutID]>)]>),
+    List { item: LayoutID, cons_tag: usize },
+}
+
+fn fmt_ids(f: &mut fmt::Formatter<'_>, ids: &[LayoutID]) -> fmt::Result {
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "#{id}")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Layout::Any => write!(f, "any"),
+            Layout::Int => write!(f, "int"),
+            Layout::Float => write!(f, "float"),
+            Layout::Bool => write!(f, "bool"),
+            Layout::String => write!(f, "string"),
+            Layout::Func(args, ret) => {
+                write!(f, "fun(")?;
+                fmt_ids(f, args)?;
+                write!(f, ") -> #{ret}")
+            }
+            Layout::Tuple(items) => {
+                write!(f, "(")?;
+                fmt_ids(f, items)?;
+                write!(f, ")")
+            }
+            Layout::Array(item) => write!(f, "[#{item}]"),
+            Layout::Record(name, fields) => {
+                write!(f, "record {name} {{")?;
+                for (i, (field, id)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {field}: #{id}")?;
+                }
+                write!(f, " }}")
+            }
+            Layout::Union(name, variants) => {
+                write!(f, "union {name} {{")?;
+                for (i, (variant, args)) in variants.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {variant}(")?;
+                    fmt_ids(f, args)?;
+                    write!(f, ")")?;
+                }
+                write!(f, " }}")
+            }
+            Layout::List { item, cons_tag } => write!(f, "list [#{item}] (cons tag {cons_tag})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LayoutTable {
+    layouts: Vec<Layout>,
+    ids: HashMap<Layout, LayoutID>,
+}
+
+impl Default for LayoutTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayoutTable {
+    // the layout of values whose type isn't known
+    pub const ANY: LayoutID = 0;
+
+    pub fn new() -> Self {
+        let mut table = Self {
+            layouts: Vec::new(),
+            ids: HashMap::new(),
+        };
+        table.add(Layout::Any);
+        table
+    }
+
+    pub fn from_layouts(layouts: Vec<Layout>) -> Self {
+        let mut ids = HashMap::new();
+        for (id, layout) in layouts.iter().enumerate() {
+            ids.entry(layout.clone()).or_insert(id);
+        }
+        Self { layouts, ids }
+    }
+
+    pub fn add(&mut self, layout: Layout) -> LayoutID {
+        if let Some(id) = self.ids.get(&layout) {
+            return *id;
+        }
+
+        let id = self.layouts.len();
+        self.layouts.push(layout.clone());
+        self.ids.insert(layout, id);
+        id
+    }
+
+    // reserves a layout which is defined later on, so that it can refer to itself
+    pub fn reserve(&mut self) -> LayoutID {
+        let id = self.layouts.len();
+        self.layouts.push(Layout::Any);
+        id
+    }
+
+    pub fn define(&mut self, id: LayoutID, layout: Layout) {
+        self.ids.entry(layout.clone()).or_insert(id);
+        self.layouts[id] = layout;
+    }
+
+    pub fn get(&self, id: LayoutID) -> &Layout {
+        &self.layouts[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.layouts.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Layout> {
+        self.layouts.iter()
+    }
+}
@@ -1,20 +1,31 @@
 pub mod vm;
 pub use vm::VM;
 
+pub mod error;
+pub use error::CallError;
+
 pub mod value;
-pub use value::Value;
+pub use value::{Closure, Value};
+
+pub mod layout;
+pub use layout::{Layout, LayoutID, LayoutTable};
+
+pub mod trace;
+pub use trace::Trace;
 
 use crate::binary;
 
-pub fn run_bytecode(mut bytecode: &[u8]) -> Value {
+pub fn load_bytecode(mut bytecode: &[u8]) -> VM<'_> {
     binary::read_magic(&mut bytecode).expect("not marin bytecode (magic bytes mismatch)");
     let constants = binary::read_constant_pool(&mut bytecode).unwrap();
-    let _ = binary::read_function_table(&mut bytecode).unwrap();
+    let functions = binary::read_function_table(&mut bytecode).unwrap();
+    let (layouts, result) = binary::read_layout_table(&mut bytecode).unwrap();
+    let exports = binary::read_export_table(&mut bytecode).unwrap();
 
-    let mut vm = VM::new(bytecode);
+    let mut vm = VM::new(bytecode, functions, layouts, result, exports);
     for value in constants {
         vm.add_constant(&value);
     }
 
-    vm.run()
+    vm
 }
@@ -0,0 +1,56 @@
+use std::{collections::HashSet, io};
+
+// how many values from the top of the stack are shown for each instruction
+pub const TRACE_STACK_LEN: usize = 4;
+
+pub struct Trace {
+    out: Box<dyn io::Write>,
+    filter: Option<HashSet<String>>,
+}
+
+impl Trace {
+    pub fn new(out: Box<dyn io::Write>) -> Self {
+        Self { out, filter: None }
+    }
+
+    // restricts tracing to the given functions of the function table
+    // either by their full name, or by their last path component
+    pub fn with_filter(mut self, functions: impl IntoIterator<Item = String>) -> Self {
+        self.filter = Some(functions.into_iter().collect());
+        self
+    }
+
+    // the names of the filter which match none of the given functions
+    pub fn unmatched_filter<'f>(&self, functions: impl Iterator<Item = &'f str>) -> Vec<String> {
+        let Some(filter) = &self.filter else {
+            return Vec::new();
+        };
+
+        let functions = functions.collect::<Vec<_>>();
+        let mut unmatched = filter
+            .iter()
+            .filter(|name| !functions.iter().any(|function| is_named(function, name)))
+            .cloned()
+            .collect::<Vec<_>>();
+        unmatched.sort();
+        unmatched
+    }
+
+    pub fn is_traced(&self, function: &str) -> bool {
+        let Some(filter) = &self.filter else {
+            return true;
+        };
+
+        filter.iter().any(|name| is_named(function, name))
+    }
+
+    pub fn log(&mut self, line: &str) {
+        writeln!(self.out, "{line}").expect("failed to write trace");
+    }
+}
+
+// a function is named either by its full name, or by its last path component
+fn is_named(function: &str, name: &str) -> bool {
+    let last = function.rsplit('.').next().unwrap_or(function);
+    function == name || last == name
+}
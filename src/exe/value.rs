@@ -1,14 +1,41 @@
+use super::LayoutID;
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq)]
+// a handle to a closure living in the heap of the vm it was given by
+// it stays valid as long as that vm is alive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Closure {
+    pub(crate) index: u32,
+    pub(crate) layout: LayoutID,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Nil,
     Int(i64),
     Float(f64),
     String(String),
     Bool(bool),
-    Func,
+    Func(Closure),
     Bundle(Box<[Value]>),
+    Tuple(Box<[Value]>),
+    Array(Box<[Value]>),
+    List(Box<[Value]>),
+    // (record name, fields)
+    Record(String, Box<[(String, Value)]>),
+    // (union name, variant name, args)
+    Variant(String, String, Box<[Value]>),
+}
+
+fn fmt_values(f: &mut std::fmt::Formatter<'_>, values: &[Value]) -> std::fmt::Result {
+    let mut iter = values.iter().peekable();
+    while let Some(value) = iter.next() {
+        value.fmt(f)?;
+        if iter.peek().is_some() {
+            write!(f, ", ")?;
+        }
+    }
+    Ok(())
 }
 
 impl Display for Value {
@@ -19,17 +46,38 @@ impl Display for Value {
             Value::Float(d) => write!(f, "{d}"),
             Value::String(s) => write!(f, "{s:?}"),
             Value::Bool(b) => write!(f, "{b}"),
-            Value::Func => write!(f, "<fun>"),
-            Value::Bundle(items) => {
+            Value::Func(_) => write!(f, "<fun>"),
+            Value::Bundle(items) | Value::Tuple(items) => {
                 write!(f, "(")?;
-                let mut iter = items.iter().peekable();
-                while let Some(item) = iter.next() {
-                    item.fmt(f)?;
+                fmt_values(f, items)?;
+                write!(f, ")")
+            }
+            Value::Array(items) | Value::List(items) => {
+                write!(f, "[")?;
+                fmt_values(f, items)?;
+                write!(f, "]")
+            }
+            Value::Record(_, fields) => {
+                if fields.is_empty() {
+                    return write!(f, "{{}}");
+                }
+                write!(f, "{{ ")?;
+                let mut iter = fields.iter().peekable();
+                while let Some((name, value)) = iter.next() {
+                    write!(f, "{name} = {value}")?;
                     if iter.peek().is_some() {
                         write!(f, ", ")?;
                     }
                 }
-                write!(f, ")")?;
+                write!(f, " }}")
+            }
+            Value::Variant(_, name, args) => {
+                write!(f, "{name}")?;
+                if !args.is_empty() {
+                    write!(f, "(")?;
+                    fmt_values(f, args)?;
+                    write!(f, ")")?;
+                }
                 Ok(())
             }
         }
@@ -1,5 +1,9 @@
-use super::Value;
-use crate::binary::opcode;
+use super::{
+    CallError, Closure, Layout, LayoutID, LayoutTable, Trace, Value, trace::TRACE_STACK_LEN,
+};
+use crate::binary::{self, opcode, value};
+use byteorder::{LE, ReadBytesExt, WriteBytesExt};
+use std::{collections::BTreeMap, io};
 
 #[derive(PartialEq, Clone, Debug)]
 enum Val {
@@ -18,6 +22,21 @@ struct Frame {
     prev_stack_cursor: usize,
 }
 
+struct Handler {
+    pos: usize,
+    stack_len: usize,
+    frame_len: usize,
+    frame_cursor: usize,
+}
+
+// the state to restore when a call from the host fails
+struct HostCall {
+    stack_len: usize,
+    handler_len: usize,
+    frame_cursor: usize,
+    error: Option<CallError>,
+}
+
 pub struct VM<'a> {
     code: &'a [u8],
     cursor: usize,
@@ -26,11 +45,25 @@ pub struct VM<'a> {
     heap: Heap,
     frame_stack: Vec<Frame>,
     frame_cursor: usize,
+    handlers: Vec<Handler>,
+    functions: BTreeMap<u32, String>,
+    layouts: LayoutTable,
+    result_layout: LayoutID,
+    exports: Vec<(String, LayoutID)>,
+    export_vals: Vec<Val>,
+    trace: Option<Trace>,
+    host_call: Option<HostCall>,
 }
 
 impl<'a> VM<'a> {
-    pub fn new(code: &'a [u8]) -> Self {
-        Self {
+    pub fn new(
+        code: &'a [u8],
+        functions: BTreeMap<u32, String>,
+        layouts: LayoutTable,
+        result_layout: LayoutID,
+        exports: Vec<(String, LayoutID)>,
+    ) -> Self {
+        let mut vm = Self {
             code,
             cursor: 0,
             constants: Vec::new(),
@@ -38,26 +71,117 @@ impl<'a> VM<'a> {
             heap: Heap::new(),
             frame_stack: Vec::new(),
             frame_cursor: 0,
-        }
+            handlers: Vec::new(),
+            functions,
+            layouts,
+            result_layout,
+            exports,
+            export_vals: Vec::new(),
+            trace: None,
+            host_call: None,
+        };
+
+        // initial frame
+        vm.push_call_frame(0);
+        vm
     }
 
     fn fatal(&self, msg: &str) -> ! {
         panic!("FATAL {msg} AT {:0>8}", self.cursor)
     }
 
-    fn store_as_val(&mut self, value: &Value) -> Val {
-        match value {
-            Value::Nil => Val::Nil,
-            Value::Int(n) => Val::Int(*n),
-            Value::Float(f) => Val::Float(*f),
-            Value::String(s) => Val::String(self.heap.alloc_string(s.clone())),
-            Value::Bool(b) => Val::Bool(*b),
-            Value::Func => panic!("unallowed user function value"),
-            Value::Bundle(items) => {
-                let vals = items.iter().map(|item| self.store_as_val(item)).collect();
-                Val::Bundle(self.heap.alloc_val_array(vals))
+    // stores a host value with the shape of the given layout
+    fn store_as_val(&mut self, value: &Value, layout: LayoutID) -> Result<Val, CallError> {
+        let layout = self.layouts.get(layout).clone();
+        let val = match (value, layout) {
+            (Value::Nil, _) => Val::Nil,
+            (Value::Int(n), _) => Val::Int(*n),
+            (Value::Float(f), _) => Val::Float(*f),
+            (Value::String(s), _) => Val::String(self.heap.alloc_string(s.clone())),
+            (Value::Bool(b), _) => Val::Bool(*b),
+            (Value::Func(closure), _) => {
+                let u = HeapIndex(closure.index);
+                if self.heap.deref_closure(u).is_none() {
+                    return Err(CallError::InvalidClosure);
+                }
+                Val::Bundle(u)
             }
-        }
+            (Value::Tuple(items), Layout::Tuple(ids)) if items.len() == ids.len() => {
+                let vals = items
+                    .iter()
+                    .zip(ids)
+                    .map(|(item, id)| self.store_as_val(item, id))
+                    .collect::<Result<_, _>>()?;
+                self.alloc_bundle(vals)
+            }
+            (Value::Array(items), Layout::Array(id)) => {
+                let vals = items
+                    .iter()
+                    .map(|item| self.store_as_val(item, id))
+                    .collect::<Result<_, _>>()?;
+                self.alloc_bundle(vals)
+            }
+            (Value::Bundle(items) | Value::Tuple(items) | Value::Array(items), _) => {
+                let vals = items
+                    .iter()
+                    .map(|item| self.store_as_val(item, LayoutTable::ANY))
+                    .collect::<Result<_, _>>()?;
+                self.alloc_bundle(vals)
+            }
+            (Value::Record(_, fields), Layout::Record(_, field_layouts)) => {
+                // fields are stored in declaration order
+                let vals = field_layouts
+                    .iter()
+                    .map(|(name, id)| {
+                        let Some((_, field)) = fields.iter().find(|(field, _)| field == name)
+                        else {
+                            return Err(CallError::MissingField(name.clone()));
+                        };
+                        self.store_as_val(field, *id)
+                    })
+                    .collect::<Result<_, _>>()?;
+                self.alloc_bundle(vals)
+            }
+            (Value::Variant(_, name, args), Layout::Union(_, variants)) => {
+                let Some(tag) = variants.iter().position(|(variant, _)| variant == name) else {
+                    return Err(CallError::UnknownVariant(name.clone()));
+                };
+                let ids = variants[tag].1.clone();
+                if ids.len() != args.len() {
+                    return Err(CallError::VariantArgCount {
+                        variant: name.clone(),
+                        expected: ids.len(),
+                    });
+                }
+                let vals = args
+                    .iter()
+                    .zip(ids)
+                    .map(|(arg, id)| self.store_as_val(arg, id))
+                    .collect::<Result<_, _>>()?;
+                self.alloc_variant(tag, vals)
+            }
+            (Value::List(items), Layout::List { item, cons_tag }) => {
+                let mut list = self.alloc_variant(1 - cons_tag, Vec::new());
+                for value in items.iter().rev() {
+                    let head = self.store_as_val(value, item)?;
+                    list = self.alloc_variant(cons_tag, vec![head, list]);
+                }
+                list
+            }
+            (Value::Record(..) | Value::Variant(..) | Value::List(..), _) => {
+                return Err(CallError::Untyped(value.to_string()));
+            }
+        };
+        Ok(val)
+    }
+
+    fn alloc_bundle(&mut self, vals: Vec<Val>) -> Val {
+        Val::Bundle(self.heap.alloc_val_array(vals))
+    }
+
+    fn alloc_variant(&mut self, tag: usize, args: Vec<Val>) -> Val {
+        let args = self.alloc_bundle(args);
+        self.alloc_bundle(vec![Val::Int(tag as i64), args])
     }
 
     fn to_user_val(&self, val: &Val) -> Value {
@@ -67,7 +191,11 @@ impl<'a> VM<'a> {
             Val::Float(f) => Value::Float(*f),
             Val::String(u) => Value::String(self.heap.deref_string(*u).to_string()),
             Val::Bool(b) => Value::Bool(*b),
-            Val::Func(_) => Value::Func,
+            Val::Func(_) => self.fatal("bare function address given to the host"),
+            Val::Bundle(u) if self.heap.deref_closure(*u).is_some() => Value::Func(Closure {
+                index: u.0,
+                layout: LayoutTable::ANY,
+            }),
             Val::Bundle(u) => {
                 let values = self
                     .heap
@@ -80,473 +208,939 @@ impl<'a> VM<'a> {
         }
     }
 
+    // reads a value back with the shape of its type
+    // falls back to untyped values whenever the value doesn't fit its layout
+    fn to_typed_val(&self, val: &Val, layout: LayoutID) -> Value {
+        let Val::Bundle(u) = val else {
+            return self.to_user_val(val);
+        };
+        let items = self.heap.deref_val_array(*u);
+
+        match self.layouts.get(layout) {
+            Layout::Func(..) if self.heap.deref_closure(*u).is_some() => {
+                Value::Func(Closure { index: u.0, layout })
+            }
+            Layout::Tuple(ids) if ids.len() == items.len() => Value::Tuple(
+                items
+                    .iter()
+                    .zip(ids)
+                    .map(|(item, id)| self.to_typed_val(item, *id))
+                    .collect(),
+            ),
+            Layout::Array(id) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.to_typed_val(item, *id))
+                    .collect(),
+            ),
+            Layout::Record(name, fields) if fields.len() == items.len() => Value::Record(
+                name.clone(),
+                items
+                    .iter()
+                    .zip(fields)
+                    .map(|(item, (field, id))| (field.clone(), self.to_typed_val(item, *id)))
+                    .collect(),
+            ),
+            Layout::Union(name, variants) => {
+                let Some((variant, args, ids)) = self.read_variant(items, variants) else {
+                    return self.to_user_val(val);
+                };
+                Value::Variant(
+                    name.clone(),
+                    variant.clone(),
+                    args.iter()
+                        .zip(ids)
+                        .map(|(arg, id)| self.to_typed_val(arg, *id))
+                        .collect(),
+                )
+            }
+            Layout::List { item, cons_tag } => {
+                let mut values = Vec::new();
+                let mut items = items;
+                loop {
+                    match items {
+                        [Val::Int(tag), Val::Bundle(args)] => {
+                            if *tag as usize != *cons_tag {
+                                break;
+                            }
+                            let [head, Val::Bundle(tail)] = self.heap.deref_val_array(*args) else {
+                                return self.to_user_val(val);
+                            };
+                            values.push(self.to_typed_val(head, *item));
+                            items = self.heap.deref_val_array(*tail);
+                        }
+                        _ => return self.to_user_val(val),
+                    }
+                }
+                Value::List(values.into())
+            }
+            _ => self.to_user_val(val),
+        }
+    }
+
+    // (variant name, variant args, variant arg layouts)
+    fn read_variant<'v>(
+        &'v self,
+        items: &[Val],
+        variants: &'v [(String, Box<[LayoutID]>)],
+    ) -> Option<(&'v String, &'v [Val], &'v [LayoutID])> {
+        let [Val::Int(tag), Val::Bundle(args)] = items else {
+            return None;
+        };
+        let (variant, ids) = variants.get(*tag as usize)?;
+        let args = self.heap.deref_val_array(*args);
+        (args.len() == ids.len()).then_some((variant, args, ids))
+    }
+
     pub fn add_constant(&mut self, value: &Value) {
-        let val = self.store_as_val(value);
+        let val = match self.store_as_val(value, LayoutTable::ANY) {
+            Ok(val) => val,
+            Err(e) => self.fatal(format!("invalid constant: {e}").as_str()),
+        };
         self.constants.push(val);
     }
 
+    // writes the whole state of the vm, along with its code
+    // so that it can be resumed later on with `VM::load`
+    pub fn save<W: io::Write>(&self, w: &mut W) -> binary::Result<()> {
+        binary::write_snapshot_magic(w)?;
+        binary::write_bytes(w, self.code)?;
+        binary::write_function_table(w, self.functions.iter())?;
+        binary::write_layout_table(w, &self.layouts, self.result_layout)?;
+        binary::write_export_table(w, &self.exports)?;
+
+        self.heap.write(w)?;
+        write_vals(w, &self.constants)?;
+        write_vals(w, &self.stack)?;
+        write_vals(w, &self.export_vals)?;
+
+        w.write_u64::<LE>(self.frame_stack.len() as u64)?;
+        for frame in &self.frame_stack {
+            w.write_u64::<LE>(frame.pos as u64)?;
+            w.write_u64::<LE>(frame.stack_cursor as u64)?;
+            w.write_u64::<LE>(frame.prev_stack_cursor as u64)?;
+        }
+
+        w.write_u64::<LE>(self.handlers.len() as u64)?;
+        for handler in &self.handlers {
+            w.write_u64::<LE>(handler.pos as u64)?;
+            w.write_u64::<LE>(handler.stack_len as u64)?;
+            w.write_u64::<LE>(handler.frame_len as u64)?;
+            w.write_u64::<LE>(handler.frame_cursor as u64)?;
+        }
+
+        w.write_u64::<LE>(self.cursor as u64)?;
+        w.write_u64::<LE>(self.frame_cursor as u64)?;
+        Ok(())
+    }
+
+    // reads the code of a saved vm, which must then be passed to `VM::load`
+    pub fn read_code<R: io::Read>(r: &mut R) -> binary::Result<Vec<u8>> {
+        binary::read_snapshot_magic(r)?;
+        binary::read_bytes(r)
+    }
+
+    // restores the state of a saved vm, right after its code was read
+    pub fn load<R: io::Read>(code: &'a [u8], r: &mut R) -> binary::Result<Self> {
+        let functions = binary::read_function_table(r)?;
+        let (layouts, result_layout) = binary::read_layout_table(r)?;
+        let exports = binary::read_export_table(r)?;
+        let heap = Heap::read(r)?;
+        let constants = read_vals(r, &heap)?;
+        let stack = read_vals(r, &heap)?;
+        let export_vals = read_vals(r, &heap)?;
+
+        let frame_count = r.read_u64::<LE>()? as usize;
+        let mut frame_stack = Vec::with_capacity(frame_count.min(MAX_PREALLOCATED));
+        for _ in 0..frame_count {
+            frame_stack.push(Frame {
+                pos: r.read_u64::<LE>()? as usize,
+                stack_cursor: r.read_u64::<LE>()? as usize,
+                prev_stack_cursor: r.read_u64::<LE>()? as usize,
+            });
+        }
+
+        let handler_count = r.read_u64::<LE>()? as usize;
+        let mut handlers = Vec::with_capacity(handler_count.min(MAX_PREALLOCATED));
+        for _ in 0..handler_count {
+            handlers.push(Handler {
+                pos: r.read_u64::<LE>()? as usize,
+                stack_len: r.read_u64::<LE>()? as usize,
+                frame_len: r.read_u64::<LE>()? as usize,
+                frame_cursor: r.read_u64::<LE>()? as usize,
+            });
+        }
+
+        let cursor = r.read_u64::<LE>()? as usize;
+        let frame_cursor = r.read_u64::<LE>()? as usize;
+
+        Ok(Self {
+            code,
+            cursor,
+            constants,
+            stack,
+            heap,
+            frame_stack,
+            frame_cursor,
+            handlers,
+            functions,
+            layouts,
+            result_layout,
+            exports,
+            export_vals,
+            trace: None,
+            host_call: None,
+        })
+    }
+
     pub fn run(&mut self) -> Value {
-        // initial frame
-        self.push_call_frame(0);
-
-        loop {
-            let op = self.read_u8();
-            match op {
-                opcode::load_fun => {
-                    let pos = self.read_u32();
-                    self.push(Val::Func(pos));
-                }
-                opcode::bundle => {
-                    let count = self.read_u8() as usize;
-                    let values = self.stack.split_off(self.stack.len() - count);
-                    let bundle = Val::Bundle(self.heap.alloc_val_array(values));
-                    self.push(bundle);
-                }
-                opcode::bundle_big => {
-                    let count = self.read_u64() as usize;
-                    let values = self.stack.split_off(self.stack.len() - count);
-                    let bundle = Val::Bundle(self.heap.alloc_val_array(values));
-                    self.push(bundle);
-                }
-                opcode::index_dup => {
-                    let index = self.read_u8() as usize;
-                    let &Val::Bundle(u) = self.peek() else {
-                        self.fatal("invalid index on a non-bundle value");
-                    };
-                    let value = self.heap.deref_val(u, index);
-                    self.push(value.clone());
-                }
-                opcode::index_big_dup => {
-                    let index = self.read_u64() as usize;
-                    let &Val::Bundle(u) = self.peek() else {
-                        self.fatal("invalid index on a non-bundle value");
-                    };
-                    let value = self.heap.deref_val(u, index);
-                    self.push(value.clone());
-                }
-                opcode::index => {
-                    let index = self.read_u8() as usize;
-                    let Val::Bundle(u) = self.pop() else {
-                        self.fatal("invalid index on a non-bundle value");
-                    };
-                    let value = self.heap.deref_val(u, index);
-                    self.push(value.clone());
-                }
-                opcode::index_big => {
-                    let index = self.read_u64() as usize;
-                    let Val::Bundle(u) = self.pop() else {
-                        self.fatal("invalid index on a non-bundle value");
-                    };
-                    let value = self.heap.deref_val(u, index);
-                    self.push(value.clone());
-                }
-                opcode::index_dyn => {
-                    let Val::Int(i) = self.pop() else {
-                        self.fatal("invalid (dynamic) index with non-integer index")
-                    };
-                    let Val::Bundle(u) = self.pop() else {
-                        self.fatal("invalid (dynamic) index on a non-bundle value");
-                    };
+        while !self.step() {}
+        self.finish()
+    }
+
+    // executes at most the given amount of instructions
+    // returns the program's result if it halted in the meantime
+    pub fn run_steps(&mut self, steps: usize) -> Option<Value> {
+        for _ in 0..steps {
+            if self.step() {
+                return Some(self.finish());
+            }
+        }
+        None
+    }
 
-                    let value = self.heap.deref_val(u, i as usize);
+    // the program returns its result along with its exported functions
+    fn finish(&mut self) -> Value {
+        let result = self.pop();
+        debug_assert!(self.stack.is_empty(), "non-empty stack after halting");
 
-                    self.push(value.clone());
-                }
-                opcode::spill => {
-                    let offset = self.read_u16() as usize;
-                    let index = self.stack.len() - offset - 1;
-                    let Val::Bundle(u) = self.stack.remove(index) else {
-                        self.fatal("invalid spill on a non-bundle value");
-                    };
+        let Val::Bundle(u) = result else {
+            self.fatal("invalid program result");
+        };
+        let [result, Val::Bundle(u_exports)] = self.heap.deref_val_array(u) else {
+            self.fatal("invalid program result bundle");
+        };
+        let result = result.clone();
+        self.export_vals = self.heap.deref_val_array(*u_exports).to_vec();
 
-                    let values = self.heap.deref_val_array(u);
-                    self.stack.extend_from_slice(values);
-                    self.stack[index..].rotate_right(values.len());
-                }
-                opcode::add => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a + b),
-                        (Val::Float(a), Val::Float(b)) => Val::Float(a + b),
-                        (Val::String(u_a), Val::String(u_b)) => {
-                            let mut ab = String::new();
-                            ab.push_str(self.heap.deref_string(u_a));
-                            ab.push_str(self.heap.deref_string(u_b));
-                            let u_ab = self.heap.alloc_string(ab);
-                            Val::String(u_ab)
-                        }
-                        _ => self.fatal("invalid 'add' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::sub => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a - b),
-                        (Val::Float(a), Val::Float(b)) => Val::Float(a - b),
-                        _ => self.fatal("invalid 'sub' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::mul => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a * b),
-                        (Val::Float(a), Val::Float(b)) => Val::Float(a * b),
-                        _ => self.fatal("invalid 'mul' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::div => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a / b),
-                        (Val::Float(a), Val::Float(b)) => Val::Float(a / b),
-                        _ => self.fatal("invalid 'mul' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::modulo => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a % b),
-                        (Val::Float(a), Val::Float(b)) => Val::Float(a % b),
-                        _ => self.fatal("invalid 'modulo' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::pow => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Float(a), Val::Float(b)) => Val::Float(a.powf(b)),
-                        _ => self.fatal("invalid 'pow' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::and => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a & b),
-                        (Val::Bool(a), Val::Bool(b)) => Val::Bool(a & b),
-                        _ => self.fatal("invalid 'and' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::or => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a | b),
-                        (Val::Bool(a), Val::Bool(b)) => Val::Bool(a | b),
-                        _ => self.fatal("invalid 'and' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::xor => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Int(a ^ b),
-                        (Val::Bool(a), Val::Bool(b)) => Val::Bool(a ^ b),
-                        _ => self.fatal("invalid 'and' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::exp => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(a) => Val::Float(a.exp()),
-                        _ => self.fatal("invalid 'exp' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::ln => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(a) => Val::Float(a.ln()),
-                        _ => self.fatal("invalid 'ln' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::pos => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Int(a) => Val::Int(a),
-                        Val::Float(a) => Val::Float(a),
-                        Val::Bool(a) => Val::Bool(a),
-                        _ => self.fatal("invalid 'pos' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::neg => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Int(a) => Val::Int(-a),
-                        Val::Float(a) => Val::Float(-a),
-                        _ => self.fatal("invalid 'neg' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::not => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Int(a) => Val::Int(!a),
-                        Val::Bool(a) => Val::Bool(!a),
-                        _ => self.fatal("invalid 'not' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::eq => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Bool(a == b),
-                        (Val::Float(a), Val::Float(b)) => Val::Bool(a == b),
-                        (Val::String(a), Val::String(b)) => {
-                            Val::Bool(self.heap.deref_string(a) == self.heap.deref_string(b))
-                        }
-                        (Val::Bool(a), Val::Bool(b)) => Val::Bool(a == b),
-                        _ => self.fatal("invalid 'eq' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::ne => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Bool(a != b),
-                        (Val::Float(a), Val::Float(b)) => Val::Bool(a != b),
-                        (Val::String(a), Val::String(b)) => {
-                            Val::Bool(self.heap.deref_string(a) != self.heap.deref_string(b))
-                        }
-                        (Val::Bool(a), Val::Bool(b)) => Val::Bool(a != b),
-                        _ => self.fatal("invalid 'ne' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::lt => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Bool(a < b),
-                        (Val::Float(a), Val::Float(b)) => Val::Bool(a < b),
-                        (Val::String(a), Val::String(b)) => {
-                            Val::Bool(self.heap.deref_string(a) < self.heap.deref_string(b))
-                        }
-                        _ => self.fatal("invalid 'lt' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::le => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Bool(a <= b),
-                        (Val::Float(a), Val::Float(b)) => Val::Bool(a <= b),
-                        (Val::String(a), Val::String(b)) => {
-                            Val::Bool(self.heap.deref_string(a) <= self.heap.deref_string(b))
-                        }
-                        _ => self.fatal("invalid 'le' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::gt => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Bool(a > b),
-                        (Val::Float(a), Val::Float(b)) => Val::Bool(a > b),
-                        (Val::String(a), Val::String(b)) => {
-                            Val::Bool(self.heap.deref_string(a) > self.heap.deref_string(b))
-                        }
-                        _ => self.fatal("invalid 'gt' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::ge => {
-                    let right = self.pop();
-                    let left = self.pop();
-                    let result = match (left, right) {
-                        (Val::Int(a), Val::Int(b)) => Val::Bool(a >= b),
-                        (Val::Float(a), Val::Float(b)) => Val::Bool(a >= b),
-                        (Val::String(a), Val::String(b)) => {
-                            Val::Bool(self.heap.deref_string(a) >= self.heap.deref_string(b))
-                        }
-                        _ => self.fatal("invalid 'ge' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::sin => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(x) => Val::Float(x.sin()),
-                        _ => self.fatal("invalid 'sin' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::cos => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(x) => Val::Float(x.cos()),
-                        _ => self.fatal("invalid 'cos' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::tan => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(x) => Val::Float(x.tan()),
-                        _ => self.fatal("invalid 'tan' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::asin => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(x) => Val::Float(x.asin()),
-                        _ => self.fatal("invalid 'asin' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::acos => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(x) => Val::Float(x.acos()),
-                        _ => self.fatal("invalid 'acos' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::atan => {
-                    let val = self.pop();
-                    let result = match val {
-                        Val::Float(x) => Val::Float(x.atan()),
-                        _ => self.fatal("invalid 'atan' operation"),
-                    };
-                    self.push(result);
-                }
-                opcode::load_const => {
-                    let index = self.read_u16() as usize;
-                    self.push(self.constants[index].clone());
-                }
-                opcode::load_local => {
-                    let local = self.read_u8() as usize;
-                    let index = self.frame_cursor + local;
-                    let value = self.stack[index].clone();
-                    self.push(value);
-                }
-                opcode::set_local => {
-                    let value = self.pop();
-                    let local = self.read_u8() as usize;
-                    let index = self.frame_cursor + local;
-                    self.stack[index] = value;
+        self.to_typed_val(&result, self.result_layout)
+    }
+
+    // a public function of the program, once it has run
+    pub fn get_function(&self, name: &str) -> Result<Closure, CallError> {
+        let unknown = || CallError::UnknownFunction(name.to_string());
+        let mut indices = self
+            .exports
+            .iter()
+            .enumerate()
+            .filter(|(_, (export, _))| export == name)
+            .map(|(index, _)| index);
+        let index = indices.next().ok_or_else(unknown)?;
+        // functions of different files may share their name
+        if indices.next().is_some() {
+            return Err(CallError::AmbiguousFunction(name.to_string()));
+        }
+        let Some(Val::Bundle(u)) = self.export_vals.get(index) else {
+            return Err(unknown());
+        };
+        Ok(Closure {
+            index: u.0,
+            layout: self.exports[index].1,
+        })
+    }
+
+    // calls a closure once the program has run, and runs it to completion
+    pub fn call(&mut self, closure: Closure, args: &[Value]) -> Result<Value, CallError> {
+        if !self.frame_stack.is_empty() {
+            return Err(CallError::Running);
+        }
+
+        let (arg_layouts, ret_layout) = match self.layouts.get(closure.layout) {
+            Layout::Func(arg_layouts, ret) => (arg_layouts.clone(), *ret),
+            _ => (vec![LayoutTable::ANY; args.len()].into(), LayoutTable::ANY),
+        };
+        if arg_layouts.len() != args.len() {
+            return Err(CallError::ArgCount {
+                expected: arg_layouts.len(),
+                given: args.len(),
+            });
+        }
+
+        let vals = args
+            .iter()
+            .zip(arg_layouts)
+            .map(|(arg, layout)| self.store_as_val(arg, layout))
+            .collect::<Result<Vec<_>, _>>()?;
+        if self.heap.deref_closure(HeapIndex(closure.index)).is_none() {
+            return Err(CallError::InvalidClosure);
+        }
+        self.host_call = Some(HostCall {
+            stack_len: self.stack.len(),
+            handler_len: self.handlers.len(),
+            frame_cursor: self.frame_cursor,
+            error: None,
+        });
+        self.stack.extend(vals);
+        self.enter_closure(HeapIndex(closure.index), args.len());
+
+        while !self.step() {}
+        let host_call = self.host_call.take().expect("host call state was lost");
+        if let Some(error) = host_call.error {
+            self.frame_stack.clear();
+            self.stack.truncate(host_call.stack_len);
+            self.handlers.truncate(host_call.handler_len);
+            self.frame_cursor = host_call.frame_cursor;
+            return Err(error);
+        }
+
+        let result = self.pop();
+        Ok(self.to_typed_val(&result, ret_layout))
+    }
+
+    // failures of a call from the host are returned to it instead of being fatal
+    // returns whether the vm should halt
+    fn fail_host_call(&mut self, error: CallError) -> bool {
+        let Some(host_call) = &mut self.host_call else {
+            return false;
+        };
+        host_call.error = Some(error);
+        true
+    }
+
+    // pushes the captured values of a closure, and jumps into it
+    fn enter_closure(&mut self, u: HeapIndex, arg_count: usize) {
+        let Some((addr, captured)) = self.heap.deref_closure(u) else {
+            self.fatal("invalid function bundle");
+        };
+
+        let captured_count = captured.len();
+        self.stack.extend_from_slice(captured);
+        self.push_call_frame(arg_count + captured_count);
+        self.cursor = addr as usize;
+    }
+
+    // returns the names of the trace filter which match no function of the program
+    pub fn set_trace(&mut self, trace: Trace) -> Vec<String> {
+        let unmatched = trace.unmatched_filter(self.functions.values().map(String::as_str));
+        self.trace = Some(trace);
+        unmatched
+    }
+
+    fn trace_instruction(&mut self) {
+        let Some(trace) = &self.trace else {
+            return;
+        };
+
+        let pos = self.cursor;
+        let function = self
+            .functions
+            .range(..=pos as u32)
+            .next_back()
+            .map(|(_, name)| name.as_str())
+            .unwrap_or("?");
+        if !trace.is_traced(function) {
+            return;
+        }
+
+        let opcode = match binary::read_opcode(&mut &self.code[pos..]) {
+            Ok(opcode) => format!("{opcode:?}"),
+            Err(_) => format!("<invalid 0x{:x}>", self.code[pos]),
+        };
+        let top = self
+            .stack
+            .iter()
+            .rev()
+            .take(TRACE_STACK_LEN)
+            .rev()
+            .map(|val| match val {
+                Val::Func(_) => "<fun>".to_string(),
+                _ => self.to_user_val(val).to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line = format!(
+            "{pos:0>8} | {function} | depth {} | {opcode} | [{top}]",
+            self.frame_stack.len()
+        );
+
+        if let Some(trace) = &mut self.trace {
+            trace.log(&line);
+        }
+    }
+
+    // executes a single instruction, returns true if the program halted
+    fn step(&mut self) -> bool {
+        self.trace_instruction();
+
+        let op = self.read_u8();
+        match op {
+            opcode::load_fun => {
+                let pos = self.read_u32();
+                self.push(Val::Func(pos));
+            }
+            opcode::bundle => {
+                let count = self.read_u8() as usize;
+                let values = self.stack.split_off(self.stack.len() - count);
+                let bundle = Val::Bundle(self.heap.alloc_val_array(values));
+                self.push(bundle);
+            }
+            opcode::bundle_big => {
+                let count = self.read_u64() as usize;
+                let values = self.stack.split_off(self.stack.len() - count);
+                let bundle = Val::Bundle(self.heap.alloc_val_array(values));
+                self.push(bundle);
+            }
+            opcode::index_dup => {
+                let index = self.read_u8() as usize;
+                let &Val::Bundle(u) = self.peek() else {
+                    self.fatal("invalid index on a non-bundle value");
+                };
+                let value = self.heap.deref_val(u, index);
+                self.push(value.clone());
+            }
+            opcode::index_big_dup => {
+                let index = self.read_u64() as usize;
+                let &Val::Bundle(u) = self.peek() else {
+                    self.fatal("invalid index on a non-bundle value");
+                };
+                let value = self.heap.deref_val(u, index);
+                self.push(value.clone());
+            }
+            opcode::index => {
+                let index = self.read_u8() as usize;
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid index on a non-bundle value");
+                };
+                let value = self.heap.deref_val(u, index);
+                self.push(value.clone());
+            }
+            opcode::index_big => {
+                let index = self.read_u64() as usize;
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid index on a non-bundle value");
+                };
+                let value = self.heap.deref_val(u, index);
+                self.push(value.clone());
+            }
+            opcode::index_dyn => {
+                let Val::Int(i) = self.pop() else {
+                    self.fatal("invalid (dynamic) index with non-integer index")
+                };
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid (dynamic) index on a non-bundle value");
+                };
+
+                let len = self.heap.deref_val_array(u).len();
+                if i < 0 || i as usize >= len {
+                    self.fatal(
+                        format!("index {i} out of bounds for array of length {len}").as_str(),
+                    );
                 }
-                opcode::load_nil => {
-                    self.push(Val::Nil);
+                let value = self.heap.deref_val(u, i as usize);
+
+                self.push(value.clone());
+            }
+            opcode::array_len => {
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid array length of a non-bundle value");
+                };
+
+                let len = self.heap.deref_val_array(u).len();
+                self.push(Val::Int(len as i64));
+            }
+            opcode::array_set => {
+                let value = self.pop();
+                let Val::Int(i) = self.pop() else {
+                    self.fatal("invalid array update with non-integer index")
+                };
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid array update on a non-bundle value");
+                };
+
+                let len = self.heap.deref_val_array(u).len();
+                if i < 0 || i as usize >= len {
+                    self.fatal(
+                        format!("index {i} out of bounds for array of length {len}").as_str(),
+                    );
                 }
-                opcode::jump => {
-                    let pos = self.read_u32() as usize;
-                    self.cursor = pos;
+                self.heap.deref_val_array_mut(u)[i as usize] = value;
+
+                let unit = self.heap.alloc_val_array(Vec::new());
+                self.push(Val::Bundle(unit));
+            }
+            opcode::array_push => {
+                let value = self.pop();
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid array push on a non-bundle value");
+                };
+
+                self.heap.deref_val_array_mut(u).push(value);
+
+                let unit = self.heap.alloc_val_array(Vec::new());
+                self.push(Val::Bundle(unit));
+            }
+            opcode::array_pop => {
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid array pop on a non-bundle value");
+                };
+
+                let Some(value) = self.heap.deref_val_array_mut(u).pop() else {
+                    self.fatal("cannot pop from an empty array");
+                };
+                self.push(value);
+            }
+            opcode::array_slice => {
+                let Val::Int(end) = self.pop() else {
+                    self.fatal("invalid array slice with non-integer end")
+                };
+                let Val::Int(start) = self.pop() else {
+                    self.fatal("invalid array slice with non-integer start")
+                };
+                let Val::Bundle(u) = self.pop() else {
+                    self.fatal("invalid array slice on a non-bundle value");
+                };
+
+                let vals = self.heap.deref_val_array(u);
+                let len = vals.len();
+                if start < 0 || end < start || end as usize > len {
+                    self.fatal(
+                        format!("slice {start}..{end} out of bounds for array of length {len}")
+                            .as_str(),
+                    );
                 }
-                opcode::jump_if => {
-                    let pos = self.read_u32() as usize;
-                    let Val::Bool(b) = self.pop() else {
-                        self.fatal("found non-boolean value as jump_if condition");
-                    };
-                    if b {
-                        self.cursor = pos;
+                let slice = vals[start as usize..end as usize].to_vec();
+
+                let sliced = self.heap.alloc_val_array(slice);
+                self.push(Val::Bundle(sliced));
+            }
+            opcode::spill => {
+                let offset = self.read_u16() as usize;
+                let index = self.stack.len() - offset - 1;
+                let Val::Bundle(u) = self.stack.remove(index) else {
+                    self.fatal("invalid spill on a non-bundle value");
+                };
+
+                let values = self.heap.deref_val_array(u);
+                self.stack.extend_from_slice(values);
+                self.stack[index..].rotate_right(values.len());
+            }
+            opcode::add => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a + b),
+                    (Val::Float(a), Val::Float(b)) => Val::Float(a + b),
+                    (Val::String(u_a), Val::String(u_b)) => {
+                        let mut ab = String::new();
+                        ab.push_str(self.heap.deref_string(u_a));
+                        ab.push_str(self.heap.deref_string(u_b));
+                        let u_ab = self.heap.alloc_string(ab);
+                        Val::String(u_ab)
                     }
-                }
-                opcode::jump_if_not => {
-                    let pos = self.read_u32() as usize;
-                    let Val::Bool(b) = self.pop() else {
-                        self.fatal("found non-boolean value as jump_if_not condition");
-                    };
-                    if !b {
-                        self.cursor = pos;
+                    _ => self.fatal("invalid 'add' operation"),
+                };
+                self.push(result);
+            }
+            opcode::sub => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a - b),
+                    (Val::Float(a), Val::Float(b)) => Val::Float(a - b),
+                    _ => self.fatal("invalid 'sub' operation"),
+                };
+                self.push(result);
+            }
+            opcode::mul => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a * b),
+                    (Val::Float(a), Val::Float(b)) => Val::Float(a * b),
+                    _ => self.fatal("invalid 'mul' operation"),
+                };
+                self.push(result);
+            }
+            opcode::div => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a / b),
+                    (Val::Float(a), Val::Float(b)) => Val::Float(a / b),
+                    _ => self.fatal("invalid 'mul' operation"),
+                };
+                self.push(result);
+            }
+            opcode::modulo => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a % b),
+                    (Val::Float(a), Val::Float(b)) => Val::Float(a % b),
+                    _ => self.fatal("invalid 'modulo' operation"),
+                };
+                self.push(result);
+            }
+            opcode::pow => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Float(a), Val::Float(b)) => Val::Float(a.powf(b)),
+                    _ => self.fatal("invalid 'pow' operation"),
+                };
+                self.push(result);
+            }
+            opcode::and => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a & b),
+                    (Val::Bool(a), Val::Bool(b)) => Val::Bool(a & b),
+                    _ => self.fatal("invalid 'and' operation"),
+                };
+                self.push(result);
+            }
+            opcode::or => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a | b),
+                    (Val::Bool(a), Val::Bool(b)) => Val::Bool(a | b),
+                    _ => self.fatal("invalid 'and' operation"),
+                };
+                self.push(result);
+            }
+            opcode::xor => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Int(a ^ b),
+                    (Val::Bool(a), Val::Bool(b)) => Val::Bool(a ^ b),
+                    _ => self.fatal("invalid 'and' operation"),
+                };
+                self.push(result);
+            }
+            opcode::exp => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(a) => Val::Float(a.exp()),
+                    _ => self.fatal("invalid 'exp' operation"),
+                };
+                self.push(result);
+            }
+            opcode::ln => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(a) => Val::Float(a.ln()),
+                    _ => self.fatal("invalid 'ln' operation"),
+                };
+                self.push(result);
+            }
+            opcode::pos => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Int(a) => Val::Int(a),
+                    Val::Float(a) => Val::Float(a),
+                    Val::Bool(a) => Val::Bool(a),
+                    _ => self.fatal("invalid 'pos' operation"),
+                };
+                self.push(result);
+            }
+            opcode::neg => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Int(a) => Val::Int(-a),
+                    Val::Float(a) => Val::Float(-a),
+                    _ => self.fatal("invalid 'neg' operation"),
+                };
+                self.push(result);
+            }
+            opcode::not => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Int(a) => Val::Int(!a),
+                    Val::Bool(a) => Val::Bool(!a),
+                    _ => self.fatal("invalid 'not' operation"),
+                };
+                self.push(result);
+            }
+            opcode::eq => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Bool(a == b),
+                    (Val::Float(a), Val::Float(b)) => Val::Bool(a == b),
+                    (Val::String(a), Val::String(b)) => {
+                        Val::Bool(self.heap.deref_string(a) == self.heap.deref_string(b))
                     }
-                }
-                opcode::jump_eq => {
-                    let pos = self.read_u32() as usize;
-                    let right = self.pop();
-                    let left = self.pop();
-                    if left == right {
-                        self.cursor = pos;
+                    (Val::Bool(a), Val::Bool(b)) => Val::Bool(a == b),
+                    _ => self.fatal("invalid 'eq' operation"),
+                };
+                self.push(result);
+            }
+            opcode::ne => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Bool(a != b),
+                    (Val::Float(a), Val::Float(b)) => Val::Bool(a != b),
+                    (Val::String(a), Val::String(b)) => {
+                        Val::Bool(self.heap.deref_string(a) != self.heap.deref_string(b))
                     }
-                }
-                opcode::jump_ne => {
-                    let pos = self.read_u32() as usize;
-                    let right = self.pop();
-                    let left = self.pop();
-                    if left != right {
-                        self.cursor = pos;
+                    (Val::Bool(a), Val::Bool(b)) => Val::Bool(a != b),
+                    _ => self.fatal("invalid 'ne' operation"),
+                };
+                self.push(result);
+            }
+            opcode::lt => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Bool(a < b),
+                    (Val::Float(a), Val::Float(b)) => Val::Bool(a < b),
+                    (Val::String(a), Val::String(b)) => {
+                        Val::Bool(self.heap.deref_string(a) < self.heap.deref_string(b))
                     }
+                    _ => self.fatal("invalid 'lt' operation"),
+                };
+                self.push(result);
+            }
+            opcode::le => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Bool(a <= b),
+                    (Val::Float(a), Val::Float(b)) => Val::Bool(a <= b),
+                    (Val::String(a), Val::String(b)) => {
+                        Val::Bool(self.heap.deref_string(a) <= self.heap.deref_string(b))
+                    }
+                    _ => self.fatal("invalid 'le' operation"),
+                };
+                self.push(result);
+            }
+            opcode::gt => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Bool(a > b),
+                    (Val::Float(a), Val::Float(b)) => Val::Bool(a > b),
+                    (Val::String(a), Val::String(b)) => {
+                        Val::Bool(self.heap.deref_string(a) > self.heap.deref_string(b))
+                    }
+                    _ => self.fatal("invalid 'gt' operation"),
+                };
+                self.push(result);
+            }
+            opcode::ge => {
+                let right = self.pop();
+                let left = self.pop();
+                let result = match (left, right) {
+                    (Val::Int(a), Val::Int(b)) => Val::Bool(a >= b),
+                    (Val::Float(a), Val::Float(b)) => Val::Bool(a >= b),
+                    (Val::String(a), Val::String(b)) => {
+                        Val::Bool(self.heap.deref_string(a) >= self.heap.deref_string(b))
+                    }
+                    _ => self.fatal("invalid 'ge' operation"),
+                };
+                self.push(result);
+            }
+            opcode::to_str => {
+                let s = match self.pop() {
+                    Val::Int(a) => a.to_string(),
+                    Val::Float(a) => format!("{a:?}"),
+                    _ => self.fatal("invalid 'to_str' operation"),
+                };
+                let u = self.heap.alloc_string(s);
+                self.push(Val::String(u));
+            }
+            opcode::sin => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(x) => Val::Float(x.sin()),
+                    _ => self.fatal("invalid 'sin' operation"),
+                };
+                self.push(result);
+            }
+            opcode::cos => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(x) => Val::Float(x.cos()),
+                    _ => self.fatal("invalid 'cos' operation"),
+                };
+                self.push(result);
+            }
+            opcode::tan => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(x) => Val::Float(x.tan()),
+                    _ => self.fatal("invalid 'tan' operation"),
+                };
+                self.push(result);
+            }
+            opcode::asin => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(x) => Val::Float(x.asin()),
+                    _ => self.fatal("invalid 'asin' operation"),
+                };
+                self.push(result);
+            }
+            opcode::acos => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(x) => Val::Float(x.acos()),
+                    _ => self.fatal("invalid 'acos' operation"),
+                };
+                self.push(result);
+            }
+            opcode::atan => {
+                let val = self.pop();
+                let result = match val {
+                    Val::Float(x) => Val::Float(x.atan()),
+                    _ => self.fatal("invalid 'atan' operation"),
+                };
+                self.push(result);
+            }
+            opcode::load_const => {
+                let index = self.read_u16() as usize;
+                self.push(self.constants[index].clone());
+            }
+            opcode::load_local => {
+                let local = self.read_u8() as usize;
+                let index = self.frame_cursor + local;
+                let value = self.stack[index].clone();
+                self.push(value);
+            }
+            opcode::set_local => {
+                let value = self.pop();
+                let local = self.read_u8() as usize;
+                let index = self.frame_cursor + local;
+                self.stack[index] = value;
+            }
+            opcode::load_nil => {
+                self.push(Val::Nil);
+            }
+            opcode::jump => {
+                let pos = self.read_u32() as usize;
+                self.cursor = pos;
+            }
+            opcode::jump_if => {
+                let pos = self.read_u32() as usize;
+                let Val::Bool(b) = self.pop() else {
+                    self.fatal("found non-boolean value as jump_if condition");
+                };
+                if b {
+                    self.cursor = pos;
                 }
-                opcode::do_frame => {
-                    self.push_frame();
+            }
+            opcode::jump_if_not => {
+                let pos = self.read_u32() as usize;
+                let Val::Bool(b) = self.pop() else {
+                    self.fatal("found non-boolean value as jump_if_not condition");
+                };
+                if !b {
+                    self.cursor = pos;
                 }
-                opcode::end_frame => {
-                    let value = self.pop();
-                    self.pop_frame();
-                    self.push(value);
+            }
+            opcode::jump_eq => {
+                let pos = self.read_u32() as usize;
+                let right = self.pop();
+                let left = self.pop();
+                if left == right {
+                    self.cursor = pos;
                 }
-                opcode::call => {
-                    let arg_count = self.read_u8() as usize;
+            }
+            opcode::jump_ne => {
+                let pos = self.read_u32() as usize;
+                let right = self.pop();
+                let left = self.pop();
+                if left != right {
+                    self.cursor = pos;
+                }
+            }
+            opcode::do_frame => {
+                self.push_frame();
+            }
+            opcode::end_frame => {
+                let value = self.pop();
+                self.pop_frame();
+                self.push(value);
+            }
+            opcode::call => {
+                let arg_count = self.read_u8() as usize;
 
-                    let fun_bundle = self.pop();
-                    let Val::Bundle(u) = fun_bundle else {
-                        self.fatal("invalid function object");
+                let fun_bundle = self.pop();
+                let Val::Bundle(u) = fun_bundle else {
+                    self.fatal("invalid function object");
+                };
+                self.enter_closure(u, arg_count);
+            }
+            opcode::ret => {
+                let value = self.pop();
+                self.ret_frame();
+                self.push(value);
+                if self.frame_stack.is_empty() {
+                    return true;
+                }
+            }
+            opcode::pop => {
+                self.pop();
+            }
+            opcode::pop_offset => {
+                let offset = self.read_u16() as usize;
+                let index = self.stack.len() - offset - 1;
+                self.stack.remove(index);
+            }
+            opcode::dup => {
+                let value = self.peek().clone();
+                self.push(value);
+            }
+            opcode::try_begin => {
+                let pos = self.read_u32() as usize;
+                self.handlers.push(Handler {
+                    pos,
+                    stack_len: self.stack.len(),
+                    frame_len: self.frame_stack.len(),
+                    frame_cursor: self.frame_cursor,
+                });
+            }
+            opcode::try_end => {
+                self.handlers.pop().expect("handler stack underflow");
+            }
+            opcode::raise => {
+                let exception = self.pop();
+                let Some(handler) = self.handlers.pop() else {
+                    let Val::Bundle(u) = exception else {
+                        self.fatal("invalid exception object");
                     };
-
-                    let &[Val::Func(addr), Val::Bundle(u_capture)] = self.heap.deref_val_array(u)
-                    else {
-                        self.fatal("invalid function bundle");
+                    let [_, val, Val::Int(layout)] = self.heap.deref_val_array(u) else {
+                        self.fatal("invalid exception bundle");
                     };
-
-                    let captured = self.heap.deref_val_array(u_capture);
-                    self.stack.extend_from_slice(captured);
-
-                    self.push_call_frame(arg_count + captured.len());
-                    self.cursor = addr as usize;
-                }
-                opcode::ret => {
-                    let value = self.pop();
-                    self.ret_frame();
-                    self.push(value);
-                    if self.frame_stack.is_empty() {
-                        break;
+                    let msg = self.to_typed_val(val, *layout as LayoutID).to_string();
+                    if self.fail_host_call(CallError::Uncaught(msg.clone())) {
+                        return true;
                     }
+                    self.fatal(format!("UNCAUGHT EXCEPTION: {msg}").as_str());
+                };
+
+                self.frame_stack.truncate(handler.frame_len);
+                self.stack.truncate(handler.stack_len);
+                self.frame_cursor = handler.frame_cursor;
+                self.push(exception);
+                self.cursor = handler.pos;
+            }
+            opcode::panic => {
+                let val = self.pop();
+                let msg = self.to_user_val(&val).to_string();
+                if self.fail_host_call(CallError::Panic(msg.clone())) {
+                    return true;
                 }
-                opcode::pop => {
-                    self.pop();
-                }
-                opcode::pop_offset => {
-                    let offset = self.read_u16() as usize;
-                    let index = self.stack.len() - offset - 1;
-                    self.stack.remove(index);
-                }
-                opcode::dup => {
-                    let value = self.peek().clone();
-                    self.push(value);
-                }
-                opcode::panic => {
-                    let val = self.pop();
-                    let msg = self.to_user_val(&val).to_string();
-                    self.fatal(format!("PANIC: {msg}").as_str());
-                }
-                _ => self.fatal(format!("invalid opcode 0x{op:x}").as_str()),
+                self.fatal(format!("PANIC: {msg}").as_str());
             }
+            _ => self.fatal(format!("invalid opcode 0x{op:x}").as_str()),
         }
 
-        let result = self.pop();
-        debug_assert!(self.stack.is_empty(), "non-empty stack after halting");
-        self.to_user_val(&result)
+        false
     }
 
     fn pop(&mut self) -> Val {
@@ -624,62 +1218,203 @@ impl<'a> VM<'a> {
     }
 }
 
-type HeapIndex = usize;
+fn write_val<W: io::Write>(w: &mut W, val: &Val) -> binary::Result<()> {
+    match val {
+        Val::Nil => w.write_u8(value::nil)?,
+        Val::Int(n) => {
+            w.write_u8(value::int)?;
+            w.write_i64::<LE>(*n)?;
+        }
+        Val::Float(f) => {
+            w.write_u8(value::float)?;
+            w.write_f64::<LE>(*f)?;
+        }
+        Val::String(u) => {
+            w.write_u8(value::string)?;
+            w.write_u32::<LE>(u.0)?;
+        }
+        Val::Bool(b) => {
+            w.write_u8(value::bool)?;
+            w.write_u8(*b as u8)?;
+        }
+        Val::Func(pos) => {
+            w.write_u8(value::func)?;
+            w.write_u32::<LE>(*pos)?;
+        }
+        Val::Bundle(u) => {
+            w.write_u8(value::bundle)?;
+            w.write_u32::<LE>(u.0)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_val<R: io::Read>(r: &mut R, heap: &Heap) -> binary::Result<Val> {
+    match r.read_u8()? {
+        value::nil => Ok(Val::Nil),
+        value::int => Ok(Val::Int(r.read_i64::<LE>()?)),
+        value::float => Ok(Val::Float(r.read_f64::<LE>()?)),
+        value::string => Ok(Val::String(read_heap_index(r, heap, true)?)),
+        value::bool => Ok(Val::Bool(r.read_u8()? != 0)),
+        value::func => Ok(Val::Func(r.read_u32::<LE>()?)),
+        value::bundle => Ok(Val::Bundle(read_heap_index(r, heap, false)?)),
+        _ => Err(binary::Error::IllegalValue),
+    }
+}
+
+// heap indices must refer to existing objects of the right kind
+fn read_heap_index<R: io::Read>(
+    r: &mut R,
+    heap: &Heap,
+    is_string: bool,
+) -> binary::Result<HeapIndex> {
+    let index = HeapIndex(r.read_u32::<LE>()?);
+    match (heap.objects.get(index.0 as usize), is_string) {
+        (Some(HeapObject::String(_)), true) => Ok(index),
+        (Some(HeapObject::Array(_)), false) => Ok(index),
+        _ => Err(binary::Error::IllegalValue),
+    }
+}
+
+fn write_vals<W: io::Write>(w: &mut W, vals: &[Val]) -> binary::Result<()> {
+    w.write_u64::<LE>(vals.len() as u64)?;
+    for val in vals {
+        write_val(w, val)?;
+    }
+    Ok(())
+}
+
+// counts read from a snapshot can't be trusted, so they only bound the preallocation
+const MAX_PREALLOCATED: usize = 1 << 12;
+
+fn read_vals<R: io::Read>(r: &mut R, heap: &Heap) -> binary::Result<Vec<Val>> {
+    let count = r.read_u64::<LE>()? as usize;
+    let mut vals = Vec::with_capacity(count.min(MAX_PREALLOCATED));
+    for _ in 0..count {
+        vals.push(read_val(r, heap)?);
+    }
+    Ok(vals)
+}
+
+// heap objects are referred to by their handle in the object table
+// which doesn't depend on where their contents are actually stored
+#[derive(PartialEq, Clone, Copy, Debug)]
+struct HeapIndex(u32);
+
+#[derive(Debug)]
+enum HeapObject {
+    String(String),
+    Array(Vec<Val>),
+}
 
 #[derive(Debug)]
 struct Heap {
-    strings: Vec<String>,
-    values: Vec<Val>,
-    value_stride_lengths: Vec<usize>,
+    objects: Vec<HeapObject>,
 }
 
 impl Heap {
     fn new() -> Self {
         Self {
-            strings: Vec::new(),
-            values: Vec::new(),
-            value_stride_lengths: Vec::new(),
+            objects: Vec::new(),
         }
     }
 
+    fn alloc(&mut self, object: HeapObject) -> HeapIndex {
+        let index = self
+            .objects
+            .len()
+            .try_into()
+            .expect("heap cannot hold more than 2^32 objects");
+        self.objects.push(object);
+        HeapIndex(index)
+    }
+
     fn alloc_string(&mut self, string: String) -> HeapIndex {
-        let index = self.strings.len();
-        self.strings.push(string);
-        index
-    }
-
-    // fn alloc_val(&mut self, val: Val) -> HeapIndex {
-    //     let index = self.values.len();
-    //     self.values.push(val);
-    //     self.value_stride_lengths.push(1);
-    //     index
-    // }
-
-    fn alloc_val_array(&mut self, mut vals: Vec<Val>) -> HeapIndex {
-        let index = self.values.len();
-        let len = vals.len();
-        if len > 0 {
-            self.values.append(&mut vals);
-            self.value_stride_lengths.push(len);
-            self.value_stride_lengths.append(&mut vec![1; len - 1]);
-        } else {
-            // todo: make unit values not cause a pointless allocation
-            self.values.push(Val::Nil);
-            self.value_stride_lengths.push(0);
-        }
-        index
+        self.alloc(HeapObject::String(string))
+    }
+
+    fn alloc_val_array(&mut self, vals: Vec<Val>) -> HeapIndex {
+        self.alloc(HeapObject::Array(vals))
     }
 
     fn deref_string(&self, index: HeapIndex) -> &str {
-        &self.strings[index]
+        match &self.objects[index.0 as usize] {
+            HeapObject::String(string) => string,
+            HeapObject::Array(_) => panic!("heap object {} is not a string", index.0),
+        }
     }
 
     fn deref_val(&self, index: HeapIndex, offset: usize) -> &Val {
-        &self.values[index + offset]
+        &self.deref_val_array(index)[offset]
     }
 
     fn deref_val_array(&self, index: HeapIndex) -> &[Val] {
-        let len = self.value_stride_lengths[index];
-        &self.values[index..(index + len)]
+        match &self.objects[index.0 as usize] {
+            HeapObject::Array(vals) => vals,
+            HeapObject::String(_) => panic!("heap object {} is not an array", index.0),
+        }
+    }
+
+    fn deref_val_array_mut(&mut self, index: HeapIndex) -> &mut Vec<Val> {
+        match &mut self.objects[index.0 as usize] {
+            HeapObject::Array(vals) => vals,
+            HeapObject::String(_) => panic!("heap object {} is not an array", index.0),
+        }
+    }
+
+    // (function address, captured values)
+    fn deref_closure(&self, index: HeapIndex) -> Option<(u32, &[Val])> {
+        match self.objects.get(index.0 as usize)? {
+            HeapObject::Array(vals) => match vals[..] {
+                [Val::Func(addr), Val::Bundle(u_capture)] => {
+                    Some((addr, self.deref_val_array(u_capture)))
+                }
+                _ => None,
+            },
+            HeapObject::String(_) => None,
+        }
+    }
+
+    fn write<W: io::Write>(&self, w: &mut W) -> binary::Result<()> {
+        w.write_u64::<LE>(self.objects.len() as u64)?;
+        for object in &self.objects {
+            match object {
+                HeapObject::String(string) => {
+                    w.write_u8(value::string)?;
+                    binary::write_string(w, string)?;
+                }
+                HeapObject::Array(vals) => {
+                    let mut bytes = Vec::new();
+                    write_vals(&mut bytes, vals)?;
+                    w.write_u8(value::bundle)?;
+                    binary::write_bytes(w, &bytes)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // arrays may refer to any object, so their items are read once all objects exist
+    fn read<R: io::Read>(r: &mut R) -> binary::Result<Self> {
+        let count = r.read_u64::<LE>()? as usize;
+        let mut objects = Vec::with_capacity(count.min(MAX_PREALLOCATED));
+        let mut raw_arrays = Vec::new();
+        for _ in 0..count {
+            match r.read_u8()? {
+                value::string => objects.push(HeapObject::String(binary::read_string(r)?)),
+                value::bundle => {
+                    raw_arrays.push((objects.len(), binary::read_bytes(r)?));
+                    objects.push(HeapObject::Array(Vec::new()));
+                }
+                _ => return Err(binary::Error::IllegalValue),
+            }
+        }
+
+        let mut heap = Self { objects };
+        for (index, bytes) in raw_arrays {
+            let vals = read_vals(&mut bytes.as_slice(), &heap)?;
+            heap.objects[index] = HeapObject::Array(vals);
+        }
+        Ok(heap)
     }
 }
@@ -6,32 +6,44 @@ test!(returns_false => bool(false));
 test!(returns_integer => int(42));
 test!(returns_float => float(4.87));
 test!(returns_string => str("hello, world"));
-test!(returns_func => func([]));
+test!(returns_func => func());
 
-test!(returns_tuple_1 => bun([int(8), float(0.5), str("hi")]));
-test!(returns_tuple_2 => bun([bun([bool(true), float(2.0)]), bun([int(3), float(4.0), int(5)]), str("6")]));
+test!(returns_tuple_1 => tup([int(8), float(0.5), str("hi")]));
+test!(returns_tuple_2 => tup([tup([bool(true), float(2.0)]), tup([int(3), float(4.0), int(5)]), str("6")]));
 
-test!(returns_array_1 => bun([int(1), int(2), int(3), int(4)]));
-test!(returns_array_2 => bun([bun([int(1), int(2)]), bun([]), bun([int(3)]), bun([int(4), int(5), int(6)])]));
+test!(returns_array_1 => arr([int(1), int(2), int(3), int(4)]));
+test!(returns_array_2 => arr([arr([int(1), int(2)]), arr([]), arr([int(3)]), arr([int(4), int(5), int(6)])]));
 
 test!(block_empty_is_unit => unit());
 test!(block_singleton_1 => unit());
 test!(block_singleton_2 => int(73));
-test!(block_singleton_3 => bun([str("single"), bun([bool(true), str("block")])]));
+test!(block_singleton_3 => tup([str("single"), tup([bool(true), str("block")])]));
 test!(block_last_is_result_1 => unit());
 test!(block_last_is_result_2 => str("okay"));
 test!(block_last_is_result_3 => str("done"));
-test!(block_let_frame => bun([int(1), int(2), int(3)]));
+test!(block_let_frame => tup([int(1), int(2), int(3)]));
 
 test!(let_is_unit_1 => unit());
 test!(let_is_unit_2 => unit());
 
+test!(let_mut_counter with std => int(15));
+test!(let_mut_tuple => tup([int(2), str("two")]));
+test!(let_mut_in_function => tup([int(10), int(55)]));
+test!(let_mut_assign_is_unit => unit());
+test!(let_mut_constraint => tup([bool(true), bool(true)]));
+test!(class_superclass => tup([bool(true), bool(true), bool(false)]));
+test!(class_default_items => tup([bool(true), bool(false), bool(false), bool(true)]));
+
 test!(let_deconstruct_variable_1 => int(999));
-test!(let_deconstruct_variable_2 => bun([int(777), str("y")]));
-test!(let_deconstruct_variable_3 => bun([int(888), str("yyy"), float(5.55)]));
-test!(let_deconstruct_tuple_1 => bun([bool(true), bool(false)]));
-test!(let_deconstruct_tuple_2 => bun([int(1), int(2), int(3), str("yy"), str("zz"), str("ww")]));
-test!(let_deconstruct_tuple_3 => bun([str("a"), int(1), bun([str("c"), float(0.42)]), bool(true), int(2), float(4.0), str("zd")]));
+test!(let_deconstruct_variable_2 => tup([int(777), str("y")]));
+test!(let_deconstruct_variable_3 => tup([int(888), str("yyy"), float(5.55)]));
+test!(let_deconstruct_tuple_1 => tup([bool(true), bool(false)]));
+test!(let_deconstruct_tuple_2 => tup([int(1), int(2), int(3), str("yy"), str("zz"), str("ww")]));
+test!(let_deconstruct_tuple_3 => tup([str("a"), int(1), tup([str("c"), float(0.42)]), bool(true), int(2), float(4.0), str("zd")]));
+test!(let_array_rest => arr([int(1), int(2), int(3)]));
+test!(let_annotated => tup([int(3), str("half"), float(0.5), arr([int(3), int(4)]), int(7), int(8)]));
+test!(let_annotated_function => tup([int(3), str("id"), bool(true), tup([str("one"), int(1)]), int(18)]));
+test!(expr_annotated => tup([tup([arr([]), arr([])]), int(1), arr([int(5), int(6)])]));
 
 test!(break_block => int(42));
 test!(break_block_label => int(42));
@@ -59,11 +71,26 @@ test!(while_exhaustive_label_break => str("break"));
 test!(while_exhaustive_label_else => str("else"));
 test!(while_exhaustive_nested_break => str("break"));
 test!(while_exhaustive_nested_else => str("else"));
+test!(while_let_frame with std => int(60));
+test!(if_let_condition with std => int(50));
+test!(while_skip_let_frame => tup([int(5), int(4)]));
+
+test!(for_range => int(45));
+test!(for_range_bounds => int(14));
+test!(for_array => int(44));
+test!(for_exhaustive_break => str("break"));
+test!(for_exhaustive_else => str("else"));
+test!(for_exhaustive_nested_break => int(700));
+test!(for_break_nested => tup([int(2), int(3)]));
+test!(for_skip => int(25));
+test!(for_capture => tup([int(0), int(20)]));
 
 test!(loop_break_unit => unit());
 test!(loop_break_val => str("val"));
 test!(loop_break_nested_unit => unit());
 test!(loop_break_nested_val => str("val"));
+test!(loop_break_let_frame => tup([int(4), int(12)]));
+test!(loop_break_match => tup([tup([int(3), int(9)]), int(3)]));
 
 test!(fun_int => int(42));
 test!(fun_unit => unit());
@@ -71,100 +98,198 @@ test!(fun_unit => unit());
 test!(fun_deconstruct_arg_1 => str("a"));
 test!(fun_deconstruct_arg_2 => str("b"));
 test!(fun_deconstruct_arg_3 => str("c"));
-test!(fun_deconstruct_tuple_1 => bun([str("a"), str("b")]));
-test!(fun_deconstruct_tuple_2 => bun([int(1), int(2), int(3), int(4)]));
-test!(fun_deconstruct_tuple_3 => bun([str("a"), int(1), bun([str("c"), float(0.42)])]));
+test!(fun_deconstruct_tuple_1 => tup([str("a"), str("b")]));
+test!(fun_deconstruct_tuple_2 => tup([int(1), int(2), int(3), int(4)]));
+test!(fun_deconstruct_tuple_3 => tup([str("a"), int(1), tup([str("c"), float(0.42)])]));
 
 test!(fun_capture_var_1 => int(42));
-test!(fun_capture_var_2 => bun([int(42), int(43)]));
-test!(fun_capture_var_3 => bun([int(42), int(43), int(44), int(45)]));
+test!(fun_capture_var_2 => tup([int(42), int(43)]));
+test!(fun_capture_var_3 => tup([int(42), int(43), int(44), int(45)]));
 test!(fun_capture_fun_1 => int(42));
-test!(fun_capture_fun_2 => bun([int(42), int(43)]));
-test!(fun_capture_fun_3 => bun([int(42), int(43), bun([int(44), int(44)]), int(45)]));
+test!(fun_capture_fun_2 => tup([int(42), int(43)]));
+test!(fun_capture_fun_3 => tup([int(42), int(43), tup([int(44), int(44)]), int(45)]));
 
 test!(fun_curry_1 => int(42));
-test!(fun_curry_2 => bun([str("a"), str("b")]));
-test!(fun_curry_3 => bun([int(1), int(2), int(3), int(4)]));
+test!(fun_curry_2 => tup([str("a"), str("b")]));
+test!(fun_curry_3 => tup([int(1), int(2), int(3), int(4)]));
 test!(fun_curry_capture_var_1 => int(42));
-test!(fun_curry_capture_var_2 => bun([str("h"), bun([int(42), int(43)])]));
-test!(fun_curry_capture_var_3 => bun([str("h"), bun([int(42), int(43), int(44), int(45)]), str("t")]));
+test!(fun_curry_capture_var_2 => tup([str("h"), tup([int(42), int(43)])]));
+test!(fun_curry_capture_var_3 => tup([str("h"), tup([int(42), int(43), int(44), int(45)]), str("t")]));
 test!(fun_curry_capture_fun_1 => int(42));
-test!(fun_curry_capture_fun_2 => bun([str("h"), bun([int(42), int(43)])]));
-test!(fun_curry_capture_fun_3 => bun([str("h"), bun([int(42), int(43), bun([int(44), int(44)]), int(45)]), str("t")]));
+test!(fun_curry_capture_fun_2 => tup([str("h"), tup([int(42), int(43)])]));
+test!(fun_curry_capture_fun_3 => tup([str("h"), tup([int(42), int(43), tup([int(44), int(44)]), int(45)]), str("t")]));
 
 test!(let_fun_1 => int(42));
-test!(let_fun_2 => bun([int(42), str("43")]));
-test!(let_fun_3 => bun([int(1), int(2), int(3), int(4)]));
-test!(let_fun_4 => bun([bun([int(8), int(8)]), bun([int(8), int(8)]), bun([int(8), int(8)])]));
-test!(let_fun_5 => bun([int(42), int(42)]));
-test!(let_fun_6 => bun([int(12), int(12)]));
+test!(let_fun_2 => tup([int(42), str("43")]));
+test!(let_fun_3 => tup([int(1), int(2), int(3), int(4)]));
+test!(let_fun_4 => tup([tup([int(8), int(8)]), tup([int(8), int(8)]), tup([int(8), int(8)])]));
+test!(let_fun_5 => tup([int(42), int(42)]));
+test!(let_fun_6 => tup([int(12), int(12)]));
+test!(let_fun_recursive_closure with std => int(5));
+test!(let_fun_dependency_order with std => tup([tup([int(1), str("a"), bool(true)]), bool(true), bool(true)]));
+test!(let_fun_mutual with std => tup([bool(true), bool(true), int(10), str("1,2,3"), str("true")]));
 
-test!(let_generalize_1 => bun([int(42), str("a"), float(12.3)]));
-test!(let_generalize_2 => bun([bun([int(2), float(1.0)]), bun([bool(true), str("a")]), bun([int(0), func([])])]));
-test!(let_generalize_3 => bun([str("u"), int(42), str("a")]));
+test!(let_generalize_1 => tup([int(42), str("a"), float(12.3)]));
+test!(let_generalize_2 => tup([tup([int(2), float(1.0)]), tup([bool(true), str("a")]), tup([int(0), func()])]));
+test!(let_generalize_3 => tup([str("u"), int(42), str("a")]));
 
 test!(array_index_1 => int(1));
 test!(array_index_2 => int(3));
 test!(array_index_3 => int(43));
+test!(array_index_multi => tup([int(2), int(5), int(4)]));
+test!(array_length => tup([int(0), int(3)]));
+test!(array_push_pop => tup([arr([int(1), int(2), int(3)]), int(4), int(3)]));
+test!(array_set => arr([str("a"), str("x"), str("y")]));
+test!(array_slice => tup([arr([int(1), int(2), int(3), int(4), int(5)]), arr([int(0), int(3), int(4)]), arr([])]));
+test!(array_shared => arr([int(1), int(2)]));
+
+test!(record_empty => record("R", []));
+test!(record_fields => record("R", [("x", float(42.0)), ("y", float(22.2))]));
+test!(record_generic_1 => tup([
+    record("R", [("x", float(42.0)), ("y", float(22.2))]),
+    record("R", [("x", int(42)), ("y", int(22))]),
+    record("R", [("x", str("a")), ("y", str("b"))]),
+]));
+test!(record_generic_2 => tup([
+    record("R", [("x", int(1)), ("y", int(2))]),
+    record("R", [("x", int(42)), ("y", float(22.2))]),
+    record("R", [("x", str("a")), ("y", bool(true))]),
+]));
+test!(record_getter => tup([float(42.0), float(22.2)]));
+test!(record_update => tup([
+    record("Person", [("name", str("ada")), ("age", int(36)), ("alive", bool(true))]),
+    record("Person", [("name", str("ada")), ("age", int(21)), ("alive", bool(true))]),
+    record("Person", [("name", str("bob")), ("age", int(21)), ("alive", bool(false))]),
+]));
+test!(record_update_base_once => tup([record("Counter", [("count", int(10)), ("step", int(2))]), int(1)]));
+test!(record_update_disambiguate => record("Pet", [
+    ("name", str("max")),
+    ("owner", record("Person", [("name", str("ada")), ("age", int(36))])),
+]));
+test!(record_field_access with std => tup([str("ada"), str("rex"), int(37), int(42), str("x")]));
+
+test!(union_variants_empty_a => variant("U", "a", []));
+test!(union_variants_empty_b => variant("U", "b", []));
+test!(union_variants_fields_a => variant("U", "a", [int(42)]));
+test!(union_variants_fields_b => variant("U", "b", [float(81.7)]));
+test!(union_variants_recursive_a => variant("U", "empty", []));
+test!(union_variants_recursive_ba => variant("U", "cons", [variant("U", "empty", [])]));
+test!(union_variants_recursive_bba => variant("U", "cons", [variant("U", "cons", [variant("U", "empty", [])])]));
+test!(type_mutual_recursion with std => tup([int(2), str("x")]));
+test!(union_generic_1_a_1 => variant("U", "a", [str("c")]));
+test!(union_generic_1_a_2 => variant("U", "a", [int(3)]));
+test!(union_generic_1_b_1 => variant("U", "b", [str("a"), str("b")]));
+test!(union_generic_1_b_2 => variant("U", "b", [int(1), int(2)]));
+test!(union_generic_2_a => variant("U", "a", [int(1)]));
+test!(union_generic_2_b => variant("U", "b", [unit()]));
+test!(union_generic_2_ab => variant("U", "ab", [str("3"), int(4)]));
 
-test!(record_empty => record([]));
-test!(record_fields => record([float(42.0), float(22.2)]));
-test!(record_generic_1 => bun([record([float(42.0), float(22.2)]), record([int(42), int(22)]), record([str("a"), str("b")])]));
-test!(record_generic_2 => bun([record([int(1), int(2)]), record([int(42), float(22.2)]), record([str("a"), bool(true)])]));
-test!(record_getter => bun([float(42.0), float(22.2)]));
-
-test!(union_variants_empty_a => union(0, []));
-test!(union_variants_empty_b => union(1, []));
-test!(union_variants_fields_a => union(0, [int(42)]));
-test!(union_variants_fields_b => union(1, [float(81.7)]));
-test!(union_variants_recursive_a => union(0, []));
-test!(union_variants_recursive_ba => union(1, [union(0, [])]));
-test!(union_variants_recursive_bba => union(1, [union(1, [union(0, [])])]));
-test!(union_generic_1_a_1 => union(0, [str("c")]));
-test!(union_generic_1_a_2 => union(0, [int(3)]));
-test!(union_generic_1_b_1 => union(1, [str("a"), str("b")]));
-test!(union_generic_1_b_2 => union(1, [int(1), int(2)]));
-test!(union_generic_2_a => union(0, [int(1)]));
-test!(union_generic_2_b => union(1, [unit()]));
-test!(union_generic_2_ab => union(2, [str("3"), int(4)]));
+test!(type_synonym => tup([tup([float(0.0), float(1.5)]), str("ada")]));
+test!(type_synonym_generic => tup([tup([int(2), int(1)]), tup([str("b"), str("a")]), tup([int(7), str("rest")])]));
+test!(newtype_erased => float(2.5));
+test!(newtype_match => tup([float(4.5), arr([int(1), int(2)]), arr([str("x")])]));
+
+test!(value_list with std => tup([list([int(1), int(2), int(3)]), list([str("a")])]));
+test!(value_list_lookalike => variant("L", "cons", [bool(true), variant("L", "nil", [])]));
+test!(value_nested => tup([
+    arr([
+        variant("O", "some", [record("R", [("x", arr([int(1), int(2)])), ("f", func())])]),
+        variant("O", "none", []),
+    ]),
+    record("R", [("x", variant("O", "some", [tup([str("a"), float(1.5)])])), ("f", func())]),
+]));
 
 test!(match_empty_is_unit => unit());
 test!(match_binding => int(42));
 test!(match_int_fallback => int(2));
 test!(match_int_success => int(42));
-test!(match_tuple_deconstruct => bun([int(43), int(42)]));
-test!(match_tuple_left_fallback => bun([int(2), int(2)]));
-test!(match_tuple_left_success => bun([int(42), int(2)]));
-test!(match_tuple_right_fallback => bun([int(2), int(2)]));
-test!(match_tuple_right_success => bun([int(2), int(42)]));
-test!(match_tuple_both_fallback_both => bun([int(2), int(2)]));
-test!(match_tuple_both_fallback_left => bun([int(2), int(1)]));
-test!(match_tuple_both_fallback_right => bun([int(1), int(2)]));
-test!(match_tuple_both_success => bun([int(42), int(42)]));
-test!(match_tuple_nested_deconstruct => bun([int(1), int(42), int(43)]));
-test!(match_tuple_nested_test_fallback => bun([int(1), int(44), int(43)]));
-test!(match_tuple_nested_test_success => bun([int(1), int(999), int(43)]));
+test!(match_fallback_frame => tup([int(3), int(5), int(7)]));
+test!(match_tuple_deconstruct => tup([int(43), int(42)]));
+test!(match_tuple_left_fallback => tup([int(2), int(2)]));
+test!(match_tuple_left_success => tup([int(42), int(2)]));
+test!(match_tuple_right_fallback => tup([int(2), int(2)]));
+test!(match_tuple_right_success => tup([int(2), int(42)]));
+test!(match_tuple_both_fallback_both => tup([int(2), int(2)]));
+test!(match_tuple_both_fallback_left => tup([int(2), int(1)]));
+test!(match_tuple_both_fallback_right => tup([int(1), int(2)]));
+test!(match_tuple_both_success => tup([int(42), int(42)]));
+test!(match_tuple_nested_deconstruct => tup([int(1), int(42), int(43)]));
+test!(match_tuple_nested_test_fallback => tup([int(1), int(44), int(43)]));
+test!(match_tuple_nested_test_success => tup([int(1), int(999), int(43)]));
 test!(match_union_exhaustive_a => str("a"));
 test!(match_union_exhaustive_b => str("b"));
 test!(match_union_deconstruct_1_a => str("a"));
 test!(match_union_deconstruct_1_b => str("sss"));
-test!(match_union_deconstruct_2_a => bun([str("a1"), str("a2")]));
-test!(match_union_deconstruct_2_b => bun([str("s1"), str("s2")]));
-test!(match_record_deconstruct => bun([int(42), float(1.0)]));
-test!(match_record_test_x_fallback => bun([int(44), float(1.0)]));
-test!(match_record_test_x_success => bun([int(999), float(1.0)]));
-test!(match_record_test_y_fallback => bun([int(44), float(1.0)]));
-test!(match_record_test_y_success => bun([int(44), float(999.0)]));
-test!(match_record_test_both_fallback_both => bun([int(1), float(1.0)]));
-test!(match_record_test_both_fallback_x => bun([int(1), float(42.0)]));
-test!(match_record_test_both_fallback_y => bun([int(42), float(1.0)]));
-test!(match_record_test_both_success => bun([int(999), float(999.0)]));
+test!(match_union_deconstruct_2_a => tup([str("a1"), str("a2")]));
+test!(match_union_deconstruct_2_b => tup([str("s1"), str("s2")]));
+test!(match_record_deconstruct => tup([int(42), float(1.0)]));
+test!(match_record_test_x_fallback => tup([int(44), float(1.0)]));
+test!(match_record_test_x_success => tup([int(999), float(1.0)]));
+test!(match_record_test_y_fallback => tup([int(44), float(1.0)]));
+test!(match_record_test_y_success => tup([int(44), float(999.0)]));
+test!(match_record_test_both_fallback_both => tup([int(1), float(1.0)]));
+test!(match_record_test_both_fallback_x => tup([int(1), float(42.0)]));
+test!(match_record_test_both_fallback_y => tup([int(42), float(1.0)]));
+test!(match_record_test_both_success => tup([int(999), float(999.0)]));
+
+test!(match_or_literals => tup([str("small"), str("small"), str("medium"), str("big")]));
+test!(match_or_variants => tup([int(3), int(4), int(5), int(0)]));
+test!(match_or_nested => tup([int(10), int(20), int(30), int(5)]));
+test!(match_guard => tup([int(5), int(6), int(100), int(21), int(7)]));
+test!(match_guard_capture => tup([str("same"), str("other")]));
+test!(match_guard_exhaustive => tup([int(2), int(3)]));
+
+test!(match_array_head_tail => int(10));
+test!(match_array_last => tup([int(7), int(8), int(0)]));
+test!(match_array_lengths => tup([int(0), int(1), int(2), int(3), int(7), int(100)]));
+test!(match_array_middle => tup([tup([int(1), arr([int(2), int(3)]), int(4)]), tup([int(1), arr([]), int(2)]), tup([int(0), arr([]), int(0)])]));
+test!(match_array_nested => tup([int(1), int(50), int(70), int(0)]));
+test!(match_array_exhaustive => tup([int(0), int(1), int(2), int(5)]));
+
+test!(try_no_raise => int(42));
+test!(try_catch_variant => str("oops"));
+test!(try_catch_nested_rethrow => int(42));
+test!(try_catch_nested_unmatched => str("outer"));
+test!(try_raise_in_function => tup([int(1), int(3), int(2)]));
+test!(try_break_out => tup([str("out"), str("caught")]));
+test!(try_break_label => str("break"));
+
+#[test]
+#[should_panic(expected = "UNCAUGHT EXCEPTION: unexpected(\"x\", 3)")]
+fn try_uncaught() {
+    test_full_program("./tests/lang/try_uncaught.mar", false, unit());
+}
+
+test!(interpolation_text => str("{x} = }{"));
+test!(interpolation_int with std => str("1 + 2 = 3"));
+test!(interpolation_float with std => str("half of 3.0 is 1.5"));
+test!(interpolation_tuple with std => str("pair: (1, true), unit: ()"));
+test!(interpolation_nested with std => str("{7} [x=2] 4"));
+test!(interpolation_string_hole with std => str("a 'b' c 3} {d}"));
+
+test!(deriving_record with std => tup([bool(true), bool(true), bool(true), bool(false), str("{ x = 1, y = 2 }"), str("{ x = 0, y = 0 }")]));
+test!(deriving_union with std => tup([bool(true), bool(false), bool(true), bool(false), str("rect(1.5, 2.0)"), str("dot")]));
+test!(deriving_recursive with std => tup([bool(true), bool(false), bool(true), str("cons(a, nil)"), bool(false), str("succ(zero)")]));
+
+test!(class_higher_kinded with std => tup([int(6), int(40), int(-1)]));
+
+test!(std_functor with std => tup([int(12), str("4"), bool(true), int(3), tup([str("a"), str("a")])]));
+test!(std_map_aliases with std => tup([int(5), str("3"), bool(true)]));
+
+test!(class_named_instances with std => tup([int(9), int(120), int(9), str("24")]));
+
+test!(class_named_instance_items with std => tup([tup([int(0), int(1)]), tup([int(5), int(6)]), tup([int(5), int(0)])]));
+
+test!(class_generic_through_generic with std => tup([tup([str("1"), str("1")]), tup([str("true"), str("true")]), tup([tup([str("2.5"), str("2.5")]), tup([str("a"), str("a")])])]));
 
 // ------------------------------------------------------------------------
 
-fn test_full_program(path: impl AsRef<Path>, expected: exe::Value) {
+fn test_full_program(path: impl AsRef<Path>, with_std: bool, expected: exe::Value) {
     let mut compiler = com::init();
     compiler.add_file(path);
+    if with_std {
+        compiler.add_marin_std_from(MARIN_DIR);
+    }
 
     let compiler = compiler.read_sources().parse().check();
 
@@ -181,8 +306,8 @@ fn test_full_program(path: impl AsRef<Path>, expected: exe::Value) {
     let compiler = compiler.emit();
     let bytecode = compiler.into_content().bytecode;
 
-    let value = exe::run_bytecode(&bytecode);
-    assert_eq!(value, expected);
+    let value = exe::load_bytecode(&bytecode).run();
+    assert_eq!(erase_closures(value), expected);
 }
 
 macro_rules! test {
@@ -192,7 +317,16 @@ macro_rules! test {
         #[test]
         fn $test_name() {
             let path = format!("./tests/lang/{}.mar", stringify!($test_name));
-            test_full_program(path, $expected);
+            test_full_program(path, false, $expected);
+        }
+    };
+    (
+        $test_name:ident with std => $expected:expr
+    ) => {
+        #[test]
+        fn $test_name() {
+            let path = format!("./tests/lang/{}.mar", stringify!($test_name));
+            test_full_program(path, true, $expected);
         }
     };
 }
@@ -0,0 +1,213 @@
+test!(host_call_add => "add"(int(40), int(2)) => int(42));
+test!(host_call_area_circle => "area"(variant("Shape", "circle", [float(2.0)])) => float(12.0));
+test!(host_call_area_rect => "area"(variant("Shape", "rect", [float(2.0), float(4.5)])) => float(9.0));
+test!(host_call_flip => "flip"(record("Point", [("y", int(2)), ("x", int(1))])) => record("Point", [("x", int(2)), ("y", int(1))]));
+test!(host_call_length_empty => "length"(list([])) => int(0));
+test!(host_call_length => "length"(list([str("a"), str("b"), str("c")])) => int(3));
+test!(host_call_range => "range"(int(3)) => list([int(3), int(2), int(1)]));
+
+#[test]
+fn host_call_returned_closure() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+
+    let exe::Value::Func(increment) = vm.run() else {
+        panic!("program did not return a closure");
+    };
+    assert_eq!(vm.call(increment, &[int(41)]).unwrap(), int(42));
+    assert_eq!(vm.call(increment, &[int(-1)]).unwrap(), int(0));
+}
+
+#[test]
+fn host_call_curried() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.run();
+
+    let adder = vm.get_function("adder").unwrap();
+    let exe::Value::Func(add_ten) = vm.call(adder, &[int(10)]).unwrap() else {
+        panic!("'adder' did not return a closure");
+    };
+    assert_eq!(vm.call(add_ten, &[int(5)]).unwrap(), int(15));
+    assert_eq!(vm.call(add_ten, &[int(32)]).unwrap(), int(42));
+}
+
+#[test]
+fn host_call_closure_argument() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    let increment = vm.run();
+
+    let apply = vm.get_function("apply").unwrap();
+    assert_eq!(vm.call(apply, &[increment, int(99)]).unwrap(), int(100));
+}
+
+#[test]
+fn host_missing_functions() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    assert!(
+        vm.get_function("add").is_err(),
+        "functions exist before running"
+    );
+
+    vm.run();
+    assert!(vm.get_function("add").is_ok());
+    assert!(
+        vm.get_function("hidden").is_err(),
+        "private functions are exported"
+    );
+    assert_eq!(
+        vm.get_function("unknown"),
+        Err(exe::CallError::UnknownFunction("unknown".to_string()))
+    );
+}
+
+#[test]
+fn host_call_misuse() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.run();
+
+    let add = vm.get_function("add").unwrap();
+    assert_eq!(
+        vm.call(add, &[int(1)]),
+        Err(exe::CallError::ArgCount {
+            expected: 2,
+            given: 1
+        })
+    );
+
+    let flip = vm.get_function("flip").unwrap();
+    assert_eq!(
+        vm.call(flip, &[record("Point", [("x", int(1))])]),
+        Err(exe::CallError::MissingField("y".to_string()))
+    );
+
+    let area = vm.get_function("area").unwrap();
+    assert_eq!(
+        vm.call(area, &[variant("Shape", "square", [float(2.0)])]),
+        Err(exe::CallError::UnknownVariant("square".to_string()))
+    );
+
+    // the vm is still usable afterwards
+    assert_eq!(vm.call(add, &[int(1), int(2)]), Ok(int(3)));
+}
+
+#[test]
+fn host_call_while_running() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.run();
+    let add = vm.get_function("add").unwrap();
+
+    let mut vm = exe::load_bytecode(&bytecode);
+    assert_eq!(vm.run_steps(1), None);
+    assert_eq!(
+        vm.call(add, &[int(1), int(2)]),
+        Err(exe::CallError::Running)
+    );
+}
+
+#[test]
+fn host_call_failure() {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.run();
+
+    let check = vm.get_function("check").unwrap();
+    assert_eq!(
+        vm.call(check, &[bool(false)]),
+        Err(exe::CallError::Uncaught("bad(3)".to_string()))
+    );
+
+    let halt = vm.get_function("halt").unwrap();
+    assert_eq!(
+        vm.call(halt, &[str("stop")]),
+        Err(exe::CallError::Panic("\"stop\"".to_string()))
+    );
+
+    // the vm is still usable afterwards
+    assert_eq!(vm.call(check, &[bool(true)]), Ok(int(1)));
+}
+
+#[test]
+fn host_ambiguous_functions() {
+    let mut compiler = com::init();
+    compiler.add_file("./tests/lang/host_functions.mar");
+    compiler.add_file("./tests/lang/host_functions_other.mar");
+    compiler.add_marin_std_from(MARIN_DIR);
+    let compiler = compiler.read_sources().parse().check();
+    assert!(!compiler.is_fatal(), "host test programs have errors");
+    let bytecode = compiler.emit().into_content().bytecode;
+
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.run();
+    assert_eq!(
+        vm.get_function("add"),
+        Err(exe::CallError::AmbiguousFunction("add".to_string()))
+    );
+    assert!(vm.get_function("sub").is_ok());
+}
+
+#[test]
+fn host_values_binary() {
+    let values = [
+        unit(),
+        arr([int(1), float(2.5)]),
+        list([str("a"), str("b")]),
+        record("Point", [("x", int(1)), ("y", int(2))]),
+        variant("Shape", "circle", [float(2.0)]),
+        exe::Value::Nil,
+    ];
+
+    let mut bytes = Vec::new();
+    binary::write_constant_pool(&mut bytes, &values).expect("failed to write host values");
+    let read =
+        binary::read_constant_pool(&mut bytes.as_slice()).expect("failed to read host values");
+    assert_eq!(read, values);
+
+    let result = binary::write_value(&mut Vec::new(), &func());
+    assert!(matches!(result, Err(binary::Error::UnserializableValue)));
+}
+
+// ------------------------------------------------------------------------
+
+fn compile_host_program() -> Vec<u8> {
+    let mut compiler = com::init();
+    compiler.add_file("./tests/lang/host_functions.mar");
+    compiler.add_marin_std_from(MARIN_DIR);
+
+    let compiler = compiler.read_sources().parse().check();
+    assert!(!compiler.is_fatal(), "host test program has errors");
+
+    let compiler = compiler.emit();
+    compiler.into_content().bytecode
+}
+
+fn test_host_call(name: &str, args: &[exe::Value], expected: exe::Value) {
+    let bytecode = compile_host_program();
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.run();
+
+    let function = vm
+        .get_function(name)
+        .unwrap_or_else(|e| panic!("missing function '{name}': {e}"));
+    assert_eq!(vm.call(function, args), Ok(expected));
+}
+
+macro_rules! test {
+    (
+        $test_name:ident => $function:literal($($arg:expr),*) => $expected:expr
+    ) => {
+        #[test]
+        fn $test_name() {
+            test_host_call($function, &[$($arg),*], $expected);
+        }
+    };
+}
+
+use test;
+
+use super::*;
+use crate::{binary, com, exe};
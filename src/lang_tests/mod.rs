@@ -1,9 +1,16 @@
 mod check;
 mod full;
+mod host;
 mod semantic_report;
+mod snapshot;
+mod syntax_report;
+mod trace;
 
 use crate::exe;
 
+// test executables live in target/<profile>/deps, so use the sources instead
+const MARIN_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
 // utility methods to construct values faster
 
 fn bool(b: bool) -> exe::Value {
@@ -22,22 +29,59 @@ fn str(s: &str) -> exe::Value {
     exe::Value::String(s.to_string())
 }
 
-fn bun<const N: usize>(items: [exe::Value; N]) -> exe::Value {
-    exe::Value::Bundle(Box::new(items))
+fn tup<const N: usize>(items: [exe::Value; N]) -> exe::Value {
+    exe::Value::Tuple(Box::new(items))
+}
+
+fn arr<const N: usize>(items: [exe::Value; N]) -> exe::Value {
+    exe::Value::Array(Box::new(items))
+}
+
+fn list<const N: usize>(items: [exe::Value; N]) -> exe::Value {
+    exe::Value::List(Box::new(items))
 }
 
 fn unit() -> exe::Value {
-    bun([])
+    tup([])
+}
+
+// closure handles depend on the heap of the vm they come from
+// so they are compared after being erased
+const ERASED_CLOSURE: exe::Closure = exe::Closure {
+    index: 0,
+    layout: exe::LayoutTable::ANY,
+};
+
+fn func() -> exe::Value {
+    exe::Value::Func(ERASED_CLOSURE)
 }
 
-fn func<const N: usize>(captured: [exe::Value; N]) -> exe::Value {
-    bun([exe::Value::Func, bun(captured)])
+fn erase_closures(value: exe::Value) -> exe::Value {
+    use exe::Value as V;
+    let erase_all = |values: Box<[V]>| values.into_iter().map(erase_closures).collect();
+    match value {
+        V::Func(_) => func(),
+        V::Bundle(items) => V::Bundle(erase_all(items)),
+        V::Tuple(items) => V::Tuple(erase_all(items)),
+        V::Array(items) => V::Array(erase_all(items)),
+        V::List(items) => V::List(erase_all(items)),
+        V::Record(name, fields) => V::Record(
+            name,
+            fields
+                .into_iter()
+                .map(|(field, value)| (field, erase_closures(value)))
+                .collect(),
+        ),
+        V::Variant(union, name, args) => V::Variant(union, name, erase_all(args)),
+        value => value,
+    }
 }
 
-fn record<const N: usize>(fields: [exe::Value; N]) -> exe::Value {
-    bun(fields)
+fn record<const N: usize>(name: &str, fields: [(&str, exe::Value); N]) -> exe::Value {
+    let fields = fields.map(|(field, value)| (field.to_string(), value));
+    exe::Value::Record(name.to_string(), Box::new(fields))
 }
 
-fn union<const N: usize>(variant: i64, items: [exe::Value; N]) -> exe::Value {
-    bun([int(variant), bun(items)])
+fn variant<const N: usize>(union: &str, name: &str, args: [exe::Value; N]) -> exe::Value {
+    exe::Value::Variant(union.to_string(), name.to_string(), Box::new(args))
 }
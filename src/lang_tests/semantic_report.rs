@@ -1,6 +1,9 @@
 test!(unknown_binding_global);
 test!(unknown_binding_local);
 test!(invalid_integer_too_big);
+test!(invalid_indexing_missing);
+test!(refutable_pattern_for);
+test!(refutable_pattern_let_array);
 
 test!(invalid_expression_record);
 test!(invalid_expression_record_big);
@@ -19,6 +22,7 @@ test!(invalid_expression_super);
 test!(invalid_super_accessor_class);
 test!(invalid_super_accessor_record);
 test!(invalid_super_accessor_union);
+test!(invalid_access_synonym);
 
 test!(invalid_type_block);
 test!(invalid_type_bool);
@@ -32,6 +36,7 @@ test!(invalid_type_match);
 test!(invalid_type_skip);
 test!(invalid_type_string);
 test!(invalid_type_while);
+test!(invalid_type_application);
 
 test!(invalid_type_arg_int);
 test!(invalid_type_arg_float);
@@ -45,9 +50,34 @@ test!(type_mismatch_int_string);
 test!(type_mismatch_string_bool);
 test!(type_mismatch_tuple_1);
 test!(type_mismatch_tuple_2);
+test!(type_mismatch_annotation_let);
+test!(type_mismatch_annotation_ret);
+test!(type_mismatch_annotation_expr);
+test!(type_mismatch_annotation_scheme);
+test!(annotation_too_general);
+test!(type_mismatch_newtype);
+test!(synonym_arg_mismatch);
+test!(invalid_superclass);
+test!(unsatisfied_superclass_instance);
+test!(unsatisfied_constraint_interpolation with std);
+test!(annotation_too_general_default);
+test!(unknown_class_item_default);
+test!(not_derivable);
+test!(overlapping_instances);
+test!(invalid_instance);
+test!(invalid_expression_instance_call);
+test!(unknown_class_item_instance);
+test!(unselected_instance);
 test!(type_mismatch_tuple_sizes);
 test!(type_mismatch_array);
+test!(type_mismatch_array_shared);
+test!(type_mismatch_not_generalized);
 test!(type_mismatch_while_items);
+test!(type_mismatch_range);
+test!(type_mismatch_record_update);
+test!(unknown_field);
+test!(type_mismatch_alternatives);
+test!(type_mismatch_match_guard);
 test!(type_mismatch_while_guard);
 test!(type_mismatch_loop_items);
 test!(type_mismatch_if_items);
@@ -59,6 +89,24 @@ test!(type_mismatch_fun_ret);
 test!(type_mismatch_fun_apply_arg_count);
 test!(type_mismatch_fun_apply_arg_type);
 
+test!(invalid_exception_type_int);
+test!(invalid_exception_type_catch);
+test!(ambiguous_exception_type);
+
+test!(immutable_assignment);
+test!(captured_assignment);
+test!(captured_mutable);
+test!(invalid_assignment);
+test!(mutable_signature);
+
+test!(typed_hole);
+test!(typed_hole_underscore);
+
+test!(inconsistent_alternatives);
+test!(invalid_pattern_array_rest);
+test!(non_exhaustive_match);
+test!(unreachable_match_case);
+
 // ------------------------------------------------------------------------
 
 #[derive(Default)]
@@ -82,7 +130,13 @@ impl regex::Replacer for &mut ReportLabelReplacer {
     }
 }
 
-fn report_test(path: impl AsRef<Path>) {
+// the compilation stage whose reports are checked
+pub(super) enum Stage {
+    Syntax,
+    Semantic { with_std: bool },
+}
+
+pub(super) fn report_test(path: impl AsRef<Path>, stage: Stage) {
     let header_regex = Regex::new(r#"--- (\w+)"#).unwrap();
     let label_regex = Regex::new(r"\\\|([^|]*)\|").unwrap();
 
@@ -104,21 +158,29 @@ fn report_test(path: impl AsRef<Path>) {
     // parse and check program
     let mut compiler = com::init();
     compiler.add_source(path.as_ref().display(), &processed_source);
+    if let Stage::Semantic { with_std: true } = stage {
+        compiler.add_marin_std_from(MARIN_DIR);
+    }
     let compiler = compiler.read_sources().parse();
-    assert!(
-        compiler.reports.is_empty(),
-        "processed semantic report test source has syntax errors"
-    );
-    let compiler = compiler.check();
+    let reports = match stage {
+        Stage::Syntax => compiler.reports,
+        Stage::Semantic { .. } => {
+            assert!(
+                compiler.reports.is_empty(),
+                "processed semantic report test source has syntax errors"
+            );
+            compiler.check().reports
+        }
+    };
 
     assert_eq!(
-        compiler.reports.len(),
+        reports.len(),
         1,
-        "semantic test case must only emit one report"
+        "report test case must only emit one report"
     );
 
     // check header name and message
-    let report = &compiler.reports[0];
+    let report = &reports[0];
     assert_eq!(report.header.name(), expected_header_name);
 
     // check label spans and count
@@ -150,7 +212,16 @@ macro_rules! test {
         #[test]
         fn $test_name() {
             let path = format!("./tests/report/semantic/{}.mar", stringify!($test_name));
-            report_test(path);
+            report_test(path, Stage::Semantic { with_std: false });
+        }
+    };
+    (
+        $test_name:ident with std
+    ) => {
+        #[test]
+        fn $test_name() {
+            let path = format!("./tests/report/semantic/{}.mar", stringify!($test_name));
+            report_test(path, Stage::Semantic { with_std: true });
         }
     };
 }
@@ -162,4 +233,5 @@ use test;
 use codespan_reporting::diagnostic::LabelStyle;
 use regex::Regex;
 
+use super::MARIN_DIR;
 use crate::com::{self, loc::Span};
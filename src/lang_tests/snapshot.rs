@@ -0,0 +1,89 @@
+test!(returns_tuple_2);
+test!(block_let_frame);
+test!(loop_break_nested_val);
+test!(fun_capture_fun_3);
+test!(fun_curry_capture_var_3);
+test!(let_generalize_2);
+test!(record_generic_2);
+test!(union_variants_recursive_bba);
+test!(match_tuple_nested_test_success);
+test!(match_union_deconstruct_2_b);
+test!(try_catch_nested_unmatched);
+test!(try_raise_in_function);
+
+#[test]
+fn snapshot_corrupt_heap_count() {
+    let bytecode = compile_snapshot_program("./tests/lang/try_raise_in_function.mar");
+    let mut vm = exe::load_bytecode(&bytecode);
+    assert_eq!(vm.run_steps(8), None);
+    let mut snapshot = Vec::new();
+    vm.save(&mut snapshot).expect("failed to save snapshot");
+    drop(vm);
+
+    // the heap comes right after the tables
+    let mut rest = snapshot.as_slice();
+    let code = exe::VM::read_code(&mut rest).expect("failed to read snapshot code");
+    let mut state = rest.to_vec();
+    binary::read_function_table(&mut rest).expect("failed to read function table");
+    binary::read_layout_table(&mut rest).expect("failed to read layout table");
+    binary::read_export_table(&mut rest).expect("failed to read export table");
+    let heap_start = state.len() - rest.len();
+    state[heap_start..heap_start + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+    assert!(exe::VM::load(&code, &mut state.as_slice()).is_err());
+}
+
+// ------------------------------------------------------------------------
+
+fn compile_snapshot_program(path: impl AsRef<Path>) -> Vec<u8> {
+    let mut compiler = com::init();
+    compiler.add_file(path);
+
+    let compiler = compiler.read_sources().parse().check();
+    assert!(!compiler.is_fatal(), "snapshot test program has errors");
+
+    let compiler = compiler.emit();
+    compiler.into_content().bytecode
+}
+
+fn test_snapshot_resume(path: impl AsRef<Path>) {
+    let bytecode = compile_snapshot_program(path);
+
+    let expected = exe::load_bytecode(&bytecode).run();
+
+    // interrupt the program after each instruction, until it halts on its own
+    for steps in 0.. {
+        let mut vm = exe::load_bytecode(&bytecode);
+        if let Some(value) = vm.run_steps(steps) {
+            assert_eq!(value, expected);
+            break;
+        }
+
+        let mut snapshot = Vec::new();
+        vm.save(&mut snapshot).expect("failed to save snapshot");
+        drop(vm);
+
+        let mut snapshot = snapshot.as_slice();
+        let code = exe::VM::read_code(&mut snapshot).expect("failed to read snapshot code");
+        let mut vm = exe::VM::load(&code, &mut snapshot).expect("failed to load snapshot");
+        let value = vm.run();
+        assert_eq!(value, expected, "resuming after {steps} steps");
+    }
+}
+
+macro_rules! test {
+    (
+        $test_name:ident
+    ) => {
+        #[test]
+        fn $test_name() {
+            let path = format!("./tests/lang/{}.mar", stringify!($test_name));
+            test_snapshot_resume(path);
+        }
+    };
+}
+
+use test;
+
+use crate::{binary, com, exe};
+use std::path::Path;
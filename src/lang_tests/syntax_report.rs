@@ -0,0 +1,20 @@
+test!(unclosed_interpolation_hole);
+test!(unmatched_interpolation_brace);
+
+// ------------------------------------------------------------------------
+
+macro_rules! test {
+    (
+        $test_name:ident
+    ) => {
+        #[test]
+        fn $test_name() {
+            let path = format!("./tests/report/syntax/{}.mar", stringify!($test_name));
+            report_test(path, Stage::Syntax);
+        }
+    };
+}
+
+use test;
+
+use super::semantic_report::{Stage, report_test};
@@ -0,0 +1,88 @@
+test!(trace_unfiltered => trace_functions, [], ["<main>", "first", "second"]);
+test!(trace_filter_one => trace_functions, ["first"], ["first"]);
+test!(trace_filter_unknown => trace_functions, ["second", "third"], ["second"]);
+
+#[test]
+fn trace_filter_unmatched() {
+    let bytecode = compile_trace_program("./tests/lang/trace_functions.mar");
+    let trace = exe::Trace::new(Box::new(io::sink())).with_filter(
+        ["second", "third", "first", "fourth"]
+            .iter()
+            .map(|f| f.to_string()),
+    );
+
+    let mut vm = exe::load_bytecode(&bytecode);
+    assert_eq!(vm.set_trace(trace), ["fourth", "third"]);
+}
+
+// ------------------------------------------------------------------------
+
+// trace output which can still be read once handed to the vm
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl io::Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn compile_trace_program(path: impl AsRef<Path>) -> Vec<u8> {
+    let mut compiler = com::init();
+    compiler.add_file(path);
+
+    let compiler = compiler.read_sources().parse().check();
+    assert!(!compiler.is_fatal(), "trace test program has errors");
+
+    let compiler = compiler.emit();
+    compiler.into_content().bytecode
+}
+
+fn test_trace(path: impl AsRef<Path>, filter: &[&str], expected_functions: &[&str]) {
+    let bytecode = compile_trace_program(path);
+
+    let buffer = SharedBuffer::default();
+    let trace = exe::Trace::new(Box::new(buffer.clone()));
+    let trace = match filter.is_empty() {
+        true => trace,
+        false => trace.with_filter(filter.iter().map(|f| f.to_string())),
+    };
+
+    let mut vm = exe::load_bytecode(&bytecode);
+    vm.set_trace(trace);
+    vm.run();
+
+    let output = String::from_utf8(buffer.0.borrow().clone()).unwrap();
+    let mut traced_functions = output
+        .lines()
+        .map(|line| {
+            let function = line.split(" | ").nth(1).expect("malformed trace line");
+            function.rsplit('.').next().unwrap().to_string()
+        })
+        .collect::<Vec<_>>();
+    traced_functions.sort();
+    traced_functions.dedup();
+
+    assert_eq!(traced_functions, expected_functions);
+}
+
+macro_rules! test {
+    (
+        $test_name:ident => $file:ident, [$($filter:literal),*], [$($expected:literal),*]
+    ) => {
+        #[test]
+        fn $test_name() {
+            let path = format!("./tests/lang/{}.mar", stringify!($file));
+            test_trace(path, &[$($filter),*], &[$($expected),*]);
+        }
+    };
+}
+
+use test;
+
+use crate::{com, exe};
+use std::{cell::RefCell, io, path::Path, rc::Rc};
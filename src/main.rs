@@ -14,11 +14,45 @@ fn main() {
 
     let mut has_std = true;
     let mut show_disassembly = false;
+    let mut snapshot_at = None;
+    let mut snapshot_file = "marin.snapshot".to_string();
+    let mut resume_file = None;
+    let mut trace = None;
+    let mut trace_filter = None;
+    let mut calls = Vec::new();
     for arg in &args {
         match arg.as_str() {
             // options
             "--no-std" => has_std = false,
             "--show-disassembly" => show_disassembly = true,
+            "--warn-orphans" => compiler.warn_orphan_instances(),
+            opt if opt.starts_with("--snapshot-at=") => {
+                let steps = &opt["--snapshot-at=".len()..];
+                let steps = steps
+                    .parse::<usize>()
+                    .unwrap_or_else(|_| panic!("invalid step count '{steps}'"));
+                snapshot_at = Some(steps);
+            }
+            opt if opt.starts_with("--snapshot-file=") => {
+                snapshot_file = opt["--snapshot-file=".len()..].to_string();
+            }
+            opt if opt.starts_with("--resume=") => {
+                resume_file = Some(opt["--resume=".len()..].to_string());
+            }
+            "--trace" => trace = Some(None),
+            opt if opt.starts_with("--trace=") => {
+                trace = Some(Some(opt["--trace=".len()..].to_string()));
+            }
+            opt if opt.starts_with("--trace-filter=") => {
+                let functions = opt["--trace-filter=".len()..].split(',');
+                trace_filter = Some(functions.map(str::to_string).collect::<Vec<_>>());
+            }
+            opt if opt.starts_with("--call=") => {
+                let mut parts = opt["--call=".len()..].split(',');
+                let name = parts.next().unwrap_or_default().to_string();
+                let args = parts.map(parse_call_arg).collect::<Vec<_>>();
+                calls.push((name, args));
+            }
             opt if opt.starts_with("--") => {
                 panic!("unknown option '{opt}'");
             }
@@ -28,32 +62,106 @@ fn main() {
         }
     }
 
-    if has_std {
-        compiler.add_marin_std();
-    }
+    // a snapshot already holds its code, nothing is compiled
+    let code;
+    let mut vm = match resume_file {
+        Some(resume_file) => {
+            let snapshot = std::fs::read(&resume_file)
+                .unwrap_or_else(|e| panic!("couldn't read snapshot '{resume_file}': {e}"));
+            let mut snapshot = snapshot.as_slice();
+            code = exe::VM::read_code(&mut snapshot)
+                .expect("not a marin snapshot (magic bytes mismatch)");
+            exe::VM::load(&code, &mut snapshot).expect("corrupted marin snapshot")
+        }
+        None => {
+            if has_std {
+                compiler.add_marin_std();
+            }
 
-    let compiler = compiler.read_sources().parse().check();
+            let compiler = compiler.read_sources().parse().check();
 
-    use codespan_reporting::term::{self, termcolor::ColorChoice};
-    let color = ColorChoice::AlwaysAnsi;
-    let config = term::Config::default();
-    compiler
-        .emit_reports(color, &config)
-        .expect("failed to emit reports");
-    if compiler.is_fatal() {
-        std::process::exit(1);
-    }
+            use codespan_reporting::term::{self, termcolor::ColorChoice};
+            let color = ColorChoice::AlwaysAnsi;
+            let config = term::Config::default();
+            compiler
+                .emit_reports(color, &config)
+                .expect("failed to emit reports");
+            if compiler.is_fatal() {
+                std::process::exit(1);
+            }
 
-    let compiler = compiler.emit();
-    let bytecode = compiler.into_content().bytecode;
+            let compiler = compiler.emit();
+            code = compiler.into_content().bytecode;
 
-    if show_disassembly {
-        let mut cursor = std::io::Cursor::new(&bytecode);
-        binary::dissasemble(&mut cursor).unwrap();
-    }
+            if show_disassembly {
+                let mut cursor = std::io::Cursor::new(&code);
+                binary::dissasemble(&mut cursor).unwrap();
+            }
+
+            println!();
+            exe::load_bytecode(&code)
+        }
+    };
 
-    println!();
+    if let Some(trace_file) = trace {
+        let out: Box<dyn std::io::Write> = match trace_file {
+            Some(trace_file) => Box::new(std::io::BufWriter::new(
+                std::fs::File::create(&trace_file)
+                    .unwrap_or_else(|e| panic!("couldn't create trace file '{trace_file}': {e}")),
+            )),
+            None => Box::new(std::io::stderr()),
+        };
+        let trace = match trace_filter {
+            Some(functions) => exe::Trace::new(out).with_filter(functions),
+            None => exe::Trace::new(out),
+        };
+        for name in vm.set_trace(trace) {
+            println!(
+                "{} trace filter '{name}' matches no function",
+                "warning:".yellow().bold()
+            );
+        }
+    }
 
-    let value = exe::run_bytecode(&bytecode);
+    let value = match snapshot_at {
+        Some(steps) => match vm.run_steps(steps) {
+            Some(value) => value,
+            None => {
+                let mut snapshot = Vec::new();
+                vm.save(&mut snapshot).expect("failed to save snapshot");
+                std::fs::write(&snapshot_file, snapshot)
+                    .unwrap_or_else(|e| panic!("couldn't write snapshot '{snapshot_file}': {e}"));
+                println!("-> snapshot saved to '{}'", snapshot_file.bold());
+                return;
+            }
+        },
+        None => vm.run(),
+    };
     println!("-> {}", value.to_string().green());
+
+    for (name, args) in calls {
+        let result = vm
+            .get_function(&name)
+            .and_then(|function| vm.call(function, &args))
+            .unwrap_or_else(|e| panic!("couldn't call '{name}': {e}"));
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        println!(
+            "{name}({}) -> {}",
+            args.join(", "),
+            result.to_string().green()
+        );
+    }
+}
+
+// arguments are integers, floats and booleans, or strings otherwise
+fn parse_call_arg(arg: &str) -> exe::Value {
+    if let Ok(n) = arg.parse() {
+        exe::Value::Int(n)
+    } else if let Ok(f) = arg.parse() {
+        exe::Value::Float(f)
+    } else if let Ok(b) = arg.parse() {
+        exe::Value::Bool(b)
+    } else {
+        exe::Value::String(arg.to_string())
+    }
 }